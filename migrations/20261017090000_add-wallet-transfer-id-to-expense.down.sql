@@ -0,0 +1,3 @@
+-- Down
+ALTER TABLE expense
+DROP COLUMN wallet_transfer_id;
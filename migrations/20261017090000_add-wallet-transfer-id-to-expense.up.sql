@@ -0,0 +1,5 @@
+-- Up
+ALTER TABLE expense
+ADD COLUMN wallet_transfer_id INTEGER REFERENCES wallet_transfer(id);
+
+CREATE INDEX ON expense(wallet_transfer_id);
@@ -0,0 +1,3 @@
+-- Up
+ALTER TABLE expense
+ADD COLUMN is_refund BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- Down
+ALTER TABLE income
+DROP COLUMN created_by;
+
+ALTER TABLE expense
+DROP COLUMN created_by;
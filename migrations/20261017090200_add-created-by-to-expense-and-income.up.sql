@@ -0,0 +1,6 @@
+-- Up
+ALTER TABLE expense
+ADD COLUMN created_by TEXT;
+
+ALTER TABLE income
+ADD COLUMN created_by TEXT;
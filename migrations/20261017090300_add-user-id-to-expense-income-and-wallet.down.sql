@@ -0,0 +1,9 @@
+-- Down
+ALTER TABLE income
+DROP COLUMN user_id;
+
+ALTER TABLE expense
+DROP COLUMN user_id;
+
+ALTER TABLE wallet
+DROP COLUMN user_id;
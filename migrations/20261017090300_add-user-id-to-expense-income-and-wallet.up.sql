@@ -0,0 +1,13 @@
+-- Up
+ALTER TABLE wallet
+ADD COLUMN user_id TEXT;
+
+ALTER TABLE expense
+ADD COLUMN user_id TEXT;
+
+ALTER TABLE income
+ADD COLUMN user_id TEXT;
+
+CREATE INDEX ON wallet(user_id);
+CREATE INDEX ON expense(user_id);
+CREATE INDEX ON income(user_id);
@@ -0,0 +1,4 @@
+-- Down
+DROP TRIGGER update_category_budget_updated_at ON category_budget;
+
+DROP TABLE category_budget;
@@ -0,0 +1,15 @@
+-- Up
+CREATE TABLE category_budget (
+    id SERIAL PRIMARY KEY,
+    category_id INTEGER NOT NULL REFERENCES category(id),
+    user_id TEXT,
+    monthly_limit INTEGER NOT NULL CHECK (monthly_limit >= 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT category_budget_category_user_unique UNIQUE NULLS NOT DISTINCT (category_id, user_id)
+);
+
+CREATE TRIGGER update_category_budget_updated_at
+BEFORE UPDATE ON category_budget
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
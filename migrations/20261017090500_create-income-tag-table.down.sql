@@ -0,0 +1,4 @@
+-- Down
+DROP TRIGGER update_income_tag_updated_at ON income_tag;
+
+DROP TABLE income_tag;
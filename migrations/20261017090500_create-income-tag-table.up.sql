@@ -0,0 +1,17 @@
+-- Up
+CREATE TABLE income_tag (
+    income_id INTEGER NOT NULL REFERENCES income(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tag(id),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (income_id, tag_id)
+);
+
+CREATE INDEX ON income_tag(income_id);
+
+CREATE INDEX ON income_tag(tag_id);
+
+CREATE TRIGGER update_income_tag_updated_at
+BEFORE UPDATE ON income_tag
+FOR EACH ROW
+EXECUTE PROCEDURE update_updated_at_column();
@@ -0,0 +1,3 @@
+-- Down
+ALTER TABLE category
+DROP COLUMN require_description;
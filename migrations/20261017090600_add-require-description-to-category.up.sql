@@ -0,0 +1,3 @@
+-- Up
+ALTER TABLE category
+ADD COLUMN require_description BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- Down
+DROP INDEX expense_user_external_id_unique;
+
+ALTER TABLE expense
+DROP COLUMN external_id;
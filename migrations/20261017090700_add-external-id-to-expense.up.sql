@@ -0,0 +1,7 @@
+-- Up
+ALTER TABLE expense
+ADD COLUMN external_id TEXT;
+
+CREATE UNIQUE INDEX expense_user_external_id_unique
+ON expense (user_id, external_id) NULLS NOT DISTINCT
+WHERE external_id IS NOT NULL;
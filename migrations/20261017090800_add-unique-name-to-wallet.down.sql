@@ -0,0 +1,3 @@
+-- Down
+-- The wallets renamed to resolve duplicate names keep their new name.
+DROP INDEX wallet_user_name_unique;
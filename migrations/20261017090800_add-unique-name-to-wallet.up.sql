@@ -0,0 +1,42 @@
+-- Up
+-- Wallets named like an older wallet of the same user, case-insensitively, would fail the unique index.
+-- They are renamed by appending their ID, so that the oldest wallet keeps its name.
+-- The ID is appended again as long as another wallet of the user already has the new name.
+DO $$
+DECLARE
+    duplicate RECORD;
+    new_name TEXT;
+BEGIN
+    FOR duplicate IN
+        SELECT w.id, w.name, w.user_id
+        FROM wallet w
+        WHERE EXISTS (
+            SELECT 1
+            FROM wallet older
+            WHERE
+                older.user_id IS NOT DISTINCT FROM w.user_id
+                AND LOWER(older.name) = LOWER(w.name)
+                AND older.id < w.id
+        )
+        ORDER BY w.id
+    LOOP
+        new_name := duplicate.name || ' (' || duplicate.id || ')';
+
+        WHILE EXISTS (
+            SELECT 1
+            FROM wallet other
+            WHERE
+                other.user_id IS NOT DISTINCT FROM duplicate.user_id
+                AND LOWER(other.name) = LOWER(new_name)
+        ) LOOP
+            new_name := new_name || ' (' || duplicate.id || ')';
+        END LOOP;
+
+        UPDATE wallet SET name = new_name WHERE id = duplicate.id;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+-- NULLS NOT DISTINCT requires PostgreSQL 15 or later.
+CREATE UNIQUE INDEX wallet_user_name_unique
+ON wallet (user_id, LOWER(name)) NULLS NOT DISTINCT;
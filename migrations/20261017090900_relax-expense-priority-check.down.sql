@@ -0,0 +1,7 @@
+-- Down
+-- Fails while expenses with a priority above 2 exist.
+ALTER TABLE expense
+DROP CONSTRAINT expense_priority_check;
+
+ALTER TABLE expense
+ADD CONSTRAINT expense_priority_check CHECK (priority >= 0 AND priority <= 2);
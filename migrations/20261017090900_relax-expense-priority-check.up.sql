@@ -0,0 +1,7 @@
+-- Up
+-- The lowest priority level is configured with the `MAX_PRIORITY` environment variable and validated by the app.
+ALTER TABLE expense
+DROP CONSTRAINT expense_priority_check;
+
+ALTER TABLE expense
+ADD CONSTRAINT expense_priority_check CHECK (priority >= 0);
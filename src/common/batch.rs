@@ -0,0 +1,45 @@
+use axum::http::StatusCode;
+use lazy_static::lazy_static;
+
+use crate::{
+    common::{env, errors::AppError},
+    constants::DEFAULT_MAX_BATCH_SIZE,
+};
+
+lazy_static! {
+    /// The maximum number of items of a bulk save, read once from the `MAX_BATCH_SIZE` environment variable.
+    static ref MAX_BATCH_SIZE: usize = env::parse_or("MAX_BATCH_SIZE", DEFAULT_MAX_BATCH_SIZE);
+}
+
+/// Rejects batches with more items than the given maximum.
+fn check_batch_size(len: usize, max_batch_size: usize) -> Result<(), AppError> {
+    if len > max_batch_size {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            format!("Batch too large: {len} items exceeds the maximum of {max_batch_size}"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Rejects batches with more items than the configured maximum batch size, so that a single
+/// bulk save doesn't hold a transaction open for too long.
+pub fn ensure_batch_size(len: usize) -> Result<(), AppError> {
+    check_batch_size(len, *MAX_BATCH_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_batch_size_within_limit() {
+        assert!(check_batch_size(500, 500).is_ok());
+    }
+
+    #[test]
+    fn test_check_batch_size_exceeds_limit() {
+        assert!(check_batch_size(501, 500).is_err());
+    }
+}
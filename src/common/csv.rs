@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+
+/// Escapes a single CSV field.
+/// Fields containing a delimiter, a quote, or a line break are wrapped in quotes,
+/// and any quote inside them is doubled.
+pub fn escape(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        return Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")));
+    }
+
+    Cow::Borrowed(field)
+}
+
+/// Joins the given fields into a single CSV line, terminated by a line break.
+pub fn line<I, S>(fields: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut line = fields
+        .into_iter()
+        .map(|field| escape(field.as_ref()).into_owned())
+        .collect::<Vec<String>>()
+        .join(",");
+    line.push('\n');
+
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_plain_field() {
+        assert_eq!(escape("Salary"), "Salary");
+    }
+
+    #[test]
+    fn test_escape_field_with_delimiter() {
+        assert_eq!(escape("Salary, April"), "\"Salary, April\"");
+    }
+
+    #[test]
+    fn test_escape_field_with_quote() {
+        assert_eq!(escape("The \"big\" bonus"), "\"The \"\"big\"\" bonus\"");
+    }
+
+    #[test]
+    fn test_escape_field_with_line_break() {
+        assert_eq!(escape("first\nsecond"), "\"first\nsecond\"");
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(line(["1", "1000", "a, b", ""]), "1,1000,\"a, b\",\n");
+    }
+}
@@ -1,31 +1,38 @@
 use serde::{Deserialize, de};
-use time::{Date, macros::format_description};
+use std::{fmt, marker::PhantomData};
+use time::{Date, Duration, OffsetDateTime, macros::format_description};
+
+use crate::common::priority;
+
+/// Parses a date in the `YYYY-MM-DD` format, or one of the `today` and `yesterday` keywords.
+/// The keywords are resolved against the current UTC date of the server.
+fn parse_date(date_str: &str) -> Result<Date, time::error::Parse> {
+    match date_str {
+        "today" => Ok(OffsetDateTime::now_utc().date()),
+        "yesterday" => Ok(OffsetDateTime::now_utc().date() - Duration::DAY),
+        _ => Date::parse(date_str, format_description!("[year]-[month]-[day]")),
+    }
+}
 
 /// Deserialize a raw input into a [`time::Date`] object.
+/// Accepts the `YYYY-MM-DD` format along with the `today` and `yesterday` keywords.
 pub fn date<'de, D>(deserializer: D) -> Result<Date, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     let date_str = Deserialize::deserialize(deserializer)?;
-    let format = format_description!("[year]-[month]-[day]");
-    Date::parse(date_str, &format).map_err(de::Error::custom)
+    parse_date(date_str).map_err(de::Error::custom)
 }
 
 /// Deserialize a raw optional input into a [`time::Date`] object.
+/// Accepts the `YYYY-MM-DD` format along with the `today` and `yesterday` keywords.
 /// Invalid inputs will be converted to `None`.
 pub fn optional_date<'de, D>(deserializer: D) -> Result<Option<Date>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    let date_str = Deserialize::deserialize(deserializer)?;
-    let format = format_description!("[year]-[month]-[day]");
-    match date_str {
-        Some(date_str) => match Date::parse(date_str, &format).map(Some) {
-            Ok(date) => Ok(date),
-            Err(_) => Ok(None),
-        },
-        None => Ok(None),
-    }
+    let date_str: Option<&str> = Deserialize::deserialize(deserializer)?;
+    Ok(date_str.and_then(|date_str| parse_date(date_str).ok()))
 }
 
 /// Deserialize a raw input into an optional value of type `S`.
@@ -53,12 +60,60 @@ where
     }
 }
 
+/// Deserialize a raw optional input into a [`time::Date`] object.
+/// Accepts the same inputs as [`date`], but unlike [`optional_date`], invalid inputs will result in an error.
+pub fn optional_strict_date<'de, D>(deserializer: D) -> Result<Option<Date>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let date_str: Option<&str> = Deserialize::deserialize(deserializer)?;
+    date_str
+        .map(|date_str| parse_date(date_str).map_err(de::Error::custom))
+        .transpose()
+}
+
+/// Deserialize a raw input given either as a number or as a numeric string into an integer.
+/// Non-numeric strings will result in an error.
+fn lenient_int<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct LenientIntVisitor;
+
+    impl de::Visitor<'_> for LenientIntVisitor {
+        type Value = i32;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an integer or a numeric string")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            i32::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            i32::try_from(value)
+                .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(LenientIntVisitor)
+}
+
 /// Deserialize a raw input into a non-negative integer.
+/// Numeric strings are accepted as well, see [`lenient_int`].
 pub fn non_negative_int<'de, D>(deserializer: D) -> Result<i32, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    let value = i32::deserialize(deserializer)?;
+    let value = lenient_int(deserializer)?;
     if value < 0 {
         return Err(de::Error::custom("Value must be non-negative"));
     }
@@ -67,12 +122,12 @@ where
 }
 
 /// Deserialize a raw input into a positive integer.
-/// Invalid input will result in an error.
+/// Numeric strings are accepted as well, see [`lenient_int`]. Invalid input will result in an error.
 pub fn positive_int<'de, D>(deserializer: D) -> Result<i32, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    let value = i32::deserialize(deserializer)?;
+    let value = lenient_int(deserializer)?;
     if value < 1 {
         return Err(de::Error::custom("Value must be positive"));
     }
@@ -80,6 +135,76 @@ where
     Ok(value)
 }
 
+/// Deserialize a raw optional input into a positive integer.
+/// Invalid input will result in an error.
+pub fn optional_positive_int<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<i32>::deserialize(deserializer)?;
+    if let Some(value) = value
+        && value < 1
+    {
+        return Err(de::Error::custom("Value must be positive"));
+    }
+
+    Ok(value)
+}
+
+/// Deserialize a batch given either as a bare array of items, or as an object holding the array in the given field.
+/// Unknown fields of the object are ignored.
+pub fn bare_or_wrapped_vec<'de, D, T>(
+    deserializer: D,
+    field: &'static str,
+) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct BatchVisitor<T> {
+        field: &'static str,
+        marker: PhantomData<T>,
+    }
+
+    impl<'de, T: Deserialize<'de>> de::Visitor<'de> for BatchVisitor<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "an array or an object with an `{}` array",
+                self.field
+            )
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            Vec::deserialize(de::value::SeqAccessDeserializer::new(seq))
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut items = None;
+
+            while let Some(key) = map.next_key::<String>()? {
+                if key != self.field {
+                    map.next_value::<de::IgnoredAny>()?;
+                    continue;
+                }
+                if items.is_some() {
+                    return Err(de::Error::duplicate_field(self.field));
+                }
+                items = Some(map.next_value()?);
+            }
+
+            items.ok_or_else(|| de::Error::missing_field(self.field))
+        }
+    }
+
+    deserializer.deserialize_any(BatchVisitor {
+        field,
+        marker: PhantomData,
+    })
+}
+
 /// Deserialize a raw input into a vector of positive integers.
 /// Invalid input will result in an error.
 pub fn positive_int_vec<'de, D>(deserializer: D) -> Result<Vec<i32>, D::Error>
@@ -96,18 +221,86 @@ where
     Ok(values)
 }
 
+/// Deserialize a raw optional input into a vector of positive integers.
+/// Invalid input will result in an error.
+pub fn optional_positive_int_vec<'de, D>(deserializer: D) -> Result<Option<Vec<i32>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let values = Option::<Vec<i32>>::deserialize(deserializer)?;
+    if let Some(values) = &values
+        && values.iter().any(|&value| value < 1)
+    {
+        return Err(de::Error::custom("Value must be positive"));
+    }
+
+    Ok(values)
+}
+
+/// Deserialize a raw comma-separated input into a vector of positive integers.
+/// Empty or invalid inputs will be converted to `None`.
+pub fn optional_comma_separated_ids<'de, D>(deserializer: D) -> Result<Option<Vec<i32>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    let ids = value.and_then(|value| {
+        value
+            .split(',')
+            .map(|id| id.trim().parse::<i32>().ok().filter(|id| *id > 0))
+            .collect::<Option<Vec<i32>>>()
+    });
+
+    Ok(ids.filter(|ids| !ids.is_empty()))
+}
+
 /// Deserialize a raw input into a priority value.
-/// A valid priority value is between 0 and 2.
+/// A valid priority value is between 0 and the configured maximum priority, which defaults to 2.
 pub fn priority_value<'de, D>(deserializer: D) -> Result<i32, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     let value = i32::deserialize(deserializer)?;
-    if !(0..=2).contains(&value) {
-        return Err(de::Error::custom("Priority must be between 0 and 2"));
+    priority::check_priority(value, priority::max_priority()).map_err(de::Error::custom)
+}
+
+/// Deserialize a raw input into a trimmed, non-empty string.
+/// Empty or whitespace-only input will result in an error.
+pub fn non_empty_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(de::Error::custom("Value must not be empty"));
     }
 
-    Ok(value)
+    Ok(trimmed.to_string())
+}
+
+/// Deserialize a raw optional input into a trimmed string.
+/// Empty or whitespace-only input will be converted to `None`.
+pub fn optional_non_empty_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty()))
+}
+
+/// Deserialize a raw input that may be explicitly `null` into a trimmed string.
+/// Meant to be used along with `default`, so a missing field stays `None`,
+/// while `null`, empty or whitespace-only input will be converted to `Some(None)`.
+pub fn nullable_non_empty_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    optional_non_empty_string(deserializer).map(Some)
 }
 
 #[cfg(test)]
@@ -152,6 +345,29 @@ mod tests {
         values: Vec<i32>,
     }
 
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct OptionalPositiveIntVecTestStruct {
+        #[serde(deserialize_with = "optional_positive_int_vec", default)]
+        values: Option<Vec<i32>>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct OptionalCommaSeparatedIdsTestStruct {
+        #[serde(deserialize_with = "optional_comma_separated_ids", default)]
+        ids: Option<Vec<i32>>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct BatchTestStruct {
+        items: Vec<i32>,
+    }
+
+    impl<'de> Deserialize<'de> for BatchTestStruct {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            bare_or_wrapped_vec(deserializer, "items").map(|items| Self { items })
+        }
+    }
+
     #[derive(Debug, Deserialize, PartialEq)]
     struct PriorityTestStruct {
         #[serde(deserialize_with = "priority_value")]
@@ -164,6 +380,36 @@ mod tests {
         value: Option<u32>,
     }
 
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct NonEmptyStringTestStruct {
+        #[serde(deserialize_with = "non_empty_string")]
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct OptionalNonEmptyStringTestStruct {
+        #[serde(deserialize_with = "optional_non_empty_string", default)]
+        description: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct OptionalStrictDateTestStruct {
+        #[serde(deserialize_with = "optional_strict_date", default)]
+        date: Option<Date>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct OptionalPositiveIntTestStruct {
+        #[serde(deserialize_with = "optional_positive_int", default)]
+        value: Option<i32>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct NullableNonEmptyStringTestStruct {
+        #[serde(deserialize_with = "nullable_non_empty_string", default)]
+        description: Option<Option<String>>,
+    }
+
     #[test]
     fn test_date_happy() {
         let json_str = r#"{
@@ -174,6 +420,36 @@ mod tests {
         assert_eq!(test_struct.date, expected_date);
     }
 
+    #[test]
+    fn test_date_today() {
+        let json_str = r#"{
+            "date": "today"
+        }"#;
+        let test_struct: TestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.date, OffsetDateTime::now_utc().date());
+    }
+
+    #[test]
+    fn test_date_yesterday() {
+        let json_str = r#"{
+            "date": "yesterday"
+        }"#;
+        let test_struct: TestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(
+            test_struct.date,
+            OffsetDateTime::now_utc().date().previous_day().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_date_unknown_keyword() {
+        let json_str = r#"{
+            "date": "tomorrow"
+        }"#;
+        let result = serde_json::from_str::<TestStruct>(json_str);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_date_invalid_date() {
         let json_str = r#"{
@@ -202,6 +478,15 @@ mod tests {
         assert_eq!(test_struct.date, Some(expected_date));
     }
 
+    #[test]
+    fn test_optional_date_today() {
+        let json_str = r#"{
+            "date": "today"
+        }"#;
+        let test_struct: OptionalTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.date, Some(OffsetDateTime::now_utc().date()));
+    }
+
     #[test]
     fn test_optional_date_invalid_date() {
         let json_str = r#"{
@@ -250,6 +535,54 @@ mod tests {
         assert_eq!(test_struct.value, 10);
     }
 
+    #[test]
+    fn test_non_negative_int_numeric_string() {
+        let json_str = r#"{
+            "value": "10"
+        }"#;
+        let test_struct: NonNegativeIntTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.value, 10);
+    }
+
+    #[test]
+    fn test_non_negative_int_negative_numeric_string() {
+        let json_str = r#"{
+            "value": "-5"
+        }"#;
+        let result = serde_json::from_str::<NonNegativeIntTestStruct>(json_str);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Value must be non-negative")
+        );
+    }
+
+    #[test]
+    fn test_non_negative_int_non_numeric_string() {
+        let json_str = r#"{
+            "value": "abc"
+        }"#;
+        let result = serde_json::from_str::<NonNegativeIntTestStruct>(json_str);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("expected an integer or a numeric string")
+        );
+    }
+
+    #[test]
+    fn test_non_negative_int_out_of_range() {
+        let json_str = r#"{
+            "value": 2147483648
+        }"#;
+        let result = serde_json::from_str::<NonNegativeIntTestStruct>(json_str);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_non_negative_int_zero() {
         let json_str = r#"{
@@ -283,6 +616,24 @@ mod tests {
         assert_eq!(test_struct.value, 10);
     }
 
+    #[test]
+    fn test_positive_int_numeric_string() {
+        let json_str = r#"{
+            "value": " 10 "
+        }"#;
+        let test_struct: PositiveIntTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.value, 10);
+    }
+
+    #[test]
+    fn test_positive_int_non_numeric_string() {
+        let json_str = r#"{
+            "value": "abc"
+        }"#;
+        let result = serde_json::from_str::<PositiveIntTestStruct>(json_str);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_positive_int_zero() {
         let json_str = r#"{
@@ -361,6 +712,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_optional_positive_int_vec_happy() {
+        let json_str = r#"{
+            "values": [1, 2, 3]
+        }"#;
+        let test_struct: OptionalPositiveIntVecTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.values, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_optional_positive_int_vec_empty() {
+        let json_str = r#"{
+            "values": []
+        }"#;
+        let test_struct: OptionalPositiveIntVecTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.values, Some(vec![]));
+    }
+
+    #[test]
+    fn test_optional_positive_int_vec_missing() {
+        let test_struct: OptionalPositiveIntVecTestStruct = serde_json::from_str("{}").unwrap();
+        assert_eq!(test_struct.values, None);
+    }
+
+    #[test]
+    fn test_optional_positive_int_vec_with_zero() {
+        let json_str = r#"{
+            "values": [1, 0]
+        }"#;
+        let result = serde_json::from_str::<OptionalPositiveIntVecTestStruct>(json_str);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Value must be positive")
+        );
+    }
+
+    #[test]
+    fn test_optional_comma_separated_ids_happy() {
+        let json_str = r#"{
+            "ids": "1, 2,3"
+        }"#;
+        let test_struct: OptionalCommaSeparatedIdsTestStruct =
+            serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.ids, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_optional_comma_separated_ids_empty() {
+        let json_str = r#"{
+            "ids": ""
+        }"#;
+        let test_struct: OptionalCommaSeparatedIdsTestStruct =
+            serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.ids, None);
+    }
+
+    #[test]
+    fn test_optional_comma_separated_ids_invalid() {
+        let json_str = r#"{
+            "ids": "1,x,0"
+        }"#;
+        let test_struct: OptionalCommaSeparatedIdsTestStruct =
+            serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.ids, None);
+    }
+
     #[test]
     fn test_priority_value_valid() {
         let valid_values = [0, 1, 2];
@@ -409,6 +829,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bare_or_wrapped_vec_both_shapes() {
+        let bare: BatchTestStruct = serde_json::from_str("[1, 2]").unwrap();
+        let wrapped: BatchTestStruct =
+            serde_json::from_str(r#"{ "items": [1, 2], "unknown": true }"#).unwrap();
+
+        assert_eq!(bare, BatchTestStruct { items: vec![1, 2] });
+        assert_eq!(bare, wrapped);
+    }
+
+    #[test]
+    fn test_bare_or_wrapped_vec_missing_field() {
+        let result = serde_json::from_str::<BatchTestStruct>(r#"{ "other": [1, 2] }"#);
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("missing field `items`")
+        );
+    }
+
+    #[test]
+    fn test_bare_or_wrapped_vec_invalid_shape() {
+        let result = serde_json::from_str::<BatchTestStruct>("42");
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_from_str_valid() {
         let json_str = r#"{
@@ -435,4 +884,160 @@ mod tests {
         let result = serde_json::from_str::<FromStrTestStruct>(json_str);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_non_empty_string_happy() {
+        let json_str = r#"{
+            "name": "  Groceries "
+        }"#;
+        let test_struct: NonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.name, "Groceries");
+    }
+
+    #[test]
+    fn test_non_empty_string_empty() {
+        let json_str = r#"{
+            "name": ""
+        }"#;
+
+        let result = serde_json::from_str::<NonEmptyStringTestStruct>(json_str);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Value must not be empty")
+        );
+    }
+
+    #[test]
+    fn test_non_empty_string_whitespace_only() {
+        let json_str = r#"{
+            "name": " \t\n "
+        }"#;
+
+        let result = serde_json::from_str::<NonEmptyStringTestStruct>(json_str);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Value must not be empty")
+        );
+    }
+
+    #[test]
+    fn test_optional_non_empty_string_happy() {
+        let json_str = r#"{
+            "description": " x "
+        }"#;
+        let test_struct: OptionalNonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.description, Some("x".to_string()));
+    }
+
+    #[test]
+    fn test_optional_non_empty_string_empty() {
+        let json_str = r#"{
+            "description": ""
+        }"#;
+        let test_struct: OptionalNonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.description, None);
+    }
+
+    #[test]
+    fn test_optional_non_empty_string_whitespace_only() {
+        let json_str = r#"{
+            "description": "  "
+        }"#;
+        let test_struct: OptionalNonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.description, None);
+    }
+
+    #[test]
+    fn test_optional_non_empty_string_null() {
+        let json_str = r#"{
+            "description": null
+        }"#;
+        let test_struct: OptionalNonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.description, None);
+    }
+
+    #[test]
+    fn test_optional_non_empty_string_missing() {
+        let test_struct: OptionalNonEmptyStringTestStruct = serde_json::from_str("{}").unwrap();
+        assert_eq!(test_struct.description, None);
+    }
+
+    #[test]
+    fn test_optional_strict_date_happy() {
+        let json_str = r#"{
+            "date": "2025-04-01"
+        }"#;
+        let test_struct: OptionalStrictDateTestStruct = serde_json::from_str(json_str).unwrap();
+        let expected_date = Date::from_calendar_date(2025, time::Month::April, 1).unwrap();
+        assert_eq!(test_struct.date, Some(expected_date));
+    }
+
+    #[test]
+    fn test_optional_strict_date_missing() {
+        let test_struct: OptionalStrictDateTestStruct = serde_json::from_str("{}").unwrap();
+        assert_eq!(test_struct.date, None);
+    }
+
+    #[test]
+    fn test_optional_strict_date_invalid() {
+        let json_str = r#"{
+            "date": "2025-13-01"
+        }"#;
+        let result = serde_json::from_str::<OptionalStrictDateTestStruct>(json_str);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_optional_positive_int_happy() {
+        let json_str = r#"{
+            "value": 5
+        }"#;
+        let test_struct: OptionalPositiveIntTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.value, Some(5));
+    }
+
+    #[test]
+    fn test_optional_positive_int_missing() {
+        let test_struct: OptionalPositiveIntTestStruct = serde_json::from_str("{}").unwrap();
+        assert_eq!(test_struct.value, None);
+    }
+
+    #[test]
+    fn test_optional_positive_int_zero() {
+        let json_str = r#"{
+            "value": 0
+        }"#;
+        let result = serde_json::from_str::<OptionalPositiveIntTestStruct>(json_str);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_nullable_non_empty_string_happy() {
+        let json_str = r#"{
+            "description": " x "
+        }"#;
+        let test_struct: NullableNonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.description, Some(Some("x".to_string())));
+    }
+
+    #[test]
+    fn test_nullable_non_empty_string_null() {
+        let json_str = r#"{
+            "description": null
+        }"#;
+        let test_struct: NullableNonEmptyStringTestStruct = serde_json::from_str(json_str).unwrap();
+        assert_eq!(test_struct.description, Some(None));
+    }
+
+    #[test]
+    fn test_nullable_non_empty_string_missing() {
+        let test_struct: NullableNonEmptyStringTestStruct = serde_json::from_str("{}").unwrap();
+        assert_eq!(test_struct.description, None);
+    }
 }
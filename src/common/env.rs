@@ -0,0 +1,93 @@
+use std::{env, str::FromStr};
+
+/// Reads an environment variable and parses it into a value of type `T`.
+/// Falls back to `default` when the variable is unset or cannot be parsed.
+pub fn parse_or<T: FromStr>(key: &str, default: T) -> T {
+    match env::var(key) {
+        Ok(raw_value) => raw_value.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("Invalid value for {key}, falling back to the default");
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// Reads a comma-separated environment variable and parses each element into a value of type `T`.
+/// Falls back to `default` when the variable is unset or any element cannot be parsed.
+pub fn parse_list_or<T: FromStr>(key: &str, default: Vec<T>) -> Vec<T> {
+    match env::var(key) {
+        Ok(raw_value) => raw_value
+            .split(',')
+            .map(str::trim)
+            .filter(|element| !element.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<T>, _>>()
+            .unwrap_or_else(|_| {
+                tracing::warn!("Invalid value for {key}, falling back to the default");
+                default
+            }),
+        Err(_) => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_parse_or_valid_value() {
+        unsafe { env::set_var("REKAPUNDI_TEST_ENV", " 42 ") };
+
+        assert_eq!(parse_or("REKAPUNDI_TEST_ENV", 7_u64), 42);
+
+        unsafe { env::remove_var("REKAPUNDI_TEST_ENV") };
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_or_invalid_value() {
+        unsafe { env::set_var("REKAPUNDI_TEST_ENV", "not_a_number") };
+
+        assert_eq!(parse_or("REKAPUNDI_TEST_ENV", 7_u64), 7);
+
+        unsafe { env::remove_var("REKAPUNDI_TEST_ENV") };
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_or_missing_value() {
+        unsafe { env::remove_var("REKAPUNDI_TEST_ENV") };
+
+        assert_eq!(parse_or("REKAPUNDI_TEST_ENV", 7_u64), 7);
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_list_or_valid_value() {
+        unsafe { env::set_var("REKAPUNDI_TEST_ENV", " 4, 2 ,") };
+
+        assert_eq!(parse_list_or("REKAPUNDI_TEST_ENV", vec![7_i32]), vec![4, 2]);
+
+        unsafe { env::remove_var("REKAPUNDI_TEST_ENV") };
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_list_or_invalid_value() {
+        unsafe { env::set_var("REKAPUNDI_TEST_ENV", "4,x") };
+
+        assert_eq!(parse_list_or("REKAPUNDI_TEST_ENV", vec![7_i32]), vec![7]);
+
+        unsafe { env::remove_var("REKAPUNDI_TEST_ENV") };
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_list_or_missing_value() {
+        unsafe { env::remove_var("REKAPUNDI_TEST_ENV") };
+
+        assert_eq!(parse_list_or("REKAPUNDI_TEST_ENV", vec![7_i32]), vec![7]);
+    }
+}
@@ -1,17 +1,50 @@
 use axum::{
     Json,
-    extract::rejection::{JsonRejection, PathRejection},
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
     http::StatusCode,
     response::IntoResponse,
 };
 use serde::Serialize;
-use sqlx::error::ErrorKind::{ForeignKeyViolation, NotNullViolation, UniqueViolation};
+use sqlx::error::ErrorKind::{
+    CheckViolation, ForeignKeyViolation, NotNullViolation, UniqueViolation,
+};
+use std::borrow::Cow;
+
+use crate::middlewares::request_id::REQUEST_ID;
 
 /// Custom error type for API responses.
 #[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
 struct ErrorResponse {
     /// Error message.
     message: String,
+    /// The ID of the request that caused the error, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+/// Response body listing every invalid field of a request.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+struct FieldErrorsResponse {
+    /// The invalid fields.
+    errors: Vec<FieldError>,
+    /// The ID of the request that caused the error, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+/// Describes why a single field of a request is invalid.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct FieldError {
+    /// The index of the item within a batch request, if the request is a batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// The name of the invalid field, as sent by the client.
+    pub field: Cow<'static, str>,
+    /// The reason why the field is invalid.
+    pub reason: String,
 }
 
 /// Enum representing different types of application errors.
@@ -21,17 +54,35 @@ pub enum AppError {
     JsonRejection(JsonRejection),
     /// Error related to Axum's Path extraction.
     PathRejection(PathRejection),
+    /// Error related to Axum's Query extraction.
+    QueryRejection(QueryRejection),
     /// Error related to SQLx database operations.
     SqlxError(sqlx::Error),
     /// Represents a generic status code error.
     StatusCode(StatusCode),
+    /// Represents a status code error with a message explaining it to the client.
+    Message(StatusCode, String),
+    /// The request references records that don't exist.
+    InvalidReferences(Vec<FieldError>),
+    /// The request body is well-formed, but some of its fields hold invalid values.
+    Validation(Vec<FieldError>),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
         let (status, message) = match self {
+            AppError::InvalidReferences(errors) => {
+                return field_errors_response(StatusCode::BAD_REQUEST, errors);
+            }
+
+            AppError::Validation(errors) => {
+                return field_errors_response(StatusCode::UNPROCESSABLE_ENTITY, errors);
+            }
+
             AppError::StatusCode(status) => (status, None),
 
+            AppError::Message(status, message) => (status, Some(message)),
+
             AppError::JsonRejection(rejection) => (rejection.status(), Some(rejection.body_text())),
 
             AppError::PathRejection(rejection) => (
@@ -39,6 +90,10 @@ impl IntoResponse for AppError {
                 Some("Invalid path parameter".to_string()),
             ),
 
+            AppError::QueryRejection(rejection) => {
+                (rejection.status(), Some(rejection.body_text()))
+            }
+
             AppError::SqlxError(error) => match error {
                 sqlx::Error::Database(db_error) => match db_error.kind() {
                     UniqueViolation | ForeignKeyViolation | NotNullViolation => {
@@ -46,6 +101,11 @@ impl IntoResponse for AppError {
                         (StatusCode::CONFLICT, None)
                     }
 
+                    CheckViolation => {
+                        tracing::debug!("{:?}", db_error.to_string());
+                        (StatusCode::UNPROCESSABLE_ENTITY, None)
+                    }
+
                     _ => {
                         tracing::debug!("{:?}", db_error.to_string());
                         (StatusCode::INTERNAL_SERVER_ERROR, None)
@@ -62,12 +122,31 @@ impl IntoResponse for AppError {
         };
 
         match message {
-            Some(msg) => (status, Json(ErrorResponse { message: msg })).into_response(),
+            Some(msg) => (
+                status,
+                Json(ErrorResponse {
+                    message: msg,
+                    request_id: REQUEST_ID.try_with(Clone::clone).ok(),
+                }),
+            )
+                .into_response(),
             None => status.into_response(),
         }
     }
 }
 
+/// Builds a response listing every invalid field of a request.
+fn field_errors_response(status: StatusCode, errors: Vec<FieldError>) -> axum::response::Response {
+    (
+        status,
+        Json(FieldErrorsResponse {
+            errors,
+            request_id: REQUEST_ID.try_with(Clone::clone).ok(),
+        }),
+    )
+        .into_response()
+}
+
 impl From<StatusCode> for AppError {
     fn from(status: StatusCode) -> Self {
         AppError::StatusCode(status)
@@ -86,24 +165,25 @@ impl From<PathRejection> for AppError {
     }
 }
 
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        AppError::QueryRejection(rejection)
+    }
+}
+
 impl From<sqlx::Error> for AppError {
     fn from(error: sqlx::Error) -> Self {
         AppError::SqlxError(error)
     }
 }
 
-/// Only tests easily testable code without mocking or external dependencies.
+/// Only tests easily testable code without mocking, database errors are raised against the test database.
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::http::StatusCode;
-    use serde::{Deserialize, Serialize};
-
-    // Test struct for validation errors
-    #[derive(Debug, Serialize, Deserialize)]
-    struct TestUser {
-        username: String,
-    }
+    use axum::{body::to_bytes, http::StatusCode};
+    use serde_json::{Value, json};
+    use serial_test::serial;
 
     #[test]
     fn test_from_sqlx_row_not_found_error() {
@@ -126,6 +206,25 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    #[serial]
+    async fn test_from_sqlx_check_violation(pool: sqlx::PgPool) {
+        sqlx::query("CREATE TABLE checked (value INTEGER CHECK (value >= 0))")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let sqlx_error = sqlx::query("INSERT INTO checked (value) VALUES (-1)")
+            .execute(&pool)
+            .await
+            .unwrap_err();
+        let app_error = AppError::from(sqlx_error);
+
+        assert_eq!(
+            app_error.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
     #[test]
     fn test_from_status_code() {
         let status_code = StatusCode::BAD_REQUEST;
@@ -134,4 +233,103 @@ mod tests {
         assert!(matches!(app_error, AppError::StatusCode(_)));
         assert_eq!(app_error.into_response().status(), StatusCode::BAD_REQUEST);
     }
+
+    #[tokio::test]
+    async fn test_message() {
+        let app_error = AppError::Message(StatusCode::BAD_REQUEST, "Test message".to_string());
+        let response = app_error.into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Value>(&body_bytes).unwrap();
+
+        assert_eq!(body, json!({ "message": "Test message" }));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_references() {
+        let app_error = AppError::InvalidReferences(vec![
+            FieldError {
+                index: Some(3),
+                field: "categoryId".into(),
+                reason: "Category 7 not found".to_string(),
+            },
+            FieldError {
+                index: None,
+                field: "walletId".into(),
+                reason: "Wallet 9 not found".to_string(),
+            },
+        ]);
+        let response = app_error.into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "errors": [
+                    { "index": 3, "field": "categoryId", "reason": "Category 7 not found" },
+                    { "field": "walletId", "reason": "Wallet 9 not found" }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validation() {
+        let app_error = AppError::Validation(vec![FieldError {
+            index: Some(0),
+            field: "priority".into(),
+            reason: "Priority must be between 0 and 2".to_string(),
+        }]);
+        let response = app_error.into_response();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "errors": [
+                    { "index": 0, "field": "priority", "reason": "Priority must be between 0 and 2" }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response_includes_request_id() {
+        let response = REQUEST_ID
+            .scope("abc-123".to_string(), async {
+                AppError::PathRejection(PathRejection::MissingPathParams(Default::default()))
+                    .into_response()
+            })
+            .await;
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            json!({ "message": "Invalid path parameter", "requestId": "abc-123" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response_without_request_id() {
+        let response =
+            AppError::PathRejection(PathRejection::MissingPathParams(Default::default()))
+                .into_response();
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Value>(&body_bytes).unwrap();
+
+        assert_eq!(body, json!({ "message": "Invalid path parameter" }));
+    }
 }
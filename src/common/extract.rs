@@ -0,0 +1,256 @@
+use axum::{
+    Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
+};
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+use serde_json::Value;
+use serde_path_to_error::{Path, Segment};
+use std::{error::Error, marker::PhantomData};
+
+use crate::common::{
+    deserializer,
+    errors::{AppError, FieldError},
+};
+
+/// JSON extractor reporting the field holding an invalid value as a validation error.
+/// Malformed JSON and other rejections are reported the same way as with [`Json`].
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(request, state).await {
+            Ok(Json(value)) => Ok(Self(value)),
+            Err(JsonRejection::JsonDataError(rejection)) => match path_error(&rejection) {
+                Some(error) => Err(AppError::Validation(vec![field_error(error, None)])),
+                None => Err(AppError::JsonRejection(rejection.into())),
+            },
+            Err(rejection) => Err(AppError::JsonRejection(rejection)),
+        }
+    }
+}
+
+/// A batch request body, given either as an object holding the items under `FIELD` or as a bare array.
+pub trait Batch: Sized {
+    /// The name of the field holding the items when the body is an object.
+    const FIELD: &'static str;
+    /// The type of the items.
+    type Item: DeserializeOwned;
+
+    /// Builds the batch from its validated items.
+    fn from_items(items: Vec<Self::Item>) -> Self;
+}
+
+/// JSON extractor validating every item of a batch on its own.
+/// Unlike [`ValidatedJson`], the invalid fields of all items are reported at once instead of only the first one.
+/// Malformed JSON and other rejections are reported the same way as with [`Json`].
+pub struct ValidatedBatch<T>(pub T);
+
+/// The raw items of a batch, before being validated one by one.
+struct RawItems<T>(Vec<Value>, PhantomData<T>);
+
+impl<'de, T: Batch> Deserialize<'de> for RawItems<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer::bare_or_wrapped_vec(deserializer, T::FIELD)
+            .map(|items| Self(items, PhantomData))
+    }
+}
+
+impl<T, S> FromRequest<S> for ValidatedBatch<T>
+where
+    T: Batch,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(RawItems(raw_items, _)) =
+            match Json::<RawItems<T>>::from_request(request, state).await {
+                Ok(raw_items) => raw_items,
+                Err(JsonRejection::JsonDataError(rejection)) => {
+                    return match path_error(&rejection) {
+                        Some(error) => Err(AppError::Validation(vec![field_error(error, None)])),
+                        None => Err(AppError::JsonRejection(rejection.into())),
+                    };
+                }
+                Err(rejection) => return Err(AppError::JsonRejection(rejection)),
+            };
+
+        let mut items = Vec::with_capacity(raw_items.len());
+        let mut errors = Vec::new();
+
+        // Items are deserialized from references, since some fields are only deserialized from borrowed strings.
+        for (index, raw_item) in raw_items.iter().enumerate() {
+            match serde_path_to_error::deserialize(raw_item) {
+                Ok(item) => items.push(item),
+                Err(error) => errors.push(field_error(&error, Some(index))),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors));
+        }
+
+        Ok(Self(T::from_items(items)))
+    }
+}
+
+/// Finds the deserialization error along with its path within the source chain of the rejection.
+fn path_error<'a>(
+    error: &'a (dyn Error + 'static),
+) -> Option<&'a serde_path_to_error::Error<serde_json::Error>> {
+    let mut source = Some(error);
+
+    while let Some(error) = source {
+        if let Some(path_error) = error.downcast_ref() {
+            return Some(path_error);
+        }
+        source = error.source();
+    }
+
+    None
+}
+
+/// Converts a deserialization error into the invalid field it points to.
+/// When the error comes from a single batch item, its index is given as `item_index`.
+/// Otherwise, the index of the first sequence in the path is reported as the index of the batch item.
+fn field_error(
+    error: &serde_path_to_error::Error<serde_json::Error>,
+    item_index: Option<usize>,
+) -> FieldError {
+    let inner = error.inner();
+    let position = format!(" at line {} column {}", inner.line(), inner.column());
+    let reason = inner.to_string();
+    let reason = reason
+        .strip_suffix(&position)
+        .unwrap_or(&reason)
+        .to_string();
+
+    let (index, mut field) = match item_index {
+        Some(item_index) => (Some(item_index), join_segments(error.path().iter())),
+        None => split_path(error.path()),
+    };
+
+    // Missing fields are reported on their parent object, so the field name is read from the message instead.
+    if let Some(missing_field) = reason
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'))
+    {
+        field = match field.is_empty() {
+            true => missing_field.to_string(),
+            false => format!("{field}.{missing_field}"),
+        };
+    }
+
+    FieldError {
+        index,
+        field: field.into(),
+        reason,
+    }
+}
+
+/// Splits a path into the index of the first sequence and the dotted path of the fields after it.
+fn split_path(path: &Path) -> (Option<usize>, String) {
+    let mut segments = path.iter();
+
+    // The fields before the first sequence only lead to the batch items, so they are dropped.
+    while let Some(segment) = segments.next() {
+        if let Segment::Seq { index } = segment {
+            return (Some(*index), join_segments(segments));
+        }
+    }
+
+    (None, join_segments(path.iter()))
+}
+
+/// Joins the segments of a path into a dotted path, with sequence indexes in brackets.
+fn join_segments<'a>(segments: impl Iterator<Item = &'a Segment>) -> String {
+    let fields = segments
+        .map(|segment| match segment {
+            Segment::Seq { index } => format!("[{index}]"),
+            Segment::Map { key } => key.clone(),
+            Segment::Enum { variant } => variant.clone(),
+            Segment::Unknown => "?".to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    fields.join(".").replace(".[", "[")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all(deserialize = "camelCase"))]
+    #[allow(dead_code)]
+    struct TestItem {
+        wallet_id: u32,
+        tag_ids: Vec<u32>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct TestBatch {
+        items: Vec<TestItem>,
+    }
+
+    fn deserialize_error<T: DeserializeOwned + std::fmt::Debug>(
+        json_str: &str,
+    ) -> serde_path_to_error::Error<serde_json::Error> {
+        let deserializer = &mut serde_json::Deserializer::from_str(json_str);
+        serde_path_to_error::deserialize::<_, T>(deserializer).unwrap_err()
+    }
+
+    #[test]
+    fn test_field_error_in_batch_item() {
+        let error = deserialize_error::<TestBatch>(
+            r#"{ "items": [{ "walletId": 1, "tagIds": [] }, { "walletId": -1, "tagIds": [] }] }"#,
+        );
+
+        let field_error = field_error(&error, None);
+
+        assert_eq!(field_error.index, Some(1));
+        assert_eq!(field_error.field, "walletId");
+        assert!(!field_error.reason.contains("at line"));
+    }
+
+    #[test]
+    fn test_field_error_nested_sequence() {
+        let error = deserialize_error::<TestBatch>(
+            r#"{ "items": [{ "walletId": 1, "tagIds": [1, "x"] }] }"#,
+        );
+
+        let field_error = field_error(&error, None);
+
+        assert_eq!(field_error.index, Some(0));
+        assert_eq!(field_error.field, "tagIds[1]");
+    }
+
+    #[test]
+    fn test_field_error_of_batch_item() {
+        let error = deserialize_error::<TestItem>(r#"{ "walletId": 1, "tagIds": [1, "x"] }"#);
+
+        let field_error = field_error(&error, Some(2));
+
+        assert_eq!(field_error.index, Some(2));
+        assert_eq!(field_error.field, "tagIds[1]");
+    }
+
+    #[test]
+    fn test_field_error_missing_field() {
+        let error = deserialize_error::<TestItem>(r#"{ "tagIds": [] }"#);
+
+        let field_error = field_error(&error, None);
+
+        assert_eq!(field_error.index, None);
+        assert_eq!(field_error.field, "walletId");
+        assert_eq!(field_error.reason, "missing field `walletId`");
+    }
+}
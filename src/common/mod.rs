@@ -1,3 +1,11 @@
+pub mod batch;
+pub mod csv;
 pub mod database;
 pub mod deserializer;
+pub mod env;
 pub mod errors;
+pub mod extract;
+pub mod priority;
+pub mod routing;
+pub mod sql;
+pub mod timing;
@@ -0,0 +1,36 @@
+use lazy_static::lazy_static;
+
+use crate::{common::env, constants::DEFAULT_MAX_PRIORITY};
+
+lazy_static! {
+    /// The lowest priority level of an expense, read once from the `MAX_PRIORITY` environment variable.
+    static ref MAX_PRIORITY: i32 = env::parse_or("MAX_PRIORITY", DEFAULT_MAX_PRIORITY);
+}
+
+/// Returns the lowest priority level of an expense, as configured by the `MAX_PRIORITY` environment variable.
+pub fn max_priority() -> i32 {
+    *MAX_PRIORITY
+}
+
+/// Checks that a priority value is between 0 and the given maximum priority.
+pub fn check_priority(value: i32, max_priority: i32) -> Result<i32, String> {
+    if !(0..=max_priority).contains(&value) {
+        return Err(format!("Priority must be between 0 and {max_priority}"));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_priority_with_higher_max_priority() {
+        assert_eq!(check_priority(3, 3), Ok(3));
+        assert_eq!(
+            check_priority(4, 3),
+            Err("Priority must be between 0 and 3".to_string())
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use axum::Router;
+
+/// Normalizes a base path into the `/segment/...` form, without a trailing slash.
+/// Returns `None` when the base path is blank or only made of slashes.
+fn normalize_base_path(base_path: &str) -> Option<String> {
+    let trimmed = base_path.trim().trim_matches('/');
+
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    Some(format!("/{trimmed}"))
+}
+
+/// Nests the router under the given base path, e.g. `/api/v1`.
+/// The router is returned as is when there is no base path.
+pub fn with_base_path(router: Router, base_path: Option<&str>) -> Router {
+    match base_path.and_then(normalize_base_path) {
+        Some(base_path) => {
+            tracing::info!("Serving routes under {base_path}");
+            Router::new().nest(&base_path, router)
+        }
+        None => router,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{handlers::health::health_routes, state::AppState};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_normalize_base_path() {
+        assert_eq!(normalize_base_path("/api/v1"), Some("/api/v1".to_string()));
+        assert_eq!(
+            normalize_base_path(" api/v1/ "),
+            Some("/api/v1".to_string())
+        );
+        assert_eq!(normalize_base_path("/"), None);
+        assert_eq!(normalize_base_path(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_base_path() {
+        // Prepare
+        let app = with_base_path(
+            health_routes().with_state(AppState::mocked()),
+            Some("/api/v1"),
+        );
+
+        let nested_request = Request::builder()
+            .uri("/api/v1/health")
+            .body(Body::empty())
+            .unwrap();
+        let root_request = Request::builder()
+            .uri("/health")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let nested_response = app.clone().oneshot(nested_request).await.unwrap();
+        let root_response = app.oneshot(root_request).await.unwrap();
+
+        // Assert
+        assert_eq!(nested_response.status(), StatusCode::OK);
+        assert_eq!(root_response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_with_base_path_none() {
+        // Prepare
+        let app = with_base_path(health_routes().with_state(AppState::mocked()), None);
+
+        let request = Request::builder()
+            .uri("/health")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,34 @@
+/// Escapes the wildcard characters of a `LIKE` pattern, so the input is matched literally.
+/// The escaped value relies on the default backslash escape character of PostgreSQL.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_like_plain_value() {
+        assert_eq!(escape_like("ess"), "ess");
+    }
+
+    #[test]
+    fn test_escape_like_wildcards() {
+        assert_eq!(escape_like("50%_off"), "50\\%\\_off");
+    }
+
+    #[test]
+    fn test_escape_like_backslash() {
+        assert_eq!(escape_like("a\\b"), "a\\\\b");
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll, ready},
+    time::Instant,
+};
+
+/// Logs how long a database operation took at DEBUG level.
+pub fn log_elapsed(operation: &'static str, started_at: Instant) {
+    tracing::debug!(
+        operation,
+        elapsed_ms = started_at.elapsed().as_millis() as u64,
+        "Database operation finished"
+    );
+}
+
+/// Future wrapper logging the elapsed time of the wrapped database operation once it completes.
+/// The clock starts on the first poll, so building the future ahead of awaiting it is not counted.
+pub struct Timed<F> {
+    /// The name of the operation, e.g. `expense.find_one`.
+    operation: &'static str,
+    /// The instant the wrapped future was first polled.
+    started_at: Option<Instant>,
+    /// The wrapped future, boxed so that the wrapper doesn't need pin projection.
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Timed<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let started_at = *self.started_at.get_or_insert_with(Instant::now);
+        let output = ready!(self.inner.as_mut().poll(cx));
+
+        log_elapsed(self.operation, started_at);
+
+        Poll::Ready(output)
+    }
+}
+
+/// Extension trait to time any future, see [`Timed`].
+pub trait TimedExt: Future + Sized {
+    /// Wraps the future so that its elapsed time is logged under the given operation name.
+    fn timed(self, operation: &'static str) -> Timed<Self> {
+        Timed {
+            operation,
+            started_at: None,
+            inner: Box::pin(self),
+        }
+    }
+}
+
+impl<F: Future> TimedExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timed_returns_output() {
+        let output = async { 42 }.timed("test.operation").await;
+
+        assert_eq!(output, 42);
+    }
+
+    #[tokio::test]
+    async fn test_timed_starts_on_first_poll() {
+        let timed = async {}.timed("test.operation");
+
+        assert!(timed.started_at.is_none());
+    }
+}
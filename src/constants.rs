@@ -1,2 +1,16 @@
 /// Constants for the application
 pub static MAX_PAGINATION_LIMIT: i32 = 100;
+/// The maximum number of records returned by the `latest` endpoints.
+pub static MAX_LATEST_COUNT: i32 = 20;
+/// The maximum number of items of a bulk save, unless overridden by the `MAX_BATCH_SIZE` environment variable.
+pub static DEFAULT_MAX_BATCH_SIZE: usize = 500;
+/// The lowest priority level of an expense, unless overridden by the `MAX_PRIORITY` environment variable.
+pub static DEFAULT_MAX_PRIORITY: i32 = 2;
+/// The longest date range of a summary in days, unless overridden by the `MAX_SUMMARY_DAYS` environment variable.
+pub static DEFAULT_MAX_SUMMARY_DAYS: i64 = 366;
+/// The priority level of the expenses recorded for wallet transfer fees,
+/// unless overridden by the `TRANSFER_FEE_PRIORITY` environment variable.
+pub static DEFAULT_TRANSFER_FEE_PRIORITY: i32 = 2;
+/// The category of the expenses recorded for wallet transfer fees,
+/// unless overridden by the `TRANSFER_FEE_CATEGORY_IDS` environment variable.
+pub static DEFAULT_TRANSFER_FEE_CATEGORY_ID: i32 = 25;
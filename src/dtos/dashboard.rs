@@ -0,0 +1,72 @@
+use crate::{
+    common::deserializer,
+    dtos::query_result::{ShowLatestExpense, ShowLatestIncome, ShowSummary, SimpleEntity, Tag},
+};
+use serde::{Deserialize, Serialize};
+use time::Date;
+
+/// The query string of the dashboard, holding the date range of its summary.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct DashboardQuery {
+    /// The start date of the summary.
+    #[serde(deserialize_with = "deserializer::date")]
+    pub start_date: Date,
+    /// The end date of the summary.
+    #[serde(deserialize_with = "deserializer::date")]
+    pub end_date: Date,
+}
+
+/// Data transfer object for the response of the dashboard endpoint.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Deserialize))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct DashboardResponse {
+    /// The latest expense, if any.
+    pub latest_expense: Option<ShowLatestExpense>,
+    /// The latest income, if any.
+    pub latest_income: Option<ShowLatestIncome>,
+    /// The summary of the expenses and incomes within the date range.
+    pub summary: ShowSummary,
+    /// The list of wallets.
+    pub wallets: Vec<SimpleEntity>,
+    /// The list of tags.
+    pub tags: Vec<Tag>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_dashboard_query_valid() {
+        let json_str = r#"{
+            "startDate": "2025-04-01",
+            "endDate": "2025-04-30"
+        }"#;
+
+        let query = serde_json::from_str::<DashboardQuery>(json_str).unwrap();
+
+        assert_eq!(
+            query.start_date,
+            Date::from_calendar_date(2025, time::Month::April, 1).unwrap()
+        );
+        assert_eq!(
+            query.end_date,
+            Date::from_calendar_date(2025, time::Month::April, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dashboard_query_missing_end_date() {
+        let json_str = r#"{
+            "startDate": "2025-04-01"
+        }"#;
+
+        let result = serde_json::from_str::<DashboardQuery>(json_str);
+
+        assert!(result.is_err());
+    }
+}
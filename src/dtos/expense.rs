@@ -1,13 +1,17 @@
-use crate::common::deserializer;
-use crate::dtos::{Pagination, query_result::IndexExpenseElement};
-use serde::{Deserialize, Serialize};
+use crate::common::{deserializer, extract::Batch};
+use crate::dtos::{
+    Pagination,
+    query_result::{IndexExpenseElement, ShowLatestExpense},
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::str::FromStr;
 use time::Date;
 
 /// Data transfer object for saving an expense.
 /// Numeric fields are represented with unsigned integers to automatically filter out negative values from the client.
 #[derive(Deserialize)]
 #[serde(rename_all(deserialize = "camelCase"))]
-#[cfg_attr(test, derive(Debug))]
+#[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct SaveExpense {
     /// The amount of the expense.
     #[serde(deserialize_with = "deserializer::non_negative_int")]
@@ -16,6 +20,8 @@ pub struct SaveExpense {
     #[serde(deserialize_with = "deserializer::date")]
     pub date: Date,
     /// Optional description of the expense.
+    /// Empty or whitespace-only descriptions are stored as `None`.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
     pub description: Option<String>,
     /// The priority level of the expense.
     /// 0: high, 1: medium, 2: low
@@ -28,23 +34,197 @@ pub struct SaveExpense {
     #[serde(deserialize_with = "deserializer::positive_int")]
     pub wallet_id: i32,
     /// The IDs of the tags associated with the expense.
-    #[serde(deserialize_with = "deserializer::positive_int_vec")]
-    pub tag_ids: Vec<i32>,
+    /// When omitted, an update leaves the existing tags untouched.
+    #[serde(deserialize_with = "deserializer::optional_positive_int_vec", default)]
+    pub tag_ids: Option<Vec<i32>>,
+    /// Whether the expense is a refund, defaulting to `false`.
+    /// Refunds are subtracted from the totals of summaries instead of being added to them.
+    #[serde(default)]
+    pub is_refund: bool,
+    /// Optional identifier of the expense supplied by the client, unique per user.
+    /// Bulk saves skip expenses whose external ID is already recorded. Ignored on update.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
+    pub external_id: Option<String>,
 }
 
 /// Data transfer object for saving a batch of expenses.
-#[derive(Deserialize)]
+/// The expenses may be given either as `{ "expenses": [...] }` or as a bare array.
 pub struct SaveBatchExpense {
     /// The list of expenses to be saved.
     pub expenses: Vec<SaveExpense>,
 }
 
+impl<'de> Deserialize<'de> for SaveBatchExpense {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer::bare_or_wrapped_vec(deserializer, "expenses")
+            .map(|expenses| Self { expenses })
+    }
+}
+
+impl Batch for SaveBatchExpense {
+    const FIELD: &'static str = "expenses";
+    type Item = SaveExpense;
+
+    fn from_items(expenses: Vec<SaveExpense>) -> Self {
+        Self { expenses }
+    }
+}
+
+/// How far apart the installments of an installment schedule are.
+#[derive(Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all(deserialize = "lowercase"))]
+#[cfg_attr(test, derive(Debug))]
+pub enum InstallmentCadence {
+    /// One installment per week, on the same weekday as the first one.
+    Weekly,
+    /// One installment per month, on the same day of the month as the first one when possible.
+    Monthly,
+}
+
+/// Data transfer object for splitting a purchase into expenses spread over future dates.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct SaveExpenseInstallments {
+    /// The total amount of the purchase, split evenly across the installments.
+    #[serde(deserialize_with = "deserializer::non_negative_int")]
+    pub amount: i32,
+    /// The number of installments.
+    #[serde(deserialize_with = "deserializer::positive_int")]
+    pub count: i32,
+    /// The date of the first installment.
+    #[serde(deserialize_with = "deserializer::date")]
+    pub start_date: Date,
+    /// How far apart the installments are.
+    pub cadence: InstallmentCadence,
+    /// Optional description of every installment.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
+    pub description: Option<String>,
+    /// The priority level of every installment.
+    #[serde(deserialize_with = "deserializer::priority_value")]
+    pub priority: i32,
+    /// The ID of the category of every installment.
+    #[serde(deserialize_with = "deserializer::positive_int")]
+    pub category_id: i32,
+    /// The ID of the wallet every installment is paid from.
+    #[serde(deserialize_with = "deserializer::positive_int")]
+    pub wallet_id: i32,
+    /// The IDs of the tags of every installment.
+    #[serde(deserialize_with = "deserializer::optional_positive_int_vec", default)]
+    pub tag_ids: Option<Vec<i32>>,
+}
+
+/// How tags are assigned to the expenses of a batch tag assignment.
+#[derive(Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all(deserialize = "lowercase"))]
+#[cfg_attr(test, derive(Debug))]
+pub enum TagAssignmentMode {
+    /// Adds the tags on top of the existing tags of each expense.
+    Add,
+    /// Replaces the existing tags of each expense with the given tags.
+    Replace,
+}
+
+/// Data transfer object for assigning tags to multiple expenses at once.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+#[cfg_attr(test, derive(Clone, Debug, PartialEq, Eq))]
+pub struct AssignExpenseTags {
+    /// The IDs of the expenses to assign the tags to.
+    #[serde(deserialize_with = "deserializer::positive_int_vec")]
+    pub expense_ids: Vec<i32>,
+    /// The IDs of the tags to assign.
+    #[serde(deserialize_with = "deserializer::positive_int_vec")]
+    pub tag_ids: Vec<i32>,
+    /// How the tags are assigned.
+    pub mode: TagAssignmentMode,
+}
+
+/// Data transfer object for the response of the bulk save expense endpoint.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct SaveBatchExpenseResponse {
+    /// The IDs of the saved expenses, in the same order as the request.
+    /// Skipped expenses are left out, so the IDs don't line up with the request when any expense is skipped.
+    pub ids: Vec<i32>,
+    /// The number of saved expenses.
+    pub inserted: usize,
+    /// The number of expenses skipped because their external ID was already recorded.
+    pub skipped: usize,
+}
+
+/// Data transfer object for the response of the bulk save expense endpoint when the full rows are requested.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct SaveBatchExpenseFullResponse {
+    /// The saved expenses along with their category, wallet and tags, in the same order as the request.
+    pub expenses: Vec<ShowLatestExpense>,
+    /// The number of saved expenses.
+    pub inserted: usize,
+    /// The number of expenses skipped because their external ID was already recorded.
+    pub skipped: usize,
+}
+
+/// The query string of the bulk save expense endpoint.
+#[derive(Deserialize)]
+pub struct SaveBatchExpenseQuery {
+    /// What the response holds for each saved expense.
+    /// Invalid values fall back to `ids`.
+    #[serde(
+        rename = "return",
+        deserialize_with = "deserializer::from_str",
+        default
+    )]
+    pub return_mode: Option<ReturnMode>,
+}
+
+impl SaveBatchExpenseQuery {
+    /// Whether the full rows of the saved expenses are returned, rather than only their IDs.
+    pub fn return_full(&self) -> bool {
+        self.return_mode == Some(ReturnMode::Full)
+    }
+}
+
+/// What the response of the bulk save expense endpoint holds for each saved expense.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReturnMode {
+    /// Only the ID of the expense.
+    Ids,
+    /// The expense along with its category, wallet and tags.
+    Full,
+}
+
+impl FromStr for ReturnMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "ids" => Ok(Self::Ids),
+            "full" => Ok(Self::Full),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Data transfer object for the response of the latest expense endpoint when a count is requested.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct ShowRecentExpenseResponse {
+    /// The most recent expenses, newest first.
+    pub expenses: Vec<ShowLatestExpense>,
+}
+
 /// Data transfer object for the response of the index expense endpoint.
 #[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
 #[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
 pub struct IndexExpenseResponse {
     /// The list of expenses.
     pub expenses: Vec<IndexExpenseElement>,
+    /// Whether more expenses exist after this page.
+    pub has_more: bool,
+    /// The number of expenses matching the query regardless of the pagination.
+    pub total: i64,
 }
 
 /// The query string for filtering expenses.
@@ -65,11 +245,100 @@ pub struct IndexExpenseQuery {
         default
     )]
     pub end_date: Option<Date>,
+    /// The tag IDs to filter expenses by, given as a comma-separated list.
+    #[serde(
+        deserialize_with = "deserializer::optional_comma_separated_ids",
+        default
+    )]
+    pub tag_ids: Option<Vec<i32>>,
+    /// Whether expenses must have all or any of the filtered tags.
+    /// Invalid values fall back to `any`.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub tag_match: Option<TagMatch>,
+    /// Whether only expenses without any tag are included.
+    /// Invalid values fall back to `false`.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub untagged: Option<bool>,
+    /// The text to search expenses by their description.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
+    pub q: Option<String>,
+    /// Whether descriptions must equal or contain the searched text.
+    /// Invalid values fall back to `contains`.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub description_match: Option<DescriptionMatch>,
     /// The pagination information for the query.
     #[serde(flatten)]
     pub pagination: Pagination,
 }
 
+impl IndexExpenseQuery {
+    /// Whether expenses must have every filtered tag, rather than at least one of them.
+    pub fn match_all_tags(&self) -> bool {
+        self.tag_match == Some(TagMatch::All)
+    }
+
+    /// Whether only expenses without any tag are requested.
+    pub fn only_untagged(&self) -> bool {
+        self.untagged == Some(true)
+    }
+
+    /// Returns the description expenses must have, case-sensitively, when searching exact descriptions.
+    pub fn exact_description(&self) -> Option<&str> {
+        self.q
+            .as_deref()
+            .filter(|_| self.description_match == Some(DescriptionMatch::Exact))
+    }
+
+    /// Returns the text descriptions must contain, case-insensitively, unless searching exact descriptions.
+    pub fn contained_description(&self) -> Option<&str> {
+        self.q
+            .as_deref()
+            .filter(|_| self.description_match != Some(DescriptionMatch::Exact))
+    }
+}
+
+/// How the description search of the expense listing is matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescriptionMatch {
+    /// Descriptions must equal the searched text.
+    Exact,
+    /// Descriptions must contain the searched text, case-insensitively.
+    Contains,
+}
+
+impl FromStr for DescriptionMatch {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "exact" => Ok(Self::Exact),
+            "contains" => Ok(Self::Contains),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How the tag filter of the expense listing is matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagMatch {
+    /// Expenses must have every filtered tag.
+    All,
+    /// Expenses must have at least one of the filtered tags.
+    Any,
+}
+
+impl FromStr for TagMatch {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "all" => Ok(Self::All),
+            "any" => Ok(Self::Any),
+            _ => Err(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +359,70 @@ mod tests {
         let result = serde_json::from_str::<SaveExpense>(json_str);
 
         assert!(result.is_ok());
+        assert!(!result.unwrap().is_refund);
+    }
+
+    #[test]
+    fn test_save_expense_numeric_string_amount() {
+        let json_str = r#"{
+            "amount": "1000",
+            "date": "2025-04-01",
+            "priority": 1,
+            "categoryId": "1",
+            "walletId": 1
+        }"#;
+
+        let expense = serde_json::from_str::<SaveExpense>(json_str).unwrap();
+
+        assert_eq!(expense.amount, 1000);
+        assert_eq!(expense.category_id, 1);
+    }
+
+    #[test]
+    fn test_save_expense_non_numeric_amount() {
+        let json_str = r#"{
+            "amount": "abc",
+            "date": "2025-04-01",
+            "priority": 1,
+            "categoryId": 1,
+            "walletId": 1
+        }"#;
+
+        let result = serde_json::from_str::<SaveExpense>(json_str);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_save_expense_external_id() {
+        let json_str = r#"{
+            "amount": 1000,
+            "date": "2025-04-01",
+            "priority": 1,
+            "categoryId": 1,
+            "walletId": 1,
+            "externalId": " bank-123 "
+        }"#;
+
+        let expense = serde_json::from_str::<SaveExpense>(json_str).unwrap();
+
+        assert_eq!(expense.external_id, Some("bank-123".to_string()));
+    }
+
+    #[test]
+    fn test_save_expense_refund() {
+        let json_str = r#"{
+            "amount": 1000,
+            "date": "2025-04-01",
+            "priority": 1,
+            "categoryId": 1,
+            "walletId": 1,
+            "isRefund": true
+        }"#;
+
+        let expense = serde_json::from_str::<SaveExpense>(json_str).unwrap();
+
+        assert!(expense.is_refund);
     }
 
     #[test]
@@ -156,6 +489,27 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn test_save_batch_expense_bare_array() {
+        let expense = r#"{
+            "amount": 1000,
+            "date": "2025-04-01",
+            "description": "Test expense 1",
+            "priority": 0,
+            "categoryId": 1,
+            "walletId": 1,
+            "tagIds": [1, 2]
+        }"#;
+
+        let bare = serde_json::from_str::<SaveBatchExpense>(&format!("[{expense}]")).unwrap();
+        let wrapped =
+            serde_json::from_str::<SaveBatchExpense>(&format!(r#"{{ "expenses": [{expense}] }}"#))
+                .unwrap();
+
+        assert_eq!(bare.expenses.len(), 1);
+        assert_eq!(bare.expenses, wrapped.expenses);
+    }
+
     #[test]
     fn test_save_batch_expense_with_invalid_expense() {
         let json_str = r#"{
@@ -206,6 +560,82 @@ mod tests {
         assert_eq!(query.pagination.offset(), 0);
     }
 
+    #[test]
+    fn test_index_expense_query_with_tag_filter() {
+        let json_str = r#"{
+            "tagIds": "1,2",
+            "tagMatch": "all"
+        }"#;
+
+        let query: IndexExpenseQuery = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(query.tag_ids, Some(vec![1, 2]));
+        assert!(query.match_all_tags());
+    }
+
+    #[test]
+    fn test_index_expense_query_with_invalid_tag_match() {
+        let json_str = r#"{
+            "tagIds": "1,2",
+            "tagMatch": "every"
+        }"#;
+
+        let query: IndexExpenseQuery = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(query.tag_match, None);
+        assert!(!query.match_all_tags());
+    }
+
+    #[test]
+    fn test_index_expense_query_with_exact_description() {
+        let json_str = r#"{
+            "q": " Lunch ",
+            "descriptionMatch": "exact"
+        }"#;
+
+        let query: IndexExpenseQuery = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(query.exact_description(), Some("Lunch"));
+        assert_eq!(query.contained_description(), None);
+    }
+
+    #[test]
+    fn test_index_expense_query_with_contained_description_by_default() {
+        let json_str = r#"{
+            "q": "Lunch",
+            "descriptionMatch": "fuzzy"
+        }"#;
+
+        let query: IndexExpenseQuery = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(query.description_match, None);
+        assert_eq!(query.exact_description(), None);
+        assert_eq!(query.contained_description(), Some("Lunch"));
+    }
+
+    #[test]
+    fn test_index_expense_query_with_untagged() {
+        let json_str = r#"{
+            "untagged": "true"
+        }"#;
+
+        let query: IndexExpenseQuery = serde_json::from_str(json_str).unwrap();
+
+        assert!(query.only_untagged());
+    }
+
+    #[test]
+    fn test_index_expense_query_with_invalid_untagged() {
+        let json_str = r#"{
+            "untagged": "yes"
+        }"#;
+
+        let query: IndexExpenseQuery = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(query.untagged, None);
+        assert!(!query.only_untagged());
+    }
+
     #[test]
     fn test_index_expense_query_with_missing_pagination_fields() {
         let json_str = r#"{
@@ -450,6 +880,49 @@ mod tests {
 
         let result = serde_json::from_str::<SaveExpense>(json_str).unwrap();
 
-        assert_eq!(result.tag_ids, Vec::<i32>::new());
+        assert_eq!(result.tag_ids, Some(Vec::<i32>::new()));
+    }
+
+    #[test]
+    fn test_save_expense_missing_tag_ids() {
+        let json_str = r#"{
+            "amount": 1000,
+            "date": "2025-04-01",
+            "description": "Test expense",
+            "priority": 1,
+            "categoryId": 1,
+            "walletId": 1
+        }"#;
+
+        let result = serde_json::from_str::<SaveExpense>(json_str).unwrap();
+
+        assert_eq!(result.tag_ids, None);
+    }
+
+    #[test]
+    fn test_assign_expense_tags_valid() {
+        let json_str = r#"{
+            "expenseIds": [1, 2],
+            "tagIds": [3],
+            "mode": "replace"
+        }"#;
+
+        let assign_tags: AssignExpenseTags = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(assign_tags.expense_ids, vec![1, 2]);
+        assert_eq!(assign_tags.tag_ids, vec![3]);
+        assert_eq!(assign_tags.mode, TagAssignmentMode::Replace);
+    }
+
+    #[test]
+    fn test_assign_expense_tags_unknown_mode() {
+        let json_str = r#"{
+            "expenseIds": [1, 2],
+            "tagIds": [3],
+            "mode": "merge"
+        }"#;
+
+        let result = serde_json::from_str::<AssignExpenseTags>(json_str);
+        assert!(result.is_err());
     }
 }
@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+#[cfg(test)]
+use serde::Deserialize;
+
+/// The response body describing the running build of the service.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct VersionResponse {
+    /// The version of the crate.
+    pub version: String,
+    /// The git commit SHA the service was built from, if provided at build time.
+    pub git_sha: Option<String>,
+    /// The time the service was built at, if provided at build time.
+    pub build_timestamp: Option<String>,
+}
+
+/// The response body holding the round-trip time of a trivial query to the database.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct PingResponse {
+    /// The round-trip time of the query in milliseconds.
+    pub db_latency_ms: u64,
+}
@@ -1,12 +1,15 @@
-use crate::common::deserializer;
-use crate::dtos::{Pagination, query_result::IndexIncomeElement};
-use serde::{Deserialize, Serialize};
+use crate::common::{deserializer, extract::Batch};
+use crate::dtos::{
+    Pagination,
+    query_result::{IndexIncomeElement, ShowLatestIncome},
+};
+use serde::{Deserialize, Deserializer, Serialize};
 use time::Date;
 
 /// Data transfer object for saving an income.
 #[derive(Deserialize)]
 #[serde(rename_all(deserialize = "camelCase"))]
-#[cfg_attr(test, derive(Debug))]
+#[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct SaveIncome {
     /// The amount of the income.
     #[serde(deserialize_with = "deserializer::positive_int")]
@@ -15,25 +18,107 @@ pub struct SaveIncome {
     #[serde(deserialize_with = "deserializer::date")]
     pub date: Date,
     /// Optional description of the income.
+    /// Empty or whitespace-only descriptions are stored as `None`.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
     pub description: Option<String>,
     /// The wallet ID where the income is going to.
     #[serde(deserialize_with = "deserializer::positive_int")]
     pub wallet_id: i32,
+    /// The IDs of the tags associated with the income.
+    /// When omitted, an update leaves the existing tags untouched.
+    #[serde(deserialize_with = "deserializer::optional_positive_int_vec", default)]
+    pub tag_ids: Option<Vec<i32>>,
 }
 
-/// Data transfer object for saving a batch of incomes.
+/// Data transfer object for partially updating an income.
+/// Only the supplied fields are updated.
 #[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+#[cfg_attr(test, derive(Clone, Debug, PartialEq, Eq))]
+pub struct UpdateIncome {
+    /// The amount of the income.
+    #[serde(deserialize_with = "deserializer::optional_positive_int", default)]
+    pub amount: Option<i32>,
+    /// The date of the income.
+    #[serde(deserialize_with = "deserializer::optional_strict_date", default)]
+    pub date: Option<Date>,
+    /// The description of the income.
+    /// `null`, empty or whitespace-only descriptions clear the stored description.
+    #[serde(deserialize_with = "deserializer::nullable_non_empty_string", default)]
+    pub description: Option<Option<String>>,
+    /// The wallet ID where the income is going to.
+    #[serde(deserialize_with = "deserializer::optional_positive_int", default)]
+    pub wallet_id: Option<i32>,
+}
+
+impl UpdateIncome {
+    /// Returns whether none of the fields are supplied.
+    pub fn is_empty(&self) -> bool {
+        self.amount.is_none()
+            && self.date.is_none()
+            && self.description.is_none()
+            && self.wallet_id.is_none()
+    }
+}
+
+/// Data transfer object for saving a batch of incomes.
+/// The incomes may be given either as `{ "incomes": [...] }` or as a bare array.
 pub struct SaveBatchIncome {
     /// The list of incomes to be saved.
     pub incomes: Vec<SaveIncome>,
 }
 
+impl<'de> Deserialize<'de> for SaveBatchIncome {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer::bare_or_wrapped_vec(deserializer, "incomes").map(|incomes| Self { incomes })
+    }
+}
+
+impl Batch for SaveBatchIncome {
+    const FIELD: &'static str = "incomes";
+    type Item = SaveIncome;
+
+    fn from_items(incomes: Vec<SaveIncome>) -> Self {
+        Self { incomes }
+    }
+}
+
+/// Data transfer object for deleting a batch of incomes.
+#[derive(Deserialize)]
+pub struct DeleteBatchIncome {
+    /// The IDs of the incomes to be deleted.
+    #[serde(deserialize_with = "deserializer::positive_int_vec")]
+    pub ids: Vec<i32>,
+}
+
+/// Data transfer object for the response of the bulk delete income endpoint.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct DeleteBatchIncomeResponse {
+    /// The number of incomes deleted. Unknown IDs are not counted.
+    pub deleted: u64,
+}
+
+/// Data transfer object for the response of the latest income endpoint when a count is requested.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct ShowRecentIncomeResponse {
+    /// The most recent incomes, newest first.
+    pub incomes: Vec<ShowLatestIncome>,
+}
+
 /// Data transfer object for the response of the index income endpoint.
 #[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
 #[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
 pub struct IndexIncomeResponse {
     /// The list of incomes.
     pub incomes: Vec<IndexIncomeElement>,
+    /// Whether more incomes exist after this page.
+    pub has_more: bool,
+    /// The number of incomes matching the query regardless of the pagination.
+    pub total: i64,
 }
 
 /// The query string for filtering incomes.
@@ -81,6 +166,67 @@ mod tests {
         assert_eq!(save_income.date, expected_date);
         assert_eq!(save_income.description, Some("Salary".to_string()));
         assert_eq!(save_income.wallet_id, 1);
+        assert_eq!(save_income.tag_ids, None);
+    }
+
+    #[test]
+    fn test_save_income_numeric_string_amount() {
+        let json_str = r#"{
+            "amount": "1500000",
+            "date": "2025-04-01",
+            "walletId": "1"
+        }"#;
+
+        let save_income = serde_json::from_str::<SaveIncome>(json_str).unwrap();
+
+        assert_eq!(save_income.amount, 1500000);
+        assert_eq!(save_income.wallet_id, 1);
+    }
+
+    #[test]
+    fn test_save_income_non_numeric_amount() {
+        let json_str = r#"{
+            "amount": "abc",
+            "date": "2025-04-01",
+            "walletId": 1
+        }"#;
+
+        let result = serde_json::from_str::<SaveIncome>(json_str);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_save_income_with_tags() {
+        let json_str = r#"{
+            "amount": 1500000,
+            "date": "2025-04-01",
+            "walletId": 1,
+            "tagIds": [1, 2]
+        }"#;
+
+        let save_income = serde_json::from_str::<SaveIncome>(json_str).unwrap();
+
+        assert_eq!(save_income.tag_ids, Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_save_income_non_positive_tag_id() {
+        let json_str = r#"{
+            "amount": 1500000,
+            "date": "2025-04-01",
+            "walletId": 1,
+            "tagIds": [1, 0]
+        }"#;
+
+        let result = serde_json::from_str::<SaveIncome>(json_str);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Value must be positive")
+        );
     }
 
     #[test]
@@ -159,6 +305,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_save_batch_income_bare_array() {
+        let income = r#"{
+            "amount": 1500000,
+            "date": "2025-04-01",
+            "description": "Salary",
+            "walletId": 1
+        }"#;
+
+        let bare = serde_json::from_str::<SaveBatchIncome>(&format!("[{income}]")).unwrap();
+        let wrapped =
+            serde_json::from_str::<SaveBatchIncome>(&format!(r#"{{ "incomes": [{income}] }}"#))
+                .unwrap();
+
+        assert_eq!(bare.incomes.len(), 1);
+        assert_eq!(bare.incomes, wrapped.incomes);
+    }
+
     #[test]
     fn test_save_batch_income_valid() {
         let json_str = r#"{
@@ -217,4 +381,25 @@ mod tests {
         assert_eq!(query.pagination.limit(), 100);
         assert_eq!(query.pagination.offset(), 0);
     }
+
+    #[test]
+    fn test_update_income_description_only() {
+        let json_str = r#"{
+            "description": "Bonus"
+        }"#;
+
+        let update_income: UpdateIncome = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(update_income.amount, None);
+        assert_eq!(update_income.date, None);
+        assert_eq!(update_income.description, Some(Some("Bonus".to_string())));
+        assert_eq!(update_income.wallet_id, None);
+        assert!(!update_income.is_empty());
+    }
+
+    #[test]
+    fn test_update_income_empty() {
+        let update_income: UpdateIncome = serde_json::from_str("{}").unwrap();
+        assert!(update_income.is_empty());
+    }
 }
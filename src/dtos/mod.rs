@@ -1,15 +1,23 @@
+pub mod dashboard;
 pub mod expense;
+pub mod health;
 pub mod income;
 pub mod query_result;
 pub mod summary;
 pub mod util;
 pub mod wallet;
 
-use crate::{common::deserializer, constants::MAX_PAGINATION_LIMIT};
+use crate::{
+    common::deserializer,
+    constants::{MAX_LATEST_COUNT, MAX_PAGINATION_LIMIT},
+};
 use serde::Deserialize;
+use std::str::FromStr;
+use time::Date;
 
 /// This struct should only be used for pagination extracted from the query string,
 /// since it implements a custom deserializer that coerce string values to `i32`.
+/// Values that can't be parsed fall back to their defaults instead of rejecting the request.
 /// While the `qs` crate automatically coerces string values to `i32`,
 /// for whatever reason it doesn't work when combined with `#[serde(flatten)]`.
 /// https://github.com/nox/serde_urlencoded/issues/33
@@ -26,6 +34,7 @@ pub struct Pagination {
 
 impl Pagination {
     /// Returns the limit for pagination, defaulting to `MAX_PAGINATION_LIMIT` if not set or invalid.
+    /// A limit of `0` is valid, see [`Pagination::is_count_only`].
     pub fn limit(&self) -> i64 {
         let raw_limit = self.limit.unwrap_or(MAX_PAGINATION_LIMIT);
 
@@ -36,6 +45,12 @@ impl Pagination {
         raw_limit.into()
     }
 
+    /// Whether only the number of matching records is requested, which is the case when the limit is `0`.
+    /// The records themselves don't need to be fetched then.
+    pub fn is_count_only(&self) -> bool {
+        self.limit() == 0
+    }
+
     /// Returns the offset for pagination, defaulting to `0` if not set or invalid.
     pub fn offset(&self) -> i64 {
         let raw_offset = self.offset.unwrap_or(0);
@@ -57,6 +72,63 @@ impl Default for Pagination {
     }
 }
 
+/// The query string for filtering records by an optional date range.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct DateRangeQuery {
+    /// The lower bound date (inclusive).
+    #[serde(deserialize_with = "deserializer::optional_date", default)]
+    pub start_date: Option<Date>,
+    /// The upper bound date (inclusive).
+    #[serde(deserialize_with = "deserializer::optional_date", default)]
+    pub end_date: Option<Date>,
+}
+
+/// The query string for the `latest` endpoints.
+#[derive(Deserialize)]
+pub struct LatestQuery {
+    /// The number of most recent records to return.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    count: Option<i32>,
+}
+
+impl LatestQuery {
+    /// Returns the number of records to return, clamped between 1 and `MAX_LATEST_COUNT`.
+    /// Returns `None` if the count is not set or invalid, meaning only the latest record is requested.
+    pub fn count(&self) -> Option<i64> {
+        self.count
+            .map(|count| count.clamp(1, MAX_LATEST_COUNT).into())
+    }
+}
+
+/// The direction in which a listing is sorted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    /// Sorts from the lowest to the highest value.
+    Asc,
+    /// Sorts from the highest to the lowest value.
+    Desc,
+}
+
+impl SortDirection {
+    /// Whether the given direction is descending, treating a missing direction as ascending.
+    pub fn is_descending(direction: Option<Self>) -> bool {
+        direction == Some(Self::Desc)
+    }
+}
+
+impl FromStr for SortDirection {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +146,45 @@ mod tests {
         assert_eq!(pagination.offset(), 10);
     }
 
+    #[test]
+    fn test_sort_direction_from_str() {
+        assert_eq!("asc".parse(), Ok(SortDirection::Asc));
+        assert_eq!("desc".parse(), Ok(SortDirection::Desc));
+        assert_eq!("DESC".parse::<SortDirection>(), Err(()));
+    }
+
+    #[test]
+    fn test_sort_direction_defaults_to_ascending() {
+        assert!(SortDirection::is_descending(Some(SortDirection::Desc)));
+        assert!(!SortDirection::is_descending(Some(SortDirection::Asc)));
+        assert!(!SortDirection::is_descending(None));
+    }
+
+    #[test]
+    fn test_pagination_zero_limit_is_count_only() {
+        let json_str = r#"{
+            "limit": "0"
+        }"#;
+
+        let pagination = serde_json::from_str::<Pagination>(json_str).unwrap();
+
+        assert_eq!(pagination.limit(), 0);
+        assert!(pagination.is_count_only());
+    }
+
+    #[test]
+    fn test_pagination_invalid_limit_is_not_count_only() {
+        for limit in ["-1", "abc", "110"] {
+            let json_str = format!(r#"{{ "limit": "{limit}" }}"#);
+
+            let pagination = serde_json::from_str::<Pagination>(&json_str).unwrap();
+
+            assert!(!pagination.is_count_only());
+        }
+
+        assert!(!Pagination::default().is_count_only());
+    }
+
     #[test]
     fn test_pagination_default() {
         let json_str = r#"{
@@ -110,4 +221,59 @@ mod tests {
         assert_eq!(pagination.limit(), MAX_PAGINATION_LIMIT as i64);
         assert_eq!(pagination.offset(), 110);
     }
+
+    #[test]
+    fn test_pagination_non_numeric_limit() {
+        let json_str = r#"{
+            "limit": "abc",
+            "offset": "10"
+        }"#;
+
+        let pagination = serde_json::from_str::<Pagination>(json_str).unwrap();
+
+        assert_eq!(pagination.limit(), MAX_PAGINATION_LIMIT as i64);
+        assert_eq!(pagination.offset(), 10);
+    }
+
+    #[test]
+    fn test_pagination_non_numeric_offset() {
+        let json_str = r#"{
+            "limit": "50",
+            "offset": "xyz"
+        }"#;
+
+        let pagination = serde_json::from_str::<Pagination>(json_str).unwrap();
+
+        assert_eq!(pagination.limit(), 50);
+        assert_eq!(pagination.offset(), 0);
+    }
+
+    #[test]
+    fn test_latest_query_count() {
+        let json_str = r#"{
+            "count": "5"
+        }"#;
+
+        let latest_query = serde_json::from_str::<LatestQuery>(json_str).unwrap();
+
+        assert_eq!(latest_query.count(), Some(5));
+    }
+
+    #[test]
+    fn test_latest_query_count_clamped() {
+        let above_max = serde_json::from_str::<LatestQuery>(r#"{ "count": "1000" }"#).unwrap();
+        let below_min = serde_json::from_str::<LatestQuery>(r#"{ "count": "-3" }"#).unwrap();
+
+        assert_eq!(above_max.count(), Some(MAX_LATEST_COUNT as i64));
+        assert_eq!(below_min.count(), Some(1));
+    }
+
+    #[test]
+    fn test_latest_query_count_missing_or_invalid() {
+        let missing = serde_json::from_str::<LatestQuery>("{}").unwrap();
+        let invalid = serde_json::from_str::<LatestQuery>(r#"{ "count": "abc" }"#).unwrap();
+
+        assert_eq!(missing.count(), None);
+        assert_eq!(invalid.count(), None);
+    }
 }
@@ -1,4 +1,47 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, ser::SerializeMap};
+use std::borrow::Cow;
+
+/// The priority level of an expense, stored as an integer.
+/// 0: high, 1: medium, 2: low, and the levels above up to the configured `MAX_PRIORITY`.
+/// It's serialized as both the `priority` and `priorityLabel` fields, so it must be flattened into its parent.
+#[derive(Clone, Copy, sqlx::Type)]
+#[sqlx(transparent)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct PriorityLevel(pub i32);
+
+impl PriorityLevel {
+    /// The human-readable label of the priority level.
+    /// The levels above the default lowest one are labelled by their number, e.g. `level 3`.
+    pub fn label(&self) -> Cow<'static, str> {
+        match self.0 {
+            0 => Cow::Borrowed("high"),
+            1 => Cow::Borrowed("medium"),
+            2 => Cow::Borrowed("low"),
+            level => Cow::Owned(format!("level {level}")),
+        }
+    }
+}
+
+impl Serialize for PriorityLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("priority", &self.0)?;
+        map.serialize_entry("priorityLabel", &self.label())?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PriorityLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// The raw `priority` field, ignoring the derived `priorityLabel` field.
+        #[derive(Deserialize)]
+        struct RawPriorityLevel {
+            priority: i32,
+        }
+
+        RawPriorityLevel::deserialize(deserializer).map(|raw| Self(raw.priority))
+    }
+}
 
 /// Reusable struct for entities with an ID and name.
 #[derive(Deserialize, Serialize)]
@@ -24,6 +67,34 @@ pub struct Tag {
     pub is_important: bool,
 }
 
+/// Represents a record of `category_budget` table in the database.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct CategoryBudget {
+    /// The ID of the category.
+    pub category_id: i32,
+    /// The maximum amount of expenses of the category per month.
+    pub monthly_limit: i32,
+}
+
+/// Represents a record of `category` table along with its parent category in the database.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct CategoryWithParent {
+    /// The ID of the category.
+    pub id: i64,
+    /// The name of the category.
+    pub name: String,
+    /// The ID of the parent category.
+    pub parent_category_id: i64,
+    /// The name of the parent category.
+    pub parent_name: String,
+}
+
 /// Represents a record of `parent_category` table and its children in the database.
 #[derive(Serialize)]
 #[cfg_attr(test, derive(Debug, Deserialize, Eq, PartialEq))]
@@ -48,15 +119,17 @@ pub struct ShowLatestExpense {
     pub date: String,
     /// Optional description of the expense.
     pub description: Option<String>,
-    /// The priority level of the expense.
-    /// 0: high, 1: medium, 2: low
-    pub priority: i32,
-    /// The category associated with the expense.
-    pub category: sqlx::types::Json<SimpleEntity>,
+    /// The priority level of the expense, along with its label.
+    #[serde(flatten)]
+    pub priority: PriorityLevel,
+    /// Whether the expense is a refund.
+    pub is_refund: bool,
+    /// The category associated with the expense, or `None` if the category no longer exists.
+    pub category: Option<sqlx::types::Json<SimpleEntity>>,
     /// The tags associated with the expense.
     pub tags: sqlx::types::Json<Vec<Tag>>,
-    /// The wallet associated with the expense.
-    pub wallet: sqlx::types::Json<SimpleEntity>,
+    /// The wallet associated with the expense, or `None` if the wallet no longer exists.
+    pub wallet: Option<sqlx::types::Json<SimpleEntity>>,
 }
 
 /// Data transfer object for showing the latest expense.
@@ -69,15 +142,20 @@ pub struct ShowExpense {
     pub date: String,
     /// Optional description of the expense.
     pub description: Option<String>,
-    /// The priority level of the expense.
-    /// 0: high, 1: medium, 2: low
-    pub priority: i32,
-    /// The category associated with the expense.
-    pub category: sqlx::types::Json<SimpleEntity>,
+    /// The priority level of the expense, along with its label.
+    #[serde(flatten)]
+    pub priority: PriorityLevel,
+    /// Whether the expense is a refund.
+    pub is_refund: bool,
+    /// The category associated with the expense, or `None` if the category no longer exists.
+    pub category: Option<sqlx::types::Json<SimpleEntity>>,
     /// The tags associated with the expense.
     pub tags: sqlx::types::Json<Vec<Tag>>,
-    /// The wallet associated with the expense.
-    pub wallet: sqlx::types::Json<SimpleEntity>,
+    /// The wallet associated with the expense, or `None` if the wallet no longer exists.
+    pub wallet: Option<sqlx::types::Json<SimpleEntity>>,
+    /// The last time the expense or its tags were updated, used to derive the `ETag` of the expense.
+    #[serde(skip)]
+    pub updated_at: Option<time::OffsetDateTime>,
 }
 
 /// Data transfer object to show the list of expenses.
@@ -104,8 +182,10 @@ pub struct ShowIncome {
     pub date: String,
     /// Optional description of the income.
     pub description: Option<String>,
-    /// The wallet associated with the income.
-    pub wallet: sqlx::types::Json<SimpleEntity>,
+    /// The wallet associated with the income, or `None` if the wallet no longer exists.
+    pub wallet: Option<sqlx::types::Json<SimpleEntity>>,
+    /// The tags associated with the income.
+    pub tags: sqlx::types::Json<Vec<Tag>>,
 }
 
 /// Data transfer object for showing the latest income.
@@ -120,8 +200,10 @@ pub struct ShowLatestIncome {
     pub date: String,
     /// Optional description of the income.
     pub description: Option<String>,
-    /// The wallet associated with the income.
-    pub wallet: sqlx::types::Json<SimpleEntity>,
+    /// The wallet associated with the income, or `None` if the wallet no longer exists.
+    pub wallet: Option<sqlx::types::Json<SimpleEntity>>,
+    /// The tags associated with the income.
+    pub tags: sqlx::types::Json<Vec<Tag>>,
 }
 
 /// Data transfer object to show the list of incomes.
@@ -138,8 +220,86 @@ pub struct IndexIncomeElement {
     pub description: Option<String>,
 }
 
-/// Data transfer object for showing a name and amount.
+/// Data transfer object to show the list of wallet transfers.
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct IndexWalletTransferElement {
+    /// The ID of the transfer.
+    pub id: i32,
+    /// The amount of the transfer.
+    pub amount: i32,
+    /// The date of the transfer.
+    pub date: String,
+    /// Optional description of the transfer.
+    pub description: Option<String>,
+    /// The wallet the money was transferred from.
+    pub source_wallet: sqlx::types::Json<SimpleEntity>,
+    /// The wallet the money was transferred to.
+    pub target_wallet: sqlx::types::Json<SimpleEntity>,
+}
+
+/// The totals of the wallet transfers between a pair of wallets.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct WalletTransferSummaryElement {
+    /// The wallet the money was transferred from.
+    pub source: sqlx::types::Json<SimpleEntity>,
+    /// The wallet the money was transferred to.
+    pub target: sqlx::types::Json<SimpleEntity>,
+    /// The total amount transferred.
+    pub total_amount: i64,
+    /// The total fee of the transfers.
+    pub total_fee: i64,
+    /// The number of transfers.
+    pub count: i64,
+}
+
+/// Aggregated statistics of the incomes within a date range.
+/// Empty date ranges have a zero count and sum, and no average, minimum, or maximum.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct IncomeStats {
+    /// The number of incomes.
+    pub count: i64,
+    /// The total amount of the incomes.
+    pub sum: i64,
+    /// The average amount of the incomes, rounded to the nearest integer.
+    pub average: Option<i64>,
+    /// The smallest amount of the incomes.
+    pub min: Option<i32>,
+    /// The largest amount of the incomes.
+    pub max: Option<i32>,
+}
+
+/// Data transfer object to export incomes along with their wallet name.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct ExportIncomeElement {
+    /// The ID of the income.
+    pub id: i32,
+    /// The amount of the income.
+    pub amount: i32,
+    /// The date of the income.
+    pub date: String,
+    /// Optional description of the income.
+    pub description: Option<String>,
+    /// The name of the wallet where the income is going to, if the wallet still exists.
+    pub wallet_name: Option<String>,
+}
+
+/// The referenced IDs that don't exist in the database, each list in ascending order.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct UnknownReferences {
+    /// The unknown category IDs.
+    pub category_ids: Vec<i32>,
+    /// The unknown wallet IDs.
+    pub wallet_ids: Vec<i32>,
+    /// The unknown tag IDs.
+    pub tag_ids: Vec<i32>,
+}
+
+/// Data transfer object for showing a name and amount.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct SimpleAmountEntity {
     /// The name of the entity.
     pub name: String,
@@ -147,18 +307,44 @@ pub struct SimpleAmountEntity {
     pub amount: i32,
 }
 
-#[derive(Deserialize, Serialize)]
+/// Data transfer object for showing a category along with the total of its expenses.
+/// The total is wider than a single amount, as the sum of many expenses can exceed the range of `i32`.
+#[derive(Serialize)]
+pub struct CategoryTotal {
+    /// The name of the category.
+    pub name: String,
+    /// The total amount of the expenses of the category.
+    pub amount: i64,
+}
+
+/// Represents the total expenses of a category, along with its budget if any.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct ExpenseCategory {
+    /// The name of the category.
+    pub name: String,
+    /// The total amount of expenses of this category.
+    pub amount: i32,
+    /// The budget of this category over the whole date range, if a monthly limit is set.
+    pub budget: Option<i64>,
+    /// Whether the amount exceeds the budget. Always `false` without a budget.
+    #[serde(default)]
+    pub over_budget: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ExpenseParentCategory {
     /// The name of the parent category.
     pub name: String,
     /// The total amount of expenses of this parent category.
     pub amount: i32,
     /// The list of child categories with their respective amounts.
-    pub categories: Vec<SimpleAmountEntity>,
+    pub categories: Vec<ExpenseCategory>,
 }
 
 /// Represents the priority of an expense.
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ExpensePriority {
     /// The level of priority.
     /// 0: high, 1: medium, 2: low
@@ -168,9 +354,8 @@ pub struct ExpensePriority {
 }
 
 /// The grouped summary of expenses.
-#[derive(Deserialize, Serialize)]
-#[serde(rename_all(serialize = "camelCase"))]
-#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExpenseGroupedSummary {
     /// The grouped summary of expenses by parent category.
     pub parent_categories: Vec<ExpenseParentCategory>,
@@ -179,36 +364,71 @@ pub struct ExpenseGroupedSummary {
 }
 
 /// The grouped summary of income.
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct IncomeGroupedSummary {
     /// The list of wallets with their respective amounts.
     pub wallets: Vec<SimpleAmountEntity>,
 }
 
 /// Represents the summary of expenses, including the grouped summary.
-#[derive(Deserialize, Serialize)]
-#[serde(rename_all(serialize = "camelCase"))]
-#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExpenseSummary {
     /// The total amount of expenses.
     pub amount: i32,
+    /// The number of expenses behind the total amount.
+    pub expense_count: i64,
+    /// The total amount of wallet transfer fees within the date range.
+    /// Reported even when the transfer fees are excluded from the total amount.
+    pub transfer_fees: i32,
     /// The grouped summary of expenses.
     pub group_summary: ExpenseGroupedSummary,
 }
 
+impl ExpenseSummary {
+    /// Flags the categories whose amount exceeds their budget.
+    pub fn flag_over_budget(&mut self) {
+        let categories = self
+            .group_summary
+            .parent_categories
+            .iter_mut()
+            .flat_map(|parent_category| parent_category.categories.iter_mut());
+
+        for category in categories {
+            category.over_budget = category
+                .budget
+                .is_some_and(|budget| i64::from(category.amount) > budget);
+        }
+    }
+}
+
 /// Represents the summary of income, including the grouped summary.
-#[derive(Deserialize, Serialize)]
-#[serde(rename_all(serialize = "camelCase"))]
-#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IncomeSummary {
     /// The total amount of income.
     pub amount: i32,
+    /// The number of incomes behind the total amount.
+    pub income_count: i64,
     /// The grouped summary of income.
     pub group_summary: IncomeGroupedSummary,
 }
 
+/// Represents the money moved between wallets, which is neither an income nor an expense,
+/// along with the fees paid for the transfers, which are expenses.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferNetFlow {
+    /// The total amount moved between wallets.
+    pub volume: i64,
+    /// The total amount of the fees of the transfers.
+    pub fees: i64,
+    /// The number of transfers behind the totals.
+    pub transfer_count: i64,
+}
+
 /// The result of the summary query.
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 #[cfg_attr(test, derive(Deserialize))]
 #[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
@@ -217,4 +437,99 @@ pub struct ShowSummary {
     pub expense: sqlx::types::Json<ExpenseSummary>,
     /// The income summary.
     pub income: sqlx::types::Json<IncomeSummary>,
+    /// The transfers between wallets along with their fees.
+    pub transfer_net_flow: sqlx::types::Json<TransferNetFlow>,
+}
+
+/// The total amount of expenses and incomes within a time series bucket.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct TimeseriesTotal {
+    /// The first day of the bucket.
+    pub bucket: time::Date,
+    /// The total amount of expenses within the bucket.
+    pub expense: i64,
+    /// The total amount of incomes within the bucket.
+    pub income: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_priority_level_label() {
+        assert_eq!(PriorityLevel(0).label(), "high");
+        assert_eq!(PriorityLevel(1).label(), "medium");
+        assert_eq!(PriorityLevel(2).label(), "low");
+        assert_eq!(PriorityLevel(3).label(), "level 3");
+    }
+
+    #[test]
+    fn test_flag_over_budget() {
+        let category = |name: &str, amount: i32, budget: Option<i64>| ExpenseCategory {
+            name: name.to_string(),
+            amount,
+            budget,
+            over_budget: false,
+        };
+
+        let mut summary = ExpenseSummary {
+            amount: 4500,
+            expense_count: 3,
+            transfer_fees: 0,
+            group_summary: ExpenseGroupedSummary {
+                parent_categories: vec![ExpenseParentCategory {
+                    name: "Daily Expenses".to_string(),
+                    amount: 4500,
+                    categories: vec![
+                        category("Food", 2500, Some(2000)),
+                        category("Transportation", 1000, Some(1000)),
+                        category("Leisure", 1000, None),
+                    ],
+                }],
+                priorities: vec![],
+            },
+        };
+
+        summary.flag_over_budget();
+
+        let over_budget = summary.group_summary.parent_categories[0]
+            .categories
+            .iter()
+            .map(|category| category.over_budget)
+            .collect::<Vec<_>>();
+
+        assert_eq!(over_budget, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_show_expense_serializes_priority_label() {
+        let expense = ShowExpense {
+            amount: 1000,
+            date: "2025-04-01".to_string(),
+            description: None,
+            priority: PriorityLevel(0),
+            is_refund: false,
+            category: Some(sqlx::types::Json(SimpleEntity {
+                id: 1,
+                name: "Food".to_string(),
+            })),
+            tags: sqlx::types::Json(vec![]),
+            wallet: Some(sqlx::types::Json(SimpleEntity {
+                id: 1,
+                name: "Cash".to_string(),
+            })),
+            updated_at: None,
+        };
+
+        let value = serde_json::to_value(&expense).unwrap();
+
+        assert_eq!(value["priority"], 0);
+        assert_eq!(value["priorityLabel"], "high");
+        assert_eq!(
+            serde_json::from_value::<ShowExpense>(value).unwrap(),
+            expense
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::common::deserializer;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use time::Date;
 
 /// The request body to generate a summary.
@@ -16,6 +16,81 @@ pub struct GenerateSummaryRequest {
     /// The list of category IDs to exclude from the summary.
     #[serde(deserialize_with = "deserializer::positive_int_vec")]
     pub exclude_category_ids: Vec<i32>,
+    /// Whether to exclude the wallet transfer fees from the summary, defaulting to `true`.
+    /// Applies on top of `exclude_category_ids`, so an expense is excluded if either filter matches it.
+    #[serde(default = "exclude_transfer_fees_default")]
+    pub exclude_transfer_fees: bool,
+    /// Whether to reject dates after the current date, defaulting to `false`.
+    #[serde(default)]
+    pub reject_future_dates: bool,
+    /// The parent category to restrict the expenses of the summary to, if any.
+    #[serde(deserialize_with = "deserializer::optional_positive_int", default)]
+    pub parent_category_id: Option<i32>,
+}
+
+/// The default value of `GenerateSummaryRequest::exclude_transfer_fees`.
+fn exclude_transfer_fees_default() -> bool {
+    true
+}
+
+/// The size of the buckets of a time series.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    /// One bucket per day.
+    Day,
+    /// One bucket per ISO week, starting on Monday.
+    Week,
+    /// One bucket per calendar month.
+    Month,
+}
+
+impl Granularity {
+    /// The unit understood by PostgreSQL's `DATE_TRUNC`.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+}
+
+/// The request body to generate a time series of income and expense totals.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug))]
+pub struct GenerateTimeseriesRequest {
+    /// The start date for the time series.
+    #[serde(deserialize_with = "deserializer::date")]
+    pub start_date: Date,
+    /// The end date for the time series.
+    #[serde(deserialize_with = "deserializer::date")]
+    pub end_date: Date,
+    /// The size of the buckets.
+    pub granularity: Granularity,
+}
+
+/// A single bucket of a time series.
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+#[cfg_attr(test, serde(rename_all(deserialize = "camelCase")))]
+pub struct TimeseriesPoint {
+    /// The label of the bucket, e.g. `2025-04-01`, `2025-W14` or `2025-04`.
+    pub label: String,
+    /// The total amount of expenses within the bucket.
+    pub expense: i64,
+    /// The total amount of incomes within the bucket.
+    pub income: i64,
+}
+
+/// The response body of a generated time series.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct ShowTimeseriesResponse {
+    /// The buckets in chronological order, including the ones without any record.
+    pub points: Vec<TimeseriesPoint>,
 }
 
 #[cfg(test)]
@@ -39,6 +114,21 @@ mod tests {
         assert_eq!(request.start_date, expected_start);
         assert_eq!(request.end_date, expected_end);
         assert_eq!(request.exclude_category_ids, vec![1, 2, 3]);
+        assert!(request.exclude_transfer_fees);
+    }
+
+    #[test]
+    fn test_generate_summary_request_include_transfer_fees() {
+        let json_str = r#"{
+            "startDate": "2025-03-01",
+            "endDate": "2025-04-01",
+            "excludeCategoryIds": [],
+            "excludeTransferFees": false
+        }"#;
+
+        let request: GenerateSummaryRequest = serde_json::from_str(json_str).unwrap();
+
+        assert!(!request.exclude_transfer_fees);
     }
 
     #[test]
@@ -129,4 +219,31 @@ mod tests {
         let result = serde_json::from_str::<GenerateSummaryRequest>(json_str);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_generate_timeseries_request_valid() {
+        let json_str = r#"{
+            "startDate": "2025-03-01",
+            "endDate": "2025-04-01",
+            "granularity": "week"
+        }"#;
+
+        let request: GenerateTimeseriesRequest = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(request.granularity, Granularity::Week);
+        assert_eq!(request.granularity.unit(), "week");
+    }
+
+    #[test]
+    fn test_generate_timeseries_request_invalid_granularity() {
+        let json_str = r#"{
+            "startDate": "2025-03-01",
+            "endDate": "2025-04-01",
+            "granularity": "fortnight"
+        }"#;
+
+        let result = serde_json::from_str::<GenerateTimeseriesRequest>(json_str);
+
+        assert!(result.is_err());
+    }
 }
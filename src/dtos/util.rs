@@ -2,10 +2,75 @@ use crate::common::deserializer;
 use serde::{Deserialize, Serialize};
 
 use crate::dtos::{
-    Pagination,
-    query_result::{ParentCategory, SimpleEntity, Tag},
+    Pagination, SortDirection,
+    query_result::{CategoryWithParent, ParentCategory, SimpleEntity, Tag},
 };
 
+/// The query string for listing categories.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct IndexCategoriesQuery {
+    /// Whether to include the parent category of each category.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub include_parent: Option<bool>,
+    /// The ID of the parent category to filter categories by.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub parent_category_id: Option<i32>,
+    /// The direction of the configured ordering, by name unless overridden by `CATEGORY_SORT_DEFAULT`.
+    /// Ascending unless `desc` is given, invalid values fall back to ascending.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub sort_dir: Option<SortDirection>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// The query string for listing parent categories.
+#[derive(Deserialize)]
+pub struct IndexParentCategoriesQuery {
+    /// The substring to search parent categories by their name, case-insensitively.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
+    pub q: Option<String>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// The request body to move a category to a different parent category.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ReparentCategoryRequest {
+    /// The ID of the new parent category.
+    #[serde(deserialize_with = "deserializer::positive_int")]
+    pub parent_category_id: i32,
+}
+
+/// The request body to set the monthly budget of a category.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct SaveCategoryBudgetRequest {
+    /// The maximum amount of expenses of the category per month.
+    #[serde(deserialize_with = "deserializer::non_negative_int")]
+    pub monthly_limit: i32,
+}
+
+/// The request body to mark several tags as important or not at once.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct SetTagImportanceRequest {
+    /// The IDs of the tags to update.
+    #[serde(deserialize_with = "deserializer::positive_int_vec")]
+    pub tag_ids: Vec<i32>,
+    /// Whether the tags are important.
+    pub is_important: bool,
+}
+
+/// The response body of marking several tags as important or not at once.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, PartialEq, Eq))]
+pub struct SetTagImportanceResponse {
+    /// The number of tags updated. Unknown IDs are not counted.
+    pub updated: u64,
+}
+
 /// The response body to list all categories.
 #[derive(Serialize)]
 #[cfg_attr(test, derive(Debug, Deserialize, Eq, PartialEq))]
@@ -14,6 +79,14 @@ pub struct IndexCategoriesResponse {
     pub categories: Vec<SimpleEntity>,
 }
 
+/// The response body to list all categories along with their parent category.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, Eq, PartialEq))]
+pub struct IndexCategoriesWithParentResponse {
+    /// The list of categories.
+    pub categories: Vec<CategoryWithParent>,
+}
+
 /// The response body to list all parent categories.
 #[derive(Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
@@ -31,6 +104,13 @@ pub struct IndexTagsQuery {
     /// The value to filter tags by their importance.
     #[serde(deserialize_with = "deserializer::bool_with_fallback", default)]
     pub mark_important_value: Option<bool>,
+    /// The substring to search tags by their name, case-insensitively.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
+    pub q: Option<String>,
+    /// The direction of the configured ordering, by name after the important tags unless overridden
+    /// by `TAG_SORT_DEFAULT`. Ascending unless `desc` is given, invalid values fall back to ascending.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub sort_dir: Option<SortDirection>,
     #[serde(flatten)]
     pub pagination: Pagination,
 }
@@ -48,6 +128,70 @@ mod tests {
     use super::*;
     use serde_json;
 
+    #[test]
+    fn test_index_categories_query_include_parent() {
+        let json_str = r#"{
+            "includeParent": "true"
+        }"#;
+
+        let query: IndexCategoriesQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.include_parent, Some(true));
+    }
+
+    #[test]
+    fn test_index_categories_query_invalid_include_parent() {
+        let json_str = r#"{
+            "includeParent": "yes"
+        }"#;
+
+        let query: IndexCategoriesQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.include_parent, None);
+    }
+
+    #[test]
+    fn test_index_categories_query_sort_dir() {
+        let json_str = r#"{
+            "sortDir": "desc"
+        }"#;
+
+        let query: IndexCategoriesQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.sort_dir, Some(SortDirection::Desc));
+    }
+
+    #[test]
+    fn test_index_categories_query_invalid_sort_dir() {
+        let json_str = r#"{
+            "sortDir": "sideways"
+        }"#;
+
+        let query: IndexCategoriesQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.sort_dir, None);
+    }
+
+    #[test]
+    fn test_index_categories_query_parent_category_id() {
+        let json_str = r#"{
+            "parentCategoryId": "2"
+        }"#;
+
+        let query: IndexCategoriesQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.parent_category_id, Some(2));
+    }
+
+    #[test]
+    fn test_index_parent_categories_query_with_name_search() {
+        let json_str = r#"{
+            "q": " daily ",
+            "offset": "10",
+            "limit": "5"
+        }"#;
+
+        let query: IndexParentCategoriesQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.q, Some("daily".to_string()));
+        assert_eq!(query.pagination.offset(), 10);
+        assert_eq!(query.pagination.limit(), 5);
+    }
+
     #[test]
     fn test_index_tag_query_happy() {
         let json_str = r#"{
@@ -58,10 +202,31 @@ mod tests {
 
         let query: IndexTagsQuery = serde_json::from_str(json_str).unwrap();
         assert_eq!(query.mark_important_value, Some(true));
+        assert_eq!(query.q, None);
         assert_eq!(query.pagination.offset(), 0);
         assert_eq!(query.pagination.limit(), 10);
     }
 
+    #[test]
+    fn test_index_tag_query_with_name_search() {
+        let json_str = r#"{
+            "q": " ess "
+        }"#;
+
+        let query: IndexTagsQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.q, Some("ess".to_string()));
+    }
+
+    #[test]
+    fn test_index_tag_query_with_blank_name_search() {
+        let json_str = r#"{
+            "q": "  "
+        }"#;
+
+        let query: IndexTagsQuery = serde_json::from_str(json_str).unwrap();
+        assert_eq!(query.q, None);
+    }
+
     #[test]
     fn test_index_tag_query_invalid_mark_important_value() {
         let json_str = r#"{
@@ -1,4 +1,10 @@
-use crate::{common::deserializer, dtos::query_result::SimpleEntity};
+use crate::{
+    common::deserializer,
+    dtos::{
+        Pagination, SortDirection,
+        query_result::{IndexWalletTransferElement, SimpleEntity},
+    },
+};
 use serde::{self, Deserialize, Serialize};
 use time::Date;
 
@@ -14,7 +20,7 @@ pub struct SaveWalletTransferRequest {
     #[serde(deserialize_with = "deserializer::positive_int")]
     pub target_wallet_id: i32,
     /// The amount of the transfer.
-    #[serde(deserialize_with = "deserializer::non_negative_int")]
+    #[serde(deserialize_with = "deserializer::positive_int")]
     pub amount: i32,
     /// The fee associated with the transfer.
     #[serde(deserialize_with = "deserializer::non_negative_int")]
@@ -23,7 +29,53 @@ pub struct SaveWalletTransferRequest {
     #[serde(deserialize_with = "deserializer::date")]
     pub date: Date,
     /// Optional description of the transfer.
+    /// Empty or whitespace-only descriptions are stored as `None`.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
     pub description: Option<String>,
+    /// Optional description of the fee expense, used verbatim instead of the one derived from `description`.
+    /// Empty or whitespace-only descriptions are treated as `None`.
+    #[serde(deserialize_with = "deserializer::optional_non_empty_string", default)]
+    pub fee_description: Option<String>,
+    /// Whether to reject the transfer if it would make the balance of the source wallet negative.
+    #[serde(default)]
+    pub enforce_balance: bool,
+    /// Whether to reject the transfer if an identical one already exists on the same date.
+    /// Transfers are identical when their wallets, amount and description all match.
+    #[serde(default)]
+    pub reject_duplicates: bool,
+}
+
+/// The request body to create a wallet.
+#[derive(Deserialize)]
+pub struct SaveWalletRequest {
+    /// The name of the wallet, trimmed.
+    #[serde(deserialize_with = "deserializer::non_empty_string")]
+    pub name: String,
+}
+
+/// The query string for creating a wallet.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct SaveWalletQuery {
+    /// Whether to return the existing wallet of the same name instead of rejecting the request.
+    /// Invalid values fall back to rejecting the request.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub get_or_create: Option<bool>,
+}
+
+impl SaveWalletQuery {
+    /// Whether the existing wallet of the same name is requested rather than a conflict.
+    pub fn is_get_or_create(&self) -> bool {
+        self.get_or_create == Some(true)
+    }
+}
+
+/// The request body to rename a wallet.
+#[derive(Deserialize)]
+pub struct UpdateWalletRequest {
+    /// The new name of the wallet, trimmed.
+    #[serde(deserialize_with = "deserializer::non_empty_string")]
+    pub name: String,
 }
 
 /// The response body to list all wallets.
@@ -34,11 +86,65 @@ pub struct IndexWalletsResponse {
     pub wallets: Vec<SimpleEntity>,
 }
 
+/// The query string for listing wallets.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct IndexWalletsQuery {
+    /// The direction to sort by name, ascending unless `desc` is given.
+    /// Invalid values fall back to ascending.
+    #[serde(deserialize_with = "deserializer::from_str", default)]
+    pub sort_dir: Option<SortDirection>,
+    /// The pagination information for the query.
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// The query string for filtering wallet transfers.
+#[derive(Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct IndexWalletTransferQuery {
+    /// The lower bound date (inclusive) for filtering transfers.
+    #[serde(deserialize_with = "deserializer::optional_date", default)]
+    pub start_date: Option<Date>,
+    /// The upper bound date (inclusive) for filtering transfers.
+    #[serde(deserialize_with = "deserializer::optional_date", default)]
+    pub end_date: Option<Date>,
+    /// The pagination information for the query.
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// The response body to list wallet transfers.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, Deserialize, Eq, PartialEq))]
+pub struct IndexWalletTransfersResponse {
+    /// The list of transfers.
+    pub transfers: Vec<IndexWalletTransferElement>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json;
 
+    #[test]
+    fn test_update_wallet_trims_name() {
+        let json_str = r#"{ "name": "  Savings  " }"#;
+
+        let request: UpdateWalletRequest = serde_json::from_str(json_str).unwrap();
+
+        assert_eq!(request.name, "Savings");
+    }
+
+    #[test]
+    fn test_update_wallet_blank_name() {
+        let json_str = r#"{ "name": "   " }"#;
+
+        let result = serde_json::from_str::<UpdateWalletRequest>(json_str);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_save_transfer_valid() {
         let json_str = r#"{
@@ -58,6 +164,22 @@ mod tests {
         assert_eq!(transfer.amount, 1000);
         assert_eq!(transfer.fee, 10);
         assert_eq!(transfer.description, Some("Test transfer".to_string()));
+        assert!(!transfer.enforce_balance);
+    }
+
+    #[test]
+    fn test_save_transfer_enforce_balance() {
+        let json_str = r#"{
+            "sourceWalletId": 1,
+            "targetWalletId": 2,
+            "amount": 1000,
+            "fee": 10,
+            "date": "2025-05-06",
+            "enforceBalance": true
+        }"#;
+
+        let transfer = serde_json::from_str::<SaveWalletTransferRequest>(json_str).unwrap();
+        assert!(transfer.enforce_balance);
     }
 
     #[test]
@@ -102,6 +224,35 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_save_transfer_zero_amount() {
+        let json_str = r#"{
+            "sourceWalletId": 1,
+            "targetWalletId": 2,
+            "amount": 0,
+            "fee": 10,
+            "date": "2025-05-06",
+            "description": null
+        }"#;
+        let result = serde_json::from_str::<SaveWalletTransferRequest>(json_str);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_save_transfer_zero_fee() {
+        let json_str = r#"{
+            "sourceWalletId": 1,
+            "targetWalletId": 2,
+            "amount": 1000,
+            "fee": 0,
+            "date": "2025-05-06",
+            "description": null
+        }"#;
+        let result = serde_json::from_str::<SaveWalletTransferRequest>(json_str);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().fee, 0);
+    }
+
     #[test]
     fn test_save_transfer_invalid_fee() {
         let json_str = r#"{
@@ -144,4 +295,20 @@ mod tests {
         let transfer = result.unwrap();
         assert_eq!(transfer.description, None);
     }
+
+    #[test]
+    fn test_save_transfer_blank_description() {
+        let json_str = r#"{
+            "sourceWalletId": 1,
+            "targetWalletId": 2,
+            "amount": 1000,
+            "fee": 10,
+            "date": "2025-05-06",
+            "description": "   "
+        }"#;
+        let result = serde_json::from_str::<SaveWalletTransferRequest>(json_str);
+        assert!(result.is_ok());
+        let transfer = result.unwrap();
+        assert_eq!(transfer.description, None);
+    }
 }
@@ -1,6 +1,7 @@
 use time::Date;
 
 /// Entity for saving a wallet transfer record in the database.
+#[cfg_attr(test, derive(Clone, PartialEq, Eq))]
 pub struct SaveWalletTransfer {
     /// The ID of the source wallet.
     pub source_wallet_id: i32,
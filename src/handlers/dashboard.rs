@@ -0,0 +1,194 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+use axum_extra::extract::WithRejection;
+
+use crate::{
+    common::errors::AppError,
+    dtos::{
+        Pagination,
+        dashboard::{DashboardQuery, DashboardResponse},
+        summary::GenerateSummaryRequest,
+        util::IndexTagsQuery,
+        wallet::IndexWalletsQuery,
+    },
+    handlers::summary::validate_summary_dates,
+    middlewares::auth::{self, AuthenticatedUser},
+    state::AppState,
+};
+
+/// Handles the routes related to the dashboard.
+pub fn dashboard_routes() -> Router<AppState> {
+    Router::new().route("/dashboard", get(show))
+}
+
+/// Handles showing the latest expense and income, the summary of the date range, the wallets and the tags at once.
+/// The underlying queries run concurrently, and the first failing one fails the whole dashboard.
+/// The wallets and the tags are listed up to the default pagination limit, i.e. `MAX_PAGINATION_LIMIT`.
+async fn show(
+    WithRejection(Query(query), _): WithRejection<Query<DashboardQuery>, AppError>,
+    State(state): State<AppState>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    if query.start_date > query.end_date {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "startDate must not be after endDate".to_string(),
+        ));
+    }
+
+    let user_id = auth::user_id(&user);
+    let summary_request = GenerateSummaryRequest {
+        start_date: query.start_date,
+        end_date: query.end_date,
+        exclude_category_ids: Vec::new(),
+        exclude_transfer_fees: true,
+        reject_future_dates: false,
+        parent_category_id: None,
+    };
+    validate_summary_dates(&summary_request)?;
+
+    let tags_query = IndexTagsQuery {
+        mark_important_value: None,
+        q: None,
+        sort_dir: None,
+        pagination: Pagination::default(),
+    };
+    let wallets_query = IndexWalletsQuery {
+        sort_dir: None,
+        pagination: Pagination::default(),
+    };
+
+    let (latest_expenses, latest_incomes, summary, wallets, tags) = tokio::try_join!(
+        state.expense_repository.find_recent(1, user_id),
+        state.income_repository.find_recent(1, user_id),
+        state
+            .summary_repository
+            .generate_raw(&summary_request, user_id),
+        state.wallet_repository.find_many(&wallets_query, user_id),
+        state.util_repository.find_many_tags(&tags_query),
+    )?;
+
+    Ok((
+        StatusCode::OK,
+        Json(DashboardResponse {
+            latest_expense: latest_expenses.into_iter().next(),
+            latest_income: latest_incomes.into_iter().next(),
+            summary,
+            wallets,
+            tags,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{
+        body::{Body, to_bytes},
+        http::{Request, StatusCode},
+    };
+    use serde_json;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_show_handler() {
+        // Prepare
+        let app = dashboard_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/dashboard?startDate=2025-04-01&endDate=2025-04-30")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<DashboardResponse>(&body_bytes).unwrap();
+
+        assert!(body.latest_expense.is_some());
+        assert!(body.latest_income.is_some());
+        assert!(body.summary.expense.amount > 0);
+        assert!(!body.wallets.is_empty());
+        assert!(!body.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_invalid_date_range() {
+        // Prepare
+        let app = dashboard_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/dashboard?startDate=2025-04-30&endDate=2025-04-01")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_date_range_too_long() {
+        // Prepare
+        let app = dashboard_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/dashboard?startDate=2020-01-01&endDate=2025-12-31")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert!(
+            body["message"]
+                .as_str()
+                .unwrap()
+                .contains("exceeding the maximum")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_missing_dates() {
+        // Prepare
+        let app = dashboard_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/dashboard")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert!(body["message"].as_str().unwrap().contains("startDate"));
+    }
+}
@@ -1,21 +1,42 @@
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{delete, get, post, put},
 };
 use axum_extra::extract::WithRejection;
+use futures::TryStreamExt;
 use std::sync::Arc;
+use time::OffsetDateTime;
 
 use crate::{
-    common::errors::AppError,
-    dtos::expense::{IndexExpenseQuery, IndexExpenseResponse, SaveBatchExpense, SaveExpense},
+    common::{
+        batch,
+        errors::{AppError, FieldError},
+        extract::{ValidatedBatch, ValidatedJson},
+    },
+    dtos::{
+        DateRangeQuery, LatestQuery,
+        expense::{
+            AssignExpenseTags, IndexExpenseQuery, IndexExpenseResponse, SaveBatchExpense,
+            SaveBatchExpenseFullResponse, SaveBatchExpenseQuery, SaveBatchExpenseResponse,
+            SaveExpense, SaveExpenseInstallments, ShowRecentExpenseResponse,
+        },
+    },
+    handlers::X_TOTAL_COUNT,
+    middlewares::auth::{self, AuthenticatedUser},
     repositories::expense,
+    services::installment,
+    state::AppState,
 };
 
+/// The media type of newline-delimited JSON, holding one JSON document per line.
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
 /// Handles the routes related to expenses operations.
-pub fn expense_routes() -> Router<Arc<dyn expense::RepositoryOperation>> {
+pub fn expense_routes() -> Router<AppState> {
     Router::new().nest(
         "/expenses",
         Router::new()
@@ -24,93 +45,482 @@ pub fn expense_routes() -> Router<Arc<dyn expense::RepositoryOperation>> {
             .route("/{id}", get(show))
             .route("/{id}", put(update))
             .route("/{id}", delete(destroy))
+            .route("/active-dates", get(index_active_dates))
+            .route("/used-categories", get(index_used_categories))
+            .route("/installments", post(save_installments))
+            .route("/tags", post(assign_tags))
             .route("/latest", get(show_latest)),
     )
 }
 
+/// Builds the weak ETag of an expense from the last time it was updated.
+fn expense_etag(id: u32, updated_at: OffsetDateTime) -> String {
+    format!("W/\"{id}-{}\"", updated_at.unix_timestamp_nanos())
+}
+
+/// Checks whether the `If-None-Match` header of the request matches the given ETag.
+/// The comparison is weak, so the `W/` prefix is ignored on both sides.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque_tag = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque_tag(etag);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque_tag(tag) == etag)
+}
+
+/// Checks whether the `Accept` header of the request lists the NDJSON media type.
+fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| {
+            media_type
+                .split(';')
+                .next()
+                .is_some_and(|essence| essence.trim().eq_ignore_ascii_case(NDJSON_CONTENT_TYPE))
+        })
+}
+
+/// Handles assigning tags to multiple expenses at once.
+async fn assign_tags(
+    State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedJson(body): ValidatedJson<AssignExpenseTags>,
+) -> Result<impl IntoResponse, AppError> {
+    if body.expense_ids.is_empty() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "No expenses to tag".to_string(),
+        ));
+    }
+
+    expense_repository
+        .assign_tags(&body, auth::user_id(&user))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Handles the deletion of a specific expense by ID.
 async fn destroy(
     WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
     State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
-    expense_repository.delete(id as i32).await?;
+    expense_repository
+        .delete(id as i32, auth::user_id(&user))
+        .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Ensures that all categories, wallets and tags referenced by the given expenses exist.
+/// Responds with a bad request listing every unknown reference otherwise.
+/// The index of the offending expense is only included for batch requests.
+async fn ensure_references_exist(
+    expense_repository: &dyn expense::RepositoryOperation,
+    expenses: &[&SaveExpense],
+    is_batch: bool,
+    user_id: Option<&str>,
+) -> Result<(), AppError> {
+    let unknown = expense_repository
+        .find_unknown_references(expenses, user_id)
+        .await?;
+
+    let mut errors = Vec::<FieldError>::new();
+
+    for (i, expense) in expenses.iter().enumerate() {
+        let index = is_batch.then_some(i);
+
+        if unknown.category_ids.contains(&expense.category_id) {
+            errors.push(FieldError {
+                index,
+                field: "categoryId".into(),
+                reason: format!("Category {} not found", expense.category_id),
+            });
+        }
+
+        if unknown.wallet_ids.contains(&expense.wallet_id) {
+            errors.push(FieldError {
+                index,
+                field: "walletId".into(),
+                reason: format!("Wallet {} not found", expense.wallet_id),
+            });
+        }
+
+        for tag_id in expense.tag_ids.iter().flatten() {
+            if unknown.tag_ids.contains(tag_id) {
+                errors.push(FieldError {
+                    index,
+                    field: "tagIds".into(),
+                    reason: format!("Tag {tag_id} not found"),
+                });
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::InvalidReferences(errors))
+}
+
+/// Ensures that every expense whose category requires a description has one.
+/// Responds with an unprocessable entity listing every expense missing its description otherwise.
+/// The index of the offending expense is only included for batch requests.
+async fn ensure_descriptions_present(
+    expense_repository: &dyn expense::RepositoryOperation,
+    expenses: &[&SaveExpense],
+    is_batch: bool,
+) -> Result<(), AppError> {
+    let category_ids = expenses
+        .iter()
+        .filter(|expense| expense.description.is_none())
+        .map(|expense| expense.category_id)
+        .collect::<Vec<i32>>();
+
+    if category_ids.is_empty() {
+        return Ok(());
+    }
+
+    let required = expense_repository
+        .find_description_required_category_ids(&category_ids)
+        .await?;
+
+    let errors = expenses
+        .iter()
+        .enumerate()
+        .filter(|(_, expense)| {
+            expense.description.is_none() && required.contains(&expense.category_id)
+        })
+        .map(|(i, expense)| FieldError {
+            index: is_batch.then_some(i),
+            field: "description".into(),
+            reason: format!("Category {} requires a description", expense.category_id),
+        })
+        .collect::<Vec<FieldError>>();
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::Validation(errors))
+}
+
 /// Handles showing the list of expenses.
+/// When the client accepts NDJSON, the expenses are streamed one per line as they are read from the database.
 async fn index(
+    headers: HeaderMap,
     Query(query): Query<IndexExpenseQuery>,
     State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<Response, AppError> {
+    if query.only_untagged() && query.tag_ids.is_some() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "untagged cannot be combined with tagIds".to_string(),
+        ));
+    }
+
+    if accepts_ndjson(&headers) {
+        let lines = expense_repository
+            .stream_all(&query, auth::user_id(&user))
+            .map_ok(|expense| {
+                let mut line =
+                    serde_json::to_string(&expense).expect("Expense is always serializable");
+                line.push('\n');
+                line
+            })
+            .inspect_err(|error| tracing::error!("Failed to stream expenses: {error}"));
+
+        return Ok((
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+            Body::from_stream(lines),
+        )
+            .into_response());
+    }
+
+    let page = expense_repository
+        .find_all(&query, auth::user_id(&user))
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        [(&X_TOTAL_COUNT, page.total.to_string())],
+        Json(IndexExpenseResponse {
+            expenses: page.items,
+            has_more: page.has_more,
+            total: page.total,
+        }),
+    )
+        .into_response())
+}
+
+/// Handles showing the distinct dates with at least one expense, in ascending order.
+async fn index_active_dates(
+    Query(query): Query<DateRangeQuery>,
+    State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let active_dates = expense_repository
+        .find_active_dates(query.start_date, query.end_date, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(active_dates)))
+}
+
+/// Handles showing the categories with at least one expense, along with the total of their expenses.
+async fn index_used_categories(
+    Query(query): Query<DateRangeQuery>,
+    State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let expenses = expense_repository.find_all(&query).await?;
+    let used_categories = expense_repository
+        .find_used_categories(query.start_date, query.end_date, auth::user_id(&user))
+        .await?;
 
-    Ok((StatusCode::OK, Json(IndexExpenseResponse { expenses })))
+    Ok((StatusCode::OK, Json(used_categories)))
 }
 
 /// Handles the bulk save of expenses.
+/// Only the IDs of the saved expenses are returned, unless the full rows are requested.
 async fn save_bulk(
+    Query(query): Query<SaveBatchExpenseQuery>,
+    State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedBatch(body): ValidatedBatch<SaveBatchExpense>,
+) -> Result<Response, AppError> {
+    if body.expenses.is_empty() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "No items to insert".to_string(),
+        ));
+    }
+    batch::ensure_batch_size(body.expenses.len())?;
+
+    let expenses = body.expenses.iter().collect::<Vec<&SaveExpense>>();
+    ensure_references_exist(
+        expense_repository.as_ref(),
+        &expenses,
+        true,
+        auth::user_id(&user),
+    )
+    .await?;
+    ensure_descriptions_present(expense_repository.as_ref(), &expenses, true).await?;
+
+    let outcome = expense_repository
+        .insert_bulk(&body.expenses, auth::user_id(&user))
+        .await?;
+    let inserted = outcome.ids.len();
+
+    if query.return_full() {
+        let expenses = expense_repository
+            .find_many_by_ids(&outcome.ids, auth::user_id(&user))
+            .await?;
+
+        return Ok((
+            StatusCode::CREATED,
+            Json(SaveBatchExpenseFullResponse {
+                expenses,
+                inserted,
+                skipped: outcome.skipped,
+            }),
+        )
+            .into_response());
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SaveBatchExpenseResponse {
+            ids: outcome.ids,
+            inserted,
+            skipped: outcome.skipped,
+        }),
+    )
+        .into_response())
+}
+
+/// Handles splitting a purchase into expenses spread over future dates, saved at once.
+async fn save_installments(
     State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
-    WithRejection(Json(body), _): WithRejection<Json<SaveBatchExpense>, AppError>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedJson(body): ValidatedJson<SaveExpenseInstallments>,
 ) -> Result<impl IntoResponse, AppError> {
-    expense_repository.insert_bulk(&body.expenses).await?;
+    batch::ensure_batch_size(body.count as usize)?;
+
+    let Some(expenses) = installment::schedule(&body) else {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "The installments run past the last supported date".to_string(),
+        ));
+    };
+
+    // Every installment shares the same references, so checking the first one is enough.
+    ensure_references_exist(
+        expense_repository.as_ref(),
+        &[&expenses[0]],
+        false,
+        auth::user_id(&user),
+    )
+    .await?;
+    ensure_descriptions_present(expense_repository.as_ref(), &[&expenses[0]], false).await?;
+
+    let outcome = expense_repository
+        .insert_bulk(&expenses, auth::user_id(&user))
+        .await?;
 
-    Ok(StatusCode::CREATED)
+    Ok((
+        StatusCode::CREATED,
+        Json(SaveBatchExpenseResponse {
+            inserted: outcome.ids.len(),
+            ids: outcome.ids,
+            skipped: outcome.skipped,
+        }),
+    ))
 }
 
 /// Handles the retrieval of a specific expense by ID.
 async fn show(
     WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
     State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
-) -> Result<impl IntoResponse, AppError> {
-    let expense = expense_repository.find_one(id as i32).await?;
+    user: Option<Extension<AuthenticatedUser>>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let expense = expense_repository
+        .find_one(id as i32, auth::user_id(&user))
+        .await?;
+
+    let Some(etag) = expense
+        .updated_at
+        .map(|updated_at| expense_etag(id, updated_at))
+    else {
+        return Ok((StatusCode::OK, Json(expense)).into_response());
+    };
+
+    if if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
 
-    Ok((StatusCode::OK, Json(expense)))
+    Ok((StatusCode::OK, [(header::ETAG, etag)], Json(expense)).into_response())
 }
 
 /// Handles the retrieval of the latest expense.
+/// When a count is requested, the most recent expenses are returned as a list instead.
 async fn show_latest(
+    Query(query): Query<LatestQuery>,
     State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
-) -> Result<impl IntoResponse, AppError> {
-    let latest_expense = expense_repository.find_latest().await?;
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<Response, AppError> {
+    if let Some(count) = query.count() {
+        let expenses = expense_repository
+            .find_recent(count, auth::user_id(&user))
+            .await?;
+
+        return Ok((StatusCode::OK, Json(ShowRecentExpenseResponse { expenses })).into_response());
+    }
 
-    Ok((StatusCode::OK, Json(latest_expense)))
+    let Some(latest_expense) = expense_repository
+        .find_recent(1, auth::user_id(&user))
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+
+    Ok((StatusCode::OK, Json(latest_expense)).into_response())
 }
 
 /// Handles the update of a specific expense by ID.
 async fn update(
     WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
     State(expense_repository): State<Arc<dyn expense::RepositoryOperation>>,
-    WithRejection(Json(body), _): WithRejection<Json<SaveExpense>, AppError>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedJson(body): ValidatedJson<SaveExpense>,
 ) -> Result<impl IntoResponse, AppError> {
-    expense_repository.update(id as i32, &body).await?;
+    ensure_references_exist(
+        expense_repository.as_ref(),
+        &[&body],
+        false,
+        auth::user_id(&user),
+    )
+    .await?;
+    ensure_descriptions_present(expense_repository.as_ref(), &[&body], false).await?;
+
+    expense_repository
+        .update(id as i32, &body, auth::user_id(&user))
+        .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::constants::DEFAULT_MAX_BATCH_SIZE;
     use crate::dtos::{
-        expense::{IndexExpenseQuery, SaveExpense},
-        query_result::{IndexExpenseElement, ShowExpense, ShowLatestExpense, SimpleEntity, Tag},
+        expense::{IndexExpenseQuery, SaveExpense, TagAssignmentMode},
+        query_result::{
+            CategoryTotal, IndexExpenseElement, PriorityLevel, ShowExpense, ShowLatestExpense,
+            SimpleEntity, Tag, UnknownReferences,
+        },
     };
+    use crate::repositories::{Page, expense::BulkInsertOutcome};
 
     use async_trait::async_trait;
     use axum::{
         body::{Body, to_bytes},
-        http::{Request, StatusCode},
+        http::{Request, StatusCode, header},
     };
+    use futures::stream::{self, BoxStream};
     use serde_json;
     use sqlx::Error as SqlxError;
-    use std::sync::Arc;
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+    use time::Date;
     use tower::ServiceExt;
 
-    pub struct MockExpenseRepository;
+    /// Referenced IDs from this value onwards are treated as nonexistent by the mock repository.
+    const UNKNOWN_ID: i32 = 100;
+
+    /// The user that doesn't own any of the expenses in the mock repository.
+    const OTHER_USER_ID: &str = "mallory";
+
+    /// The expense whose category and wallet no longer exist in the mock repository.
+    const ORPHANED_ID: i32 = 99;
+
+    /// The category that requires expenses to have a description in the mock repository.
+    const DESCRIPTION_REQUIRED_CATEGORY_ID: i32 = 3;
+
+    pub struct MockExpenseRepository {
+        /// The tag IDs received by the last update, if any update happened.
+        updated_tag_ids: Mutex<Option<Option<Vec<i32>>>>,
+        /// The last batch tag assignment, if any assignment happened.
+        tag_assignment: Mutex<Option<AssignExpenseTags>>,
+        /// The creator received by the last bulk insertion, if any bulk insertion happened.
+        created_by: Mutex<Option<Option<String>>>,
+        /// The amounts and dates received by the last bulk insertion, if any bulk insertion happened.
+        inserted: Mutex<Option<Vec<(i32, Date)>>>,
+    }
 
     impl MockExpenseRepository {
         pub fn new() -> Arc<Self> {
-            Arc::new(Self)
+            Arc::new(Self {
+                updated_tag_ids: Mutex::new(None),
+                tag_assignment: Mutex::new(None),
+                created_by: Mutex::new(None),
+                inserted: Mutex::new(None),
+            })
         }
     }
 
@@ -129,7 +539,24 @@ mod tests {
                     date: "2025-04-02".to_string(),
                     description: None,
                 },
+                IndexExpenseElement {
+                    id: 3,
+                    amount: 3000,
+                    date: "2025-04-03".to_string(),
+                    description: Some("Parking".to_string()),
+                },
             ],
+            has_more: false,
+            total: 3,
+        }
+    }
+
+    /// The tag IDs assigned to each expense of the listing, by expense ID.
+    fn expense_tag_ids(id: i32) -> Vec<i32> {
+        match id {
+            1 => vec![1, 2],
+            2 => vec![1],
+            _ => vec![],
         }
     }
 
@@ -139,15 +566,16 @@ mod tests {
             amount: 3000,
             date: "2025-04-03".to_string(),
             description: Some("Latest test expense".to_string()),
-            priority: 0,
-            category: sqlx::types::Json(SimpleEntity {
+            priority: PriorityLevel(0),
+            is_refund: false,
+            category: Some(sqlx::types::Json(SimpleEntity {
                 id: 1,
                 name: "Food".to_string(),
-            }),
-            wallet: sqlx::types::Json(SimpleEntity {
+            })),
+            wallet: Some(sqlx::types::Json(SimpleEntity {
                 id: 1,
                 name: "Cash".to_string(),
-            }),
+            })),
             tags: sqlx::types::Json(vec![
                 Tag {
                     id: 1,
@@ -163,54 +591,289 @@ mod tests {
         }
     }
 
+    fn recent_expenses_response() -> Vec<ShowLatestExpense> {
+        vec![
+            show_latest_expense_response(),
+            ShowLatestExpense {
+                id: 2,
+                date: "2025-04-02".to_string(),
+                ..show_latest_expense_response()
+            },
+            ShowLatestExpense {
+                id: 1,
+                date: "2025-04-01".to_string(),
+                ..show_latest_expense_response()
+            },
+        ]
+    }
+
     fn show_expense_response(id: i32) -> ShowExpense {
         ShowExpense {
             amount: 1000,
             date: "2025-04-01".to_string(),
             description: Some(format!("Test expense {id}")),
-            priority: 1,
-            category: sqlx::types::Json(SimpleEntity {
+            priority: PriorityLevel(1),
+            is_refund: false,
+            category: Some(sqlx::types::Json(SimpleEntity {
                 id: 1,
                 name: "Food".to_string(),
-            }),
-            wallet: sqlx::types::Json(SimpleEntity {
+            })),
+            wallet: Some(sqlx::types::Json(SimpleEntity {
                 id: 1,
                 name: "Cash".to_string(),
-            }),
+            })),
             tags: sqlx::types::Json(vec![Tag {
                 id: 1,
                 name: "Essential".to_string(),
                 is_important: true,
             }]),
+            updated_at: OffsetDateTime::from_unix_timestamp(1_743_465_600).ok(),
+        }
+    }
+
+    /// Builds the app state holding the given mock repository.
+    fn state(repo: Arc<MockExpenseRepository>) -> AppState {
+        AppState {
+            expense_repository: repo,
+            ..AppState::mocked()
         }
     }
 
     #[async_trait]
     impl expense::RepositoryOperation for MockExpenseRepository {
-        async fn delete(&self, _id: i32) -> Result<(), SqlxError> {
+        async fn assign_tags(
+            &self,
+            assignment: &AssignExpenseTags,
+            _user_id: Option<&str>,
+        ) -> Result<(), SqlxError> {
+            *self.tag_assignment.lock().unwrap() = Some(assignment.clone());
+            Ok(())
+        }
+
+        async fn delete(&self, _id: i32, _user_id: Option<&str>) -> Result<(), SqlxError> {
             Ok(())
         }
 
+        async fn find_active_dates(
+            &self,
+            start_date: Option<Date>,
+            end_date: Option<Date>,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<String>, SqlxError> {
+            let active_dates = ["2025-04-01", "2025-04-03", "2025-04-07"]
+                .into_iter()
+                .filter(|&date| {
+                    start_date.is_none_or(|start_date| date >= start_date.to_string().as_str())
+                        && end_date.is_none_or(|end_date| date <= end_date.to_string().as_str())
+                })
+                .map(str::to_string)
+                .collect();
+
+            Ok(active_dates)
+        }
+
+        async fn find_used_categories(
+            &self,
+            start_date: Option<Date>,
+            end_date: Option<Date>,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<CategoryTotal>, SqlxError> {
+            let used_categories = [
+                ("2025-04-01", "Food", 1000),
+                ("2025-04-03", "Transport", 2000),
+            ]
+            .into_iter()
+            .filter(|&(date, _, _)| {
+                start_date.is_none_or(|start_date| date >= start_date.to_string().as_str())
+                    && end_date.is_none_or(|end_date| date <= end_date.to_string().as_str())
+            })
+            .map(|(_, name, amount)| CategoryTotal {
+                name: name.to_string(),
+                amount,
+            })
+            .collect();
+
+            Ok(used_categories)
+        }
+
         async fn find_all(
             &self,
-            _query: &IndexExpenseQuery,
-        ) -> Result<Vec<IndexExpenseElement>, SqlxError> {
-            Ok(index_expense_response().expenses)
+            query: &IndexExpenseQuery,
+            _user_id: Option<&str>,
+        ) -> Result<Page<IndexExpenseElement>, SqlxError> {
+            let expenses = index_expense_response()
+                .expenses
+                .into_iter()
+                .filter(|expense| match &query.tag_ids {
+                    Some(tag_ids) => {
+                        let assigned = expense_tag_ids(expense.id);
+                        let mut matches = tag_ids.iter().map(|tag_id| assigned.contains(tag_id));
+
+                        if query.match_all_tags() {
+                            matches.all(|matched| matched)
+                        } else {
+                            matches.any(|matched| matched)
+                        }
+                    }
+                    None => true,
+                })
+                .filter(|expense| !query.only_untagged() || expense_tag_ids(expense.id).is_empty())
+                .filter(|expense| {
+                    query.exact_description().is_none_or(|exact_description| {
+                        expense.description.as_deref() == Some(exact_description)
+                    })
+                })
+                .filter(|expense| {
+                    query
+                        .contained_description()
+                        .is_none_or(|contained_description| {
+                            expense.description.as_ref().is_some_and(|description| {
+                                description
+                                    .to_lowercase()
+                                    .contains(&contained_description.to_lowercase())
+                            })
+                        })
+                })
+                .collect::<Vec<_>>();
+            let total = expenses.len() as i64;
+
+            if query.pagination.is_count_only() {
+                return Ok(Page::count_only(total, query.pagination.offset()));
+            }
+
+            Ok(Page::from_rows(expenses, query.pagination.limit(), total))
+        }
+
+        async fn find_many_by_ids(
+            &self,
+            ids: &[i32],
+            _user_id: Option<&str>,
+        ) -> Result<Vec<ShowLatestExpense>, SqlxError> {
+            Ok(ids
+                .iter()
+                .map(|&id| ShowLatestExpense {
+                    id,
+                    ..show_latest_expense_response()
+                })
+                .collect())
         }
 
-        async fn find_latest(&self) -> Result<ShowLatestExpense, SqlxError> {
-            Ok(show_latest_expense_response())
+        async fn find_recent(
+            &self,
+            limit: i64,
+            user_id: Option<&str>,
+        ) -> Result<Vec<ShowLatestExpense>, SqlxError> {
+            if user_id == Some(OTHER_USER_ID) {
+                return Ok(Vec::new());
+            }
+
+            Ok(recent_expenses_response()
+                .into_iter()
+                .take(limit as usize)
+                .collect())
         }
 
-        async fn find_one(&self, id: i32) -> Result<ShowExpense, SqlxError> {
+        async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<ShowExpense, SqlxError> {
+            if user_id == Some(OTHER_USER_ID) {
+                return Err(SqlxError::RowNotFound);
+            }
+
+            if id == ORPHANED_ID {
+                return Ok(ShowExpense {
+                    category: None,
+                    wallet: None,
+                    ..show_expense_response(id)
+                });
+            }
+
             Ok(show_expense_response(id))
         }
 
-        async fn insert_bulk(&self, _expenses: &[SaveExpense]) -> Result<(), SqlxError> {
-            Ok(())
+        async fn find_description_required_category_ids(
+            &self,
+            category_ids: &[i32],
+        ) -> Result<Vec<i32>, SqlxError> {
+            Ok(category_ids
+                .iter()
+                .copied()
+                .filter(|&id| id == DESCRIPTION_REQUIRED_CATEGORY_ID)
+                .collect())
+        }
+
+        async fn find_unknown_references(
+            &self,
+            expenses: &[&SaveExpense],
+            _user_id: Option<&str>,
+        ) -> Result<UnknownReferences, SqlxError> {
+            let unknown = |ids: Vec<i32>| {
+                let mut ids = ids
+                    .into_iter()
+                    .filter(|&id| id >= UNKNOWN_ID)
+                    .collect::<Vec<i32>>();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            };
+
+            Ok(UnknownReferences {
+                category_ids: unknown(expenses.iter().map(|e| e.category_id).collect()),
+                wallet_ids: unknown(expenses.iter().map(|e| e.wallet_id).collect()),
+                tag_ids: unknown(
+                    expenses
+                        .iter()
+                        .flat_map(|e| e.tag_ids.iter().flatten().copied())
+                        .collect(),
+                ),
+            })
+        }
+
+        async fn insert_bulk(
+            &self,
+            expenses: &[SaveExpense],
+            user_id: Option<&str>,
+        ) -> Result<BulkInsertOutcome, SqlxError> {
+            *self.created_by.lock().unwrap() = Some(user_id.map(str::to_string));
+            *self.inserted.lock().unwrap() = Some(
+                expenses
+                    .iter()
+                    .map(|expense| (expense.amount, expense.date))
+                    .collect(),
+            );
+
+            let mut external_ids = HashSet::new();
+            let inserted = expenses
+                .iter()
+                .filter(|expense| {
+                    expense
+                        .external_id
+                        .as_ref()
+                        .is_none_or(|external_id| external_ids.insert(external_id))
+                })
+                .count();
+
+            Ok(BulkInsertOutcome {
+                ids: (1..=inserted as i32).collect(),
+                skipped: expenses.len() - inserted,
+            })
+        }
+
+        fn stream_all(
+            &self,
+            _query: &IndexExpenseQuery,
+            _user_id: Option<&str>,
+        ) -> BoxStream<'static, Result<IndexExpenseElement, SqlxError>> {
+            Box::pin(stream::iter(
+                index_expense_response().expenses.into_iter().map(Ok),
+            ))
         }
 
-        async fn update(&self, _id: i32, _expense: &SaveExpense) -> Result<(), SqlxError> {
+        async fn update(
+            &self,
+            _id: i32,
+            expense: &SaveExpense,
+            _user_id: Option<&str>,
+        ) -> Result<(), SqlxError> {
+            *self.updated_tag_ids.lock().unwrap() = Some(expense.tag_ids.clone());
             Ok(())
         }
     }
@@ -219,7 +882,7 @@ mod tests {
     async fn test_destroy_handler() {
         // Prepare
         let repo = MockExpenseRepository::new();
-        let app = expense_routes().with_state(repo);
+        let app = expense_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("DELETE")
@@ -238,7 +901,7 @@ mod tests {
     async fn test_index_handler() {
         // Prepare
         let repo = MockExpenseRepository::new();
-        let app = expense_routes().with_state(repo);
+        let app = expense_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -259,48 +922,40 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_save_bulk_handler() {
+    async fn test_index_handler_has_more() {
         // Prepare
         let repo = MockExpenseRepository::new();
-        let app = expense_routes().with_state(repo);
+        let app = expense_routes().with_state(state(repo));
 
-        // Use serde_json::json! macro to avoid serialization issues
         let request = Request::builder()
-            .method("POST")
-            .uri("/expenses")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                serde_json::json!({
-                    "expenses": [{
-                        "amount": 1000,
-                        "date": "2025-04-01",
-                        "description": "Test expense",
-                        "priority": 1,
-                        "categoryId": 1,
-                        "walletId": 1,
-                        "tagIds": [1, 2]
-                    }]
-                })
-                .to_string(),
-            ))
+            .method("GET")
+            .uri("/expenses?limit=1")
+            .body(Body::empty())
             .unwrap();
 
         // Execute
         let response = app.oneshot(request).await.unwrap();
 
         // Assert
-        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(body["expenses"].as_array().unwrap().len(), 1);
+        assert_eq!(body["hasMore"], true);
+        assert_eq!(body["total"], 3);
     }
 
     #[tokio::test]
-    async fn test_show_handler() {
+    async fn test_index_handler_count_only() {
         // Prepare
         let repo = MockExpenseRepository::new();
-        let app = expense_routes().with_state(repo);
+        let app = expense_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
-            .uri("/expenses/1")
+            .uri("/expenses?limit=0")
             .body(Body::empty())
             .unwrap();
 
@@ -311,20 +966,22 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let body = serde_json::from_slice::<ShowExpense>(&body_bytes).unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
 
-        assert_eq!(body, show_expense_response(1));
+        assert!(body.expenses.is_empty());
+        assert!(body.has_more);
+        assert_eq!(body.total, index_expense_response().total);
     }
 
     #[tokio::test]
-    async fn test_show_latest_handler() {
+    async fn test_index_handler_with_all_tags() {
         // Prepare
         let repo = MockExpenseRepository::new();
-        let app = expense_routes().with_state(repo);
+        let app = expense_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
-            .uri("/expenses/latest")
+            .uri("/expenses?tagIds=1,2&tagMatch=all")
             .body(Body::empty())
             .unwrap();
 
@@ -335,40 +992,1533 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let body = serde_json::from_slice::<ShowLatestExpense>(&body_bytes).unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
+        let ids = body
+            .expenses
+            .iter()
+            .map(|expense| expense.id)
+            .collect::<Vec<i32>>();
 
-        assert_eq!(body, show_latest_expense_response());
+        // Expense 2 misses tag 2, so it must be excluded
+        assert_eq!(ids, vec![1]);
     }
 
     #[tokio::test]
-    async fn test_update_handler() {
+    async fn test_index_handler_total_count_header() {
         // Prepare
         let repo = MockExpenseRepository::new();
-        let app = expense_routes().with_state(repo);
+        let app = expense_routes().with_state(state(repo));
 
-        // Use serde_json::json! macro to avoid serialization issues
         let request = Request::builder()
-            .method("PUT")
-            .uri("/expenses/1")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                serde_json::json!({
-                    "amount": 1000,
-                    "date": "2025-04-01",
-                    "description": "Updated test expense",
-                    "priority": 1,
-                    "categoryId": 1,
-                    "walletId": 1,
-                    "tagIds": [1, 2]
-                })
-                .to_string(),
-            ))
+            .method("GET")
+            .uri("/expenses?tagIds=1,2&tagMatch=all&limit=0")
+            .body(Body::empty())
             .unwrap();
 
         // Execute
         let response = app.oneshot(request).await.unwrap();
 
         // Assert
-        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Only expense 1 has both tags
+        assert_eq!(response.headers()[&X_TOTAL_COUNT], "1");
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_with_any_tags() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses?tagIds=1,2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
+        let mut tagged_expenses = index_expense_response().expenses;
+        tagged_expenses.truncate(2);
+
+        assert_eq!(body.expenses, tagged_expenses);
+        assert_eq!(body.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_untagged() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses?untagged=true")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.expenses,
+            vec![IndexExpenseElement {
+                id: 3,
+                amount: 3000,
+                date: "2025-04-03".to_string(),
+                description: Some("Parking".to_string()),
+            }]
+        );
+        assert_eq!(body.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_exact_description() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let exact_request = Request::builder()
+            .method("GET")
+            .uri("/expenses?q=Test%20expense%201&descriptionMatch=exact")
+            .body(Body::empty())
+            .unwrap();
+        let partial_request = Request::builder()
+            .method("GET")
+            .uri("/expenses?q=expense&descriptionMatch=exact")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let exact_response = app.clone().oneshot(exact_request).await.unwrap();
+        let partial_response = app.oneshot(partial_request).await.unwrap();
+
+        // Assert
+        assert_eq!(exact_response.status(), StatusCode::OK);
+        assert_eq!(partial_response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(exact_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.expenses
+                .iter()
+                .map(|expense| expense.id)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+
+        let body_bytes = to_bytes(partial_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
+
+        assert!(body.expenses.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_contained_description() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses?q=EXPENSE")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexExpenseResponse>(&body_bytes).unwrap();
+
+        // The expense without a description never matches
+        assert_eq!(
+            body.expenses
+                .iter()
+                .map(|expense| expense.id)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(body.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_untagged_with_tag_ids() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses?untagged=true&tagIds=1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_ndjson() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses")
+            .header(header::ACCEPT, "application/x-ndjson")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+        assert!(body.ends_with('\n'));
+
+        let expenses = body
+            .lines()
+            .map(|line| serde_json::from_str::<IndexExpenseElement>(line).unwrap())
+            .collect::<Vec<IndexExpenseElement>>();
+
+        assert_eq!(expenses, index_expense_response().expenses);
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        // Use serde_json::json! macro to avoid serialization issues
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-01",
+                            "description": "Test expense",
+                            "priority": 1,
+                            "categoryId": 1,
+                            "walletId": 1,
+                            "tagIds": [1, 2]
+                        },
+                        {
+                            "amount": 2000,
+                            "date": "2025-04-02",
+                            "description": null,
+                            "priority": 2,
+                            "categoryId": 2,
+                            "walletId": 1,
+                            "tagIds": []
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SaveBatchExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            SaveBatchExpenseResponse {
+                ids: vec![1, 2],
+                inserted: 2,
+                skipped: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_bare_array() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!([
+                    {
+                        "amount": 1000,
+                        "date": "2025-04-01",
+                        "description": "Test expense",
+                        "priority": 1,
+                        "categoryId": 1,
+                        "walletId": 1
+                    },
+                    {
+                        "amount": 2000,
+                        "date": "2025-04-02",
+                        "priority": 2,
+                        "categoryId": 2,
+                        "walletId": 1
+                    }
+                ])
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SaveBatchExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            SaveBatchExpenseResponse {
+                ids: vec![1, 2],
+                inserted: 2,
+                skipped: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_duplicate_external_id() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!([
+                    {
+                        "amount": 1000,
+                        "date": "2025-04-01",
+                        "priority": 1,
+                        "categoryId": 1,
+                        "walletId": 1,
+                        "externalId": "bank-1"
+                    },
+                    {
+                        "amount": 1000,
+                        "date": "2025-04-01",
+                        "priority": 1,
+                        "categoryId": 1,
+                        "walletId": 1,
+                        "externalId": "bank-1"
+                    },
+                    {
+                        "amount": 2000,
+                        "date": "2025-04-02",
+                        "priority": 2,
+                        "categoryId": 2,
+                        "walletId": 1
+                    }
+                ])
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SaveBatchExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            SaveBatchExpenseResponse {
+                ids: vec![1, 2],
+                inserted: 2,
+                skipped: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_installments_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses/installments")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "count": 3,
+                    "startDate": "2025-01-31",
+                    "cadence": "monthly",
+                    "description": "Laptop",
+                    "priority": 1,
+                    "categoryId": 1,
+                    "walletId": 1,
+                    "tagIds": [1]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SaveBatchExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            SaveBatchExpenseResponse {
+                ids: vec![1, 2, 3],
+                inserted: 3,
+                skipped: 0,
+            }
+        );
+        assert_eq!(
+            *repo.inserted.lock().unwrap(),
+            Some(vec![
+                (
+                    334,
+                    Date::from_calendar_date(2025, time::Month::January, 31).unwrap()
+                ),
+                (
+                    333,
+                    Date::from_calendar_date(2025, time::Month::February, 28).unwrap()
+                ),
+                (
+                    333,
+                    Date::from_calendar_date(2025, time::Month::March, 31).unwrap()
+                ),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_installments_handler_unknown_category() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses/installments")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "count": 3,
+                    "startDate": "2025-01-31",
+                    "cadence": "weekly",
+                    "priority": 1,
+                    "categoryId": UNKNOWN_ID,
+                    "walletId": 1
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*repo.inserted.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_return_full() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses?return=full")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-01",
+                            "description": "Test expense",
+                            "priority": 1,
+                            "categoryId": 1,
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 2000,
+                            "date": "2025-04-02",
+                            "priority": 2,
+                            "categoryId": 2,
+                            "walletId": 1
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SaveBatchExpenseFullResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.expenses
+                .iter()
+                .map(|expense| expense.id)
+                .collect::<Vec<i32>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_records_creator() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes()
+            .layer(Extension(AuthenticatedUser("alice".to_string())))
+            .with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [{
+                        "amount": 1000,
+                        "date": "2025-04-01",
+                        "description": "Test expense",
+                        "priority": 1,
+                        "categoryId": 1,
+                        "walletId": 1
+                    }]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            *repo.created_by.lock().unwrap(),
+            Some(Some("alice".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<ShowExpense>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            ShowExpense {
+                updated_at: None,
+                ..show_expense_response(1)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_orphaned() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("/expenses/{ORPHANED_ID}"))
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(body["category"], serde_json::Value::Null);
+        assert_eq!(body["wallet"], serde_json::Value::Null);
+        assert_eq!(body["amount"], 1000);
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_other_user() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes()
+            .layer(Extension(AuthenticatedUser(OTHER_USER_ID.to_string())))
+            .with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_etag() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG).unwrap(),
+            "W/\"1-1743465600000000000\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_not_modified() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/1")
+            .header(header::IF_NONE_MATCH, "\"1-1743465600000000000\"")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response.headers().get(header::ETAG).unwrap(),
+            "W/\"1-1743465600000000000\""
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body_bytes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_stale_etag() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/1")
+            .header(
+                header::IF_NONE_MATCH,
+                "W/\"1-1000\", W/\"2-1743465600000000000\"",
+            )
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed_lists_allowed_methods() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses/1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let allow = response
+            .headers()
+            .get(header::ALLOW)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        let mut allowed_methods = allow.split(',').collect::<Vec<&str>>();
+        allowed_methods.sort_unstable();
+
+        assert_eq!(allowed_methods, vec!["DELETE", "GET", "HEAD", "PUT"]);
+    }
+
+    #[tokio::test]
+    async fn test_assign_tags_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses/tags")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenseIds": [1, 2, 3],
+                    "tagIds": [4, 5],
+                    "mode": "add"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            *repo.tag_assignment.lock().unwrap(),
+            Some(AssignExpenseTags {
+                expense_ids: vec![1, 2, 3],
+                tag_ids: vec![4, 5],
+                mode: TagAssignmentMode::Add,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assign_tags_handler_no_expenses() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses/tags")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenseIds": [],
+                    "tagIds": [4],
+                    "mode": "replace"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*repo.tag_assignment.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_index_active_dates_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/active-dates?startDate=2025-04-02&endDate=2025-04-30")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Vec<String>>(&body_bytes).unwrap();
+
+        assert_eq!(body, vec!["2025-04-03", "2025-04-07"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_used_categories_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/used-categories?startDate=2025-04-02&endDate=2025-04-30")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!([{ "name": "Transport", "amount": 2000 }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_latest_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/latest")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<ShowLatestExpense>(&body_bytes).unwrap();
+
+        assert_eq!(body, show_latest_expense_response());
+    }
+
+    #[tokio::test]
+    async fn test_show_latest_handler_without_expenses() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes()
+            .with_state(state(repo))
+            .layer(Extension(AuthenticatedUser(OTHER_USER_ID.to_string())));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/latest")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body_bytes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_show_latest_handler_with_count() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/expenses/latest?count=2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<ShowRecentExpenseResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.expenses,
+            recent_expenses_response()
+                .into_iter()
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_handler() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        // Use serde_json::json! macro to avoid serialization issues
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/expenses/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "date": "2025-04-01",
+                    "description": "Updated test expense",
+                    "priority": 1,
+                    "categoryId": 1,
+                    "walletId": 1,
+                    "tagIds": [1, 2]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_with_empty_tags() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/expenses/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "date": "2025-04-01",
+                    "description": "Updated test expense",
+                    "priority": 1,
+                    "categoryId": 1,
+                    "walletId": 1,
+                    "tagIds": []
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(*repo.updated_tag_ids.lock().unwrap(), Some(Some(vec![])));
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_without_tags() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/expenses/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "date": "2025-04-01",
+                    "description": "Updated test expense",
+                    "priority": 1,
+                    "categoryId": 1,
+                    "walletId": 1
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(*repo.updated_tag_ids.lock().unwrap(), Some(None));
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_empty_batch() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "expenses": [] }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(body, serde_json::json!({ "message": "No items to insert" }));
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_batch_too_large() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let expense = serde_json::json!({
+            "amount": 1000,
+            "date": "2025-04-01",
+            "priority": 1,
+            "categoryId": 1,
+            "walletId": 1
+        });
+        let expenses = vec![expense; DEFAULT_MAX_BATCH_SIZE + 1];
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "expenses": expenses }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({ "message": "Batch too large: 501 items exceeds the maximum of 500" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_unknown_references() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-01",
+                            "priority": 1,
+                            "categoryId": 1,
+                            "walletId": 1,
+                            "tagIds": [1, 2]
+                        },
+                        {
+                            "amount": 2000,
+                            "date": "2025-04-02",
+                            "priority": 2,
+                            "categoryId": 100,
+                            "walletId": 1,
+                            "tagIds": [1, 101]
+                        },
+                        {
+                            "amount": 3000,
+                            "date": "2025-04-03",
+                            "priority": 0,
+                            "categoryId": 2,
+                            "walletId": 102
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [
+                    { "index": 1, "field": "categoryId", "reason": "Category 100 not found" },
+                    { "index": 1, "field": "tagIds", "reason": "Tag 101 not found" },
+                    { "index": 2, "field": "walletId", "reason": "Wallet 102 not found" }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_missing_required_description() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-01",
+                            "description": "Lunch",
+                            "priority": 1,
+                            "categoryId": 3,
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 2000,
+                            "date": "2025-04-02",
+                            "description": "   ",
+                            "priority": 2,
+                            "categoryId": 3,
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 3000,
+                            "date": "2025-04-03",
+                            "priority": 0,
+                            "categoryId": 1,
+                            "walletId": 1
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [
+                    { "index": 1, "field": "description", "reason": "Category 3 requires a description" }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_invalid_priority() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-01",
+                            "priority": 1,
+                            "categoryId": 1,
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 2000,
+                            "date": "2025-04-02",
+                            "priority": 5,
+                            "categoryId": 2,
+                            "walletId": 1
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [
+                    { "index": 1, "field": "priority", "reason": "Priority must be between 0 and 2" }
+                ]
+            })
+        );
+    }
+
+    /// Saves an expense with priority 3, which is only valid with `MAX_PRIORITY` set to 3.
+    /// The maximum priority is read once per process, so it's run in a dedicated process by
+    /// `test_save_bulk_handler_with_configured_max_priority`.
+    #[tokio::test]
+    #[ignore = "run with MAX_PRIORITY=3 by test_save_bulk_handler_with_configured_max_priority"]
+    async fn save_bulk_handler_with_max_priority_3() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "expenses": [
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-01",
+                            "priority": 3,
+                            "categoryId": 1,
+                            "walletId": 1
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[test]
+    fn test_save_bulk_handler_with_configured_max_priority() {
+        // Execute
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "handlers::expense::tests::save_bulk_handler_with_max_priority_3",
+                "--exact",
+                "--ignored",
+            ])
+            .env("MAX_PRIORITY", "3")
+            .output()
+            .unwrap();
+
+        // Assert
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert!(output.status.success(), "{stdout}");
+        assert!(stdout.contains("1 passed"), "{stdout}");
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_reports_every_invalid_item() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!([
+                    {
+                        "amount": 1000,
+                        "date": "2025-04-01",
+                        "priority": 1,
+                        "categoryId": 1,
+                        "walletId": 1
+                    },
+                    {
+                        "amount": 2000,
+                        "date": "2025-04-02",
+                        "priority": 1,
+                        "categoryId": 2,
+                        "walletId": 1,
+                        "tagIds": [1, 0]
+                    },
+                    {
+                        "amount": -3000,
+                        "date": "2025-04-03",
+                        "priority": 1,
+                        "walletId": 1
+                    }
+                ])
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+        let errors = body["errors"].as_array().unwrap();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["index"], 1);
+        assert_eq!(errors[0]["field"], "tagIds");
+        assert_eq!(errors[1]["index"], 2);
+        assert_eq!(errors[1]["field"], "amount");
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_missing_expenses_field() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{ "items": [] }"#))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(body["errors"][0]["field"], "expenses");
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_malformed_json() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{ "expenses": ["#))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert!(body["message"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_unknown_references() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/expenses/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "date": "2025-04-01",
+                    "priority": 1,
+                    "categoryId": 1,
+                    "walletId": 1,
+                    "tagIds": [1, 100]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*repo.updated_tag_ids.lock().unwrap(), None);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{ "field": "tagIds", "reason": "Tag 100 not found" }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_missing_required_description() {
+        // Prepare
+        let repo = MockExpenseRepository::new();
+        let app = expense_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/expenses/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 1000,
+                    "date": "2025-04-01",
+                    "description": "",
+                    "priority": 1,
+                    "categoryId": 3,
+                    "walletId": 1
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(*repo.updated_tag_ids.lock().unwrap(), None);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{ "field": "description", "reason": "Category 3 requires a description" }]
+            })
+        );
     }
 }
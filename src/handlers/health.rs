@@ -0,0 +1,124 @@
+use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::get};
+use std::sync::Arc;
+
+use crate::{
+    common::errors::AppError,
+    dtos::health::{PingResponse, VersionResponse},
+    repositories::util,
+    state::AppState,
+};
+
+/// Handles the routes to verify the service is up and which build is running.
+pub fn health_routes() -> Router<AppState> {
+    Router::new()
+        .route("/health", get(|| async { StatusCode::OK }))
+        .route("/ping", get(ping))
+        .route("/version", get(version))
+}
+
+/// Handler to show the round-trip time of a trivial query to the database.
+/// Unlike `/health`, it fails when the database can't be reached.
+async fn ping(
+    State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
+) -> Result<impl IntoResponse, AppError> {
+    let latency = util_repository.ping().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(PingResponse {
+            db_latency_ms: latency.as_millis() as u64,
+        }),
+    ))
+}
+
+/// Handler to show the version of the running build.
+/// The git SHA and build timestamp are read from the `GIT_SHA` and `BUILD_TIMESTAMP`
+/// environment variables at compile time.
+async fn version() -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(VersionResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_sha: option_env!("GIT_SHA").map(str::to_string),
+            build_timestamp: option_env!("BUILD_TIMESTAMP").map(str::to_string),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{Body, to_bytes},
+        http::Request,
+    };
+    use serde_json;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_health_handler() {
+        // Prepare
+        let app = health_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/health")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body_bytes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ping_handler() {
+        // Prepare
+        let app = health_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/ping")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<PingResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body, PingResponse { db_latency_ms: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_version_handler() {
+        // Prepare
+        let app = health_routes().with_state(AppState::mocked());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/version")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<VersionResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body.version, env!("CARGO_PKG_VERSION"));
+    }
+}
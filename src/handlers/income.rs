@@ -1,29 +1,50 @@
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{delete, get, post, put},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch, post, put},
 };
 use axum_extra::extract::WithRejection;
+use futures::{StreamExt, TryStreamExt, stream};
 use std::sync::Arc;
+use time::Date;
 
 use crate::{
-    common::errors::AppError,
-    dtos::income::{IndexIncomeQuery, IndexIncomeResponse, SaveBatchIncome, SaveIncome},
+    common::{
+        batch, csv,
+        errors::{AppError, FieldError},
+        extract::ValidatedBatch,
+    },
+    dtos::{
+        DateRangeQuery, LatestQuery,
+        income::{
+            DeleteBatchIncome, DeleteBatchIncomeResponse, IndexIncomeQuery, IndexIncomeResponse,
+            SaveBatchIncome, SaveIncome, ShowRecentIncomeResponse, UpdateIncome,
+        },
+    },
+    handlers::X_TOTAL_COUNT,
+    middlewares::auth::{self, AuthenticatedUser},
     repositories::income,
+    state::AppState,
 };
 
 /// Handles the routes related to incomes operations.
-pub fn income_routes() -> Router<Arc<dyn income::RepositoryOperation>> {
+pub fn income_routes() -> Router<AppState> {
     Router::new().nest(
         "/incomes",
         Router::new()
             .route("/", get(index))
             .route("/", post(save_bulk))
+            .route("/", delete(destroy_bulk))
             .route("/{id}", get(show))
             .route("/{id}", put(update))
+            .route("/{id}", patch(partial_update))
             .route("/{id}", delete(destroy))
+            .route("/export", get(export))
+            .route("/active-dates", get(index_active_dates))
+            .route("/stats", get(show_stats))
             .route("/latest", get(show_latest)),
     )
 }
@@ -32,85 +53,378 @@ pub fn income_routes() -> Router<Arc<dyn income::RepositoryOperation>> {
 async fn destroy(
     WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
     State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
-    income_repository.delete(id as i32).await?;
+    income_repository
+        .delete(id as i32, auth::user_id(&user))
+        .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Handles the bulk deletion of incomes by ID.
+/// Unknown IDs are skipped, so the response holds the number of incomes actually deleted.
+async fn destroy_bulk(
+    State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    WithRejection(Json(body), _): WithRejection<Json<DeleteBatchIncome>, AppError>,
+) -> Result<impl IntoResponse, AppError> {
+    if body.ids.is_empty() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "No items to delete".to_string(),
+        ));
+    }
+
+    let deleted = income_repository
+        .delete_bulk(&body.ids, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(DeleteBatchIncomeResponse { deleted })))
+}
+
+/// Handles exporting the list of incomes as a CSV attachment.
+/// The rows are streamed to the client as they are read from the database.
+async fn export(
+    Query(query): Query<IndexIncomeQuery>,
+    State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> impl IntoResponse {
+    let filename = export_filename(query.start_date, query.end_date);
+
+    let header_line = csv::line(["id", "amount", "date", "description", "wallet"]);
+    let rows = income_repository
+        .stream_export(&query, auth::user_id(&user))
+        .map_ok(|income| {
+            csv::line([
+                income.id.to_string(),
+                income.amount.to_string(),
+                income.date,
+                income.description.unwrap_or_default(),
+                income.wallet_name.unwrap_or_default(),
+            ])
+        })
+        .inspect_err(|error| tracing::error!("Failed to export incomes: {error}"));
+
+    let body = Body::from_stream(stream::once(async { Ok(header_line) }).chain(rows));
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+}
+
+/// Ensures that the wallets and tags referenced by the given incomes exist.
+/// Each income is given as its wallet ID along with its tag IDs.
+/// Responds with a bad request naming every offending field otherwise.
+/// The index of the offending income is only included for batch requests.
+async fn ensure_references_exist(
+    income_repository: &dyn income::RepositoryOperation,
+    references: &[(i32, &[i32])],
+    is_batch: bool,
+    user_id: Option<&str>,
+) -> Result<(), AppError> {
+    let wallet_ids = references
+        .iter()
+        .map(|(wallet_id, _)| *wallet_id)
+        .collect::<Vec<i32>>();
+    let tag_ids = references
+        .iter()
+        .flat_map(|(_, tag_ids)| tag_ids.iter().copied())
+        .collect::<Vec<i32>>();
+
+    let unknown_wallet_ids = income_repository
+        .find_unknown_wallet_ids(&wallet_ids, user_id)
+        .await?;
+    let unknown_tag_ids = if tag_ids.is_empty() {
+        Vec::new()
+    } else {
+        income_repository.find_unknown_tag_ids(&tag_ids).await?
+    };
+
+    let mut errors = Vec::<FieldError>::new();
+
+    for (i, (wallet_id, tag_ids)) in references.iter().enumerate() {
+        let index = is_batch.then_some(i);
+
+        if unknown_wallet_ids.contains(wallet_id) {
+            errors.push(FieldError {
+                index,
+                field: "walletId".into(),
+                reason: format!("Wallet {wallet_id} not found"),
+            });
+        }
+
+        for tag_id in tag_ids.iter() {
+            if unknown_tag_ids.contains(tag_id) {
+                errors.push(FieldError {
+                    index,
+                    field: "tagIds".into(),
+                    reason: format!("Tag {tag_id} not found"),
+                });
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::InvalidReferences(errors))
+}
+
+/// Builds the name of the exported CSV file from the queried date range.
+fn export_filename(start_date: Option<Date>, end_date: Option<Date>) -> String {
+    match (start_date, end_date) {
+        (Some(start_date), Some(end_date)) => format!("incomes_{start_date}_{end_date}.csv"),
+        (Some(start_date), None) => format!("incomes_since_{start_date}.csv"),
+        (None, Some(end_date)) => format!("incomes_until_{end_date}.csv"),
+        (None, None) => "incomes.csv".to_string(),
+    }
+}
+
 /// Handles showing the list of incomes.
 async fn index(
     Query(query): Query<IndexIncomeQuery>,
     State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let incomes = income_repository.find_all(&query).await?;
+    let page = income_repository
+        .find_all(&query, auth::user_id(&user))
+        .await?;
 
-    Ok((StatusCode::OK, Json(IndexIncomeResponse { incomes })))
+    Ok((
+        StatusCode::OK,
+        [(&X_TOTAL_COUNT, page.total.to_string())],
+        Json(IndexIncomeResponse {
+            incomes: page.items,
+            has_more: page.has_more,
+            total: page.total,
+        }),
+    ))
+}
+
+/// Handles showing the distinct dates with at least one income, in ascending order.
+async fn index_active_dates(
+    Query(query): Query<DateRangeQuery>,
+    State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let active_dates = income_repository
+        .find_active_dates(query.start_date, query.end_date, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(active_dates)))
 }
 
 /// Handles the bulk save of incomes.
 async fn save_bulk(
     State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
-    WithRejection(Json(body), _): WithRejection<Json<SaveBatchIncome>, AppError>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedBatch(body): ValidatedBatch<SaveBatchIncome>,
 ) -> Result<impl IntoResponse, AppError> {
-    income_repository.insert_bulk(body.incomes).await?;
+    if body.incomes.is_empty() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "No items to insert".to_string(),
+        ));
+    }
+    batch::ensure_batch_size(body.incomes.len())?;
+
+    let references = body
+        .incomes
+        .iter()
+        .map(|income| {
+            (
+                income.wallet_id,
+                income.tag_ids.as_deref().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<(i32, &[i32])>>();
+    ensure_references_exist(
+        income_repository.as_ref(),
+        &references,
+        true,
+        auth::user_id(&user),
+    )
+    .await?;
+
+    income_repository
+        .insert_bulk(body.incomes, auth::user_id(&user))
+        .await?;
 
     Ok(StatusCode::CREATED)
 }
 
+/// Handles the partial update of a specific income by ID.
+/// Only the supplied fields are updated.
+async fn partial_update(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    WithRejection(Json(body), _): WithRejection<Json<UpdateIncome>, AppError>,
+) -> Result<impl IntoResponse, AppError> {
+    if body.is_empty() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "No fields to update".to_string(),
+        ));
+    }
+
+    if let Some(wallet_id) = body.wallet_id {
+        ensure_references_exist(
+            income_repository.as_ref(),
+            &[(wallet_id, &[])],
+            false,
+            auth::user_id(&user),
+        )
+        .await?;
+    }
+
+    income_repository
+        .patch(id as i32, &body, auth::user_id(&user))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Handles the retrieval of a specific income by ID.
 async fn show(
     WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
     State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let income = income_repository.find_one(id as i32).await?;
+    let income = income_repository
+        .find_one(id as i32, auth::user_id(&user))
+        .await?;
 
     Ok((StatusCode::OK, Json(income)))
 }
 
 /// Handles the retrieval of the latest income.
+/// When a count is requested, the most recent incomes are returned as a list instead.
 async fn show_latest(
+    Query(query): Query<LatestQuery>,
+    State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<Response, AppError> {
+    if let Some(count) = query.count() {
+        let incomes = income_repository
+            .find_recent(count, auth::user_id(&user))
+            .await?;
+
+        return Ok((StatusCode::OK, Json(ShowRecentIncomeResponse { incomes })).into_response());
+    }
+
+    let Some(latest_income) = income_repository
+        .find_recent(1, auth::user_id(&user))
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+
+    Ok((StatusCode::OK, Json(latest_income)).into_response())
+}
+
+/// Handles showing the aggregated statistics of the incomes within the optional date range.
+async fn show_stats(
+    Query(query): Query<DateRangeQuery>,
     State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
-    let latest_income = income_repository.find_latest().await?;
+    let stats = income_repository
+        .find_stats(query.start_date, query.end_date, auth::user_id(&user))
+        .await?;
 
-    Ok((StatusCode::OK, Json(latest_income)))
+    Ok((StatusCode::OK, Json(stats)))
 }
 
 /// Handles the update of a specific income by ID.
 async fn update(
     WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
     State(income_repository): State<Arc<dyn income::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
     WithRejection(Json(body), _): WithRejection<Json<SaveIncome>, AppError>,
 ) -> Result<impl IntoResponse, AppError> {
-    income_repository.update(id as i32, &body).await?;
+    ensure_references_exist(
+        income_repository.as_ref(),
+        &[(body.wallet_id, body.tag_ids.as_deref().unwrap_or_default())],
+        false,
+        auth::user_id(&user),
+    )
+    .await?;
+
+    income_repository
+        .update(id as i32, &body, auth::user_id(&user))
+        .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::constants::DEFAULT_MAX_BATCH_SIZE;
     use crate::dtos::{
-        income::{IndexIncomeQuery, SaveIncome},
-        query_result::{IndexIncomeElement, ShowIncome, ShowLatestIncome, SimpleEntity},
+        income::{IndexIncomeQuery, SaveIncome, UpdateIncome},
+        query_result::{
+            ExportIncomeElement, IncomeStats, IndexIncomeElement, ShowIncome, ShowLatestIncome,
+            SimpleEntity, Tag,
+        },
     };
+    use crate::repositories::Page;
 
     use async_trait::async_trait;
     use axum::{
         body::{Body, to_bytes},
         http::{Request, StatusCode},
     };
+    use futures::stream::BoxStream;
     use serde_json;
     use sqlx::Error as SqlxError;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use tower::ServiceExt;
 
-    pub struct MockIncomeRepository;
+    /// Referenced IDs from this value onwards are treated as nonexistent by the mock repository.
+    const UNKNOWN_ID: i32 = 100;
+
+    /// The user that doesn't own any of the incomes in the mock repository.
+    const OTHER_USER_ID: &str = "mallory";
+
+    /// The income whose wallet no longer exists in the mock repository.
+    const ORPHANED_ID: i32 = 99;
+
+    pub struct MockIncomeRepository {
+        /// The IDs received by the last bulk deletion, if any bulk deletion happened.
+        deleted_ids: Mutex<Option<Vec<i32>>>,
+        /// The fields received by the last partial update, if any partial update happened.
+        patched_income: Mutex<Option<UpdateIncome>>,
+        /// The creator received by the last update, if any update happened.
+        created_by: Mutex<Option<Option<String>>>,
+        /// The tag IDs of each income received by the last bulk save, if any bulk save happened.
+        saved_tag_ids: Mutex<Option<Vec<Option<Vec<i32>>>>>,
+        /// The tag IDs received by the last update, if any update happened.
+        updated_tag_ids: Mutex<Option<Option<Vec<i32>>>>,
+    }
 
     impl MockIncomeRepository {
         pub fn new() -> Arc<Self> {
-            Arc::new(Self)
+            Arc::new(Self {
+                deleted_ids: Mutex::new(None),
+                patched_income: Mutex::new(None),
+                created_by: Mutex::new(None),
+                saved_tag_ids: Mutex::new(None),
+                updated_tag_ids: Mutex::new(None),
+            })
         }
     }
 
@@ -130,6 +444,8 @@ mod tests {
                     description: None,
                 },
             ],
+            has_more: false,
+            total: 2,
         }
     }
 
@@ -139,51 +455,244 @@ mod tests {
             amount: 8000,
             date: "2025-04-03".to_string(),
             description: Some("Latest test income".to_string()),
-            wallet: sqlx::types::Json(SimpleEntity {
+            wallet: Some(sqlx::types::Json(SimpleEntity {
                 id: 1,
                 name: "Bank Account".to_string(),
-            }),
+            })),
+            tags: sqlx::types::Json(vec![Tag {
+                id: 1,
+                name: "Recurring".to_string(),
+                is_important: false,
+            }]),
         }
     }
 
+    fn recent_incomes_response() -> Vec<ShowLatestIncome> {
+        vec![
+            show_latest_income_response(),
+            ShowLatestIncome {
+                id: 2,
+                date: "2025-04-02".to_string(),
+                ..show_latest_income_response()
+            },
+            ShowLatestIncome {
+                id: 1,
+                date: "2025-04-01".to_string(),
+                ..show_latest_income_response()
+            },
+        ]
+    }
+
     fn show_income_response(id: i32) -> ShowIncome {
         ShowIncome {
             amount: 5000,
             date: "2025-04-01".to_string(),
             description: Some(format!("Test income {id}")),
-            wallet: sqlx::types::Json(SimpleEntity {
+            wallet: Some(sqlx::types::Json(SimpleEntity {
                 id: 1,
                 name: "Bank Account".to_string(),
-            }),
+            })),
+            tags: sqlx::types::Json(vec![Tag {
+                id: 1,
+                name: "Recurring".to_string(),
+                is_important: false,
+            }]),
+        }
+    }
+
+    /// Builds the app state holding the given mock repository.
+    fn state(repo: Arc<MockIncomeRepository>) -> AppState {
+        AppState {
+            income_repository: repo,
+            ..AppState::mocked()
         }
     }
 
     #[async_trait]
     impl income::RepositoryOperation for MockIncomeRepository {
-        async fn delete(&self, _id: i32) -> Result<(), SqlxError> {
+        async fn delete(&self, _id: i32, _user_id: Option<&str>) -> Result<(), SqlxError> {
             Ok(())
         }
 
+        async fn delete_bulk(&self, ids: &[i32], _user_id: Option<&str>) -> Result<u64, SqlxError> {
+            *self.deleted_ids.lock().unwrap() = Some(ids.to_vec());
+            Ok(ids.iter().filter(|&&id| id < UNKNOWN_ID).count() as u64)
+        }
+
+        async fn find_active_dates(
+            &self,
+            start_date: Option<Date>,
+            end_date: Option<Date>,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<String>, SqlxError> {
+            let active_dates = ["2025-04-01", "2025-04-15", "2025-05-01"]
+                .into_iter()
+                .filter(|&date| {
+                    start_date.is_none_or(|start_date| date >= start_date.to_string().as_str())
+                        && end_date.is_none_or(|end_date| date <= end_date.to_string().as_str())
+                })
+                .map(str::to_string)
+                .collect();
+
+            Ok(active_dates)
+        }
+
         async fn find_all(
             &self,
-            _query: &IndexIncomeQuery,
-        ) -> Result<Vec<IndexIncomeElement>, SqlxError> {
-            Ok(index_income_response().incomes)
+            query: &IndexIncomeQuery,
+            _user_id: Option<&str>,
+        ) -> Result<Page<IndexIncomeElement>, SqlxError> {
+            let incomes = index_income_response().incomes;
+            let total = incomes.len() as i64;
+
+            if query.pagination.is_count_only() {
+                return Ok(Page::count_only(total, query.pagination.offset()));
+            }
+
+            Ok(Page::from_rows(incomes, query.pagination.limit(), total))
+        }
+
+        async fn find_recent(
+            &self,
+            limit: i64,
+            user_id: Option<&str>,
+        ) -> Result<Vec<ShowLatestIncome>, SqlxError> {
+            if user_id == Some(OTHER_USER_ID) {
+                return Ok(Vec::new());
+            }
+
+            Ok(recent_incomes_response()
+                .into_iter()
+                .take(limit as usize)
+                .collect())
+        }
+
+        async fn find_stats(
+            &self,
+            start_date: Option<Date>,
+            end_date: Option<Date>,
+            _user_id: Option<&str>,
+        ) -> Result<IncomeStats, SqlxError> {
+            let amounts = index_income_response()
+                .incomes
+                .into_iter()
+                .filter(|income| {
+                    start_date.is_none_or(|start_date| income.date >= start_date.to_string())
+                        && end_date.is_none_or(|end_date| income.date <= end_date.to_string())
+                })
+                .map(|income| income.amount)
+                .collect::<Vec<i32>>();
+
+            let count = amounts.len() as i64;
+            let sum = amounts.iter().copied().map(i64::from).sum::<i64>();
+
+            Ok(IncomeStats {
+                count,
+                sum,
+                average: (count > 0).then(|| sum / count),
+                min: amounts.iter().copied().min(),
+                max: amounts.iter().copied().max(),
+            })
+        }
+
+        async fn find_unknown_tag_ids(&self, tag_ids: &[i32]) -> Result<Vec<i32>, SqlxError> {
+            let mut unknown_tag_ids = tag_ids
+                .iter()
+                .copied()
+                .filter(|&tag_id| tag_id >= UNKNOWN_ID)
+                .collect::<Vec<i32>>();
+            unknown_tag_ids.sort_unstable();
+            unknown_tag_ids.dedup();
+
+            Ok(unknown_tag_ids)
         }
 
-        async fn find_latest(&self) -> Result<ShowLatestIncome, SqlxError> {
-            Ok(show_latest_income_response())
+        async fn find_unknown_wallet_ids(
+            &self,
+            wallet_ids: &[i32],
+            _user_id: Option<&str>,
+        ) -> Result<Vec<i32>, SqlxError> {
+            let mut unknown_wallet_ids = wallet_ids
+                .iter()
+                .copied()
+                .filter(|&wallet_id| wallet_id >= UNKNOWN_ID)
+                .collect::<Vec<i32>>();
+            unknown_wallet_ids.sort_unstable();
+            unknown_wallet_ids.dedup();
+
+            Ok(unknown_wallet_ids)
         }
 
-        async fn find_one(&self, id: i32) -> Result<ShowIncome, SqlxError> {
+        async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<ShowIncome, SqlxError> {
+            if user_id == Some(OTHER_USER_ID) {
+                return Err(SqlxError::RowNotFound);
+            }
+
+            if id == ORPHANED_ID {
+                return Ok(ShowIncome {
+                    wallet: None,
+                    ..show_income_response(id)
+                });
+            }
+
             Ok(show_income_response(id))
         }
 
-        async fn insert_bulk(&self, _incomes: Vec<SaveIncome>) -> Result<(), SqlxError> {
+        fn stream_export(
+            &self,
+            _query: &IndexIncomeQuery,
+            _user_id: Option<&str>,
+        ) -> BoxStream<'static, Result<ExportIncomeElement, SqlxError>> {
+            Box::pin(stream::iter(vec![
+                Ok(ExportIncomeElement {
+                    id: 1,
+                    amount: 5000,
+                    date: "2025-04-01".to_string(),
+                    description: Some("Salary, April".to_string()),
+                    wallet_name: Some("Bank Account".to_string()),
+                }),
+                Ok(ExportIncomeElement {
+                    id: 2,
+                    amount: 3000,
+                    date: "2025-04-02".to_string(),
+                    description: None,
+                    wallet_name: None,
+                }),
+            ]))
+        }
+
+        async fn insert_bulk(
+            &self,
+            incomes: Vec<SaveIncome>,
+            _user_id: Option<&str>,
+        ) -> Result<(), SqlxError> {
+            *self.saved_tag_ids.lock().unwrap() =
+                Some(incomes.into_iter().map(|income| income.tag_ids).collect());
+            Ok(())
+        }
+
+        async fn patch(
+            &self,
+            id: i32,
+            income: &UpdateIncome,
+            _user_id: Option<&str>,
+        ) -> Result<(), SqlxError> {
+            if id >= UNKNOWN_ID {
+                return Err(SqlxError::RowNotFound);
+            }
+
+            *self.patched_income.lock().unwrap() = Some(income.clone());
             Ok(())
         }
 
-        async fn update(&self, _id: i32, _income: &SaveIncome) -> Result<(), SqlxError> {
+        async fn update(
+            &self,
+            _id: i32,
+            income: &SaveIncome,
+            user_id: Option<&str>,
+        ) -> Result<(), SqlxError> {
+            *self.created_by.lock().unwrap() = Some(user_id.map(str::to_string));
+            *self.updated_tag_ids.lock().unwrap() = Some(income.tag_ids.clone());
             Ok(())
         }
     }
@@ -192,7 +701,7 @@ mod tests {
     async fn test_destroy_handler() {
         // Prepare
         let repo = MockIncomeRepository::new();
-        let app = income_routes().with_state(repo);
+        let app = income_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("DELETE")
@@ -207,11 +716,120 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NO_CONTENT);
     }
 
+    #[tokio::test]
+    async fn test_destroy_bulk_handler() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "ids": [1, 2, UNKNOWN_ID] }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<DeleteBatchIncomeResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body, DeleteBatchIncomeResponse { deleted: 2 });
+        assert_eq!(
+            *repo.deleted_ids.lock().unwrap(),
+            Some(vec![1, 2, UNKNOWN_ID])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_destroy_bulk_handler_empty_batch() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::json!({ "ids": [] }).to_string()))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*repo.deleted_ids.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_export_handler() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/export?startDate=2025-04-01&endDate=2025-04-30")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"incomes_2025-04-01_2025-04-30.csv\""
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+        assert_eq!(
+            body,
+            "id,amount,date,description,wallet\n\
+             1,5000,2025-04-01,\"Salary, April\",Bank Account\n\
+             2,3000,2025-04-02,,\n"
+        );
+    }
+
+    #[test]
+    fn test_export_filename() {
+        let start_date = Date::from_calendar_date(2025, time::Month::April, 1).unwrap();
+        let end_date = Date::from_calendar_date(2025, time::Month::April, 30).unwrap();
+
+        assert_eq!(
+            export_filename(Some(start_date), Some(end_date)),
+            "incomes_2025-04-01_2025-04-30.csv"
+        );
+        assert_eq!(
+            export_filename(Some(start_date), None),
+            "incomes_since_2025-04-01.csv"
+        );
+        assert_eq!(
+            export_filename(None, Some(end_date)),
+            "incomes_until_2025-04-30.csv"
+        );
+        assert_eq!(export_filename(None, None), "incomes.csv");
+    }
+
     #[tokio::test]
     async fn test_index_handler() {
         // Prepare
         let repo = MockIncomeRepository::new();
-        let app = income_routes().with_state(repo);
+        let app = income_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -231,11 +849,62 @@ mod tests {
         assert_eq!(body, index_income_response());
     }
 
+    #[tokio::test]
+    async fn test_index_handler_count_only() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes?limit=0")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexIncomeResponse>(&body_bytes).unwrap();
+
+        assert!(body.incomes.is_empty());
+        assert_eq!(body.total, index_income_response().total);
+    }
+
+    #[tokio::test]
+    async fn test_index_handler_total_count_header() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes?limit=1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&X_TOTAL_COUNT], "2");
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexIncomeResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body.incomes.len(), 1);
+        assert_eq!(body.total, 2);
+    }
+
     #[tokio::test]
     async fn test_save_bulk_handler() {
         // Prepare
         let repo = MockIncomeRepository::new();
-        let app = income_routes().with_state(repo);
+        let app = income_routes().with_state(state(repo));
 
         // Use serde_json::json! macro to create request body
         let request = Request::builder()
@@ -266,7 +935,7 @@ mod tests {
     async fn test_show_handler() {
         // Prepare
         let repo = MockIncomeRepository::new();
-        let app = income_routes().with_state(repo);
+        let app = income_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -287,14 +956,14 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_show_latest_handler() {
+    async fn test_show_handler_orphaned() {
         // Prepare
         let repo = MockIncomeRepository::new();
-        let app = income_routes().with_state(repo);
+        let app = income_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
-            .uri("/incomes/latest")
+            .uri(format!("/incomes/{ORPHANED_ID}"))
             .body(Body::empty())
             .unwrap();
 
@@ -305,37 +974,667 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let body = serde_json::from_slice::<ShowLatestIncome>(&body_bytes).unwrap();
+        let body = serde_json::from_slice::<ShowIncome>(&body_bytes).unwrap();
 
-        assert_eq!(body, show_latest_income_response());
+        assert_eq!(body.wallet, None);
+        assert_eq!(body.amount, 5000);
     }
 
     #[tokio::test]
-    async fn test_update_handler() {
+    async fn test_show_handler_other_user() {
         // Prepare
         let repo = MockIncomeRepository::new();
-        let app = income_routes().with_state(repo);
+        let app = income_routes()
+            .layer(Extension(AuthenticatedUser(OTHER_USER_ID.to_string())))
+            .with_state(state(repo));
 
-        // Use serde_json::json! macro to create request body
         let request = Request::builder()
-            .method("PUT")
+            .method("GET")
             .uri("/incomes/1")
-            .header("Content-Type", "application/json")
-            .body(Body::from(
-                serde_json::json!({
-                    "amount": 5000,
-                    "date": "2025-04-01",
-                    "description": "Updated test income",
-                    "walletId": 1
-                })
-                .to_string(),
-            ))
+            .body(Body::empty())
             .unwrap();
 
         // Execute
         let response = app.oneshot(request).await.unwrap();
 
         // Assert
-        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_index_active_dates_handler() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/active-dates?endDate=2025-04-30")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<Vec<String>>(&body_bytes).unwrap();
+
+        assert_eq!(body, vec!["2025-04-01", "2025-04-15"]);
+    }
+
+    #[tokio::test]
+    async fn test_show_stats_handler() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/stats?startDate=2025-04-01&endDate=2025-04-30")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IncomeStats>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            IncomeStats {
+                count: 2,
+                sum: 8000,
+                average: Some(4000),
+                min: Some(3000),
+                max: Some(5000),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_stats_handler_empty_range() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/stats?startDate=2025-05-01&endDate=2025-05-31")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "count": 0,
+                "sum": 0,
+                "average": null,
+                "min": null,
+                "max": null
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_latest_handler() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/latest")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<ShowLatestIncome>(&body_bytes).unwrap();
+
+        assert_eq!(body, show_latest_income_response());
+    }
+
+    #[tokio::test]
+    async fn test_show_latest_handler_without_incomes() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes()
+            .with_state(state(repo))
+            .layer(Extension(AuthenticatedUser(OTHER_USER_ID.to_string())));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/latest")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body_bytes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_show_latest_handler_with_count() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/incomes/latest?count=2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<ShowRecentIncomeResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.incomes,
+            recent_incomes_response()
+                .into_iter()
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_handler() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        // Use serde_json::json! macro to create request body
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 5000,
+                    "date": "2025-04-01",
+                    "description": "Updated test income",
+                    "walletId": 1
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_records_creator() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes()
+            .layer(Extension(AuthenticatedUser("alice".to_string())))
+            .with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 5000,
+                    "date": "2025-04-01",
+                    "description": "Updated test income",
+                    "walletId": 1
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            *repo.created_by.lock().unwrap(),
+            Some(Some("alice".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_empty_batch() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::json!({ "incomes": [] }).to_string()))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(body, serde_json::json!({ "message": "No items to insert" }));
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_batch_too_large() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let income = serde_json::json!({
+            "amount": 5000,
+            "date": "2025-04-01",
+            "walletId": 1
+        });
+        let incomes = vec![income; DEFAULT_MAX_BATCH_SIZE + 1];
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "incomes": incomes }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({ "message": "Batch too large: 501 items exceeds the maximum of 500" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_invalid_item() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "incomes": [
+                        {
+                            "amount": 5000,
+                            "date": "2025-04-01",
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 3000,
+                            "date": "2025-04-31",
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 1000,
+                            "date": "2025-04-03",
+                            "walletId": 1
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+        let errors = body["errors"].as_array().unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["index"], 1);
+        assert_eq!(errors[0]["field"], "date");
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_unknown_wallet() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "incomes": [
+                        {
+                            "amount": 5000,
+                            "date": "2025-04-01",
+                            "walletId": 1
+                        },
+                        {
+                            "amount": 3000,
+                            "date": "2025-04-02",
+                            "walletId": 100
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{ "index": 1, "field": "walletId", "reason": "Wallet 100 not found" }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_with_tags() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "incomes": [
+                        {
+                            "amount": 5000,
+                            "date": "2025-04-01",
+                            "walletId": 1,
+                            "tagIds": [1, 2]
+                        },
+                        {
+                            "amount": 3000,
+                            "date": "2025-04-02",
+                            "walletId": 1
+                        }
+                    ]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            *repo.saved_tag_ids.lock().unwrap(),
+            Some(vec![Some(vec![1, 2]), None])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_bulk_handler_unknown_tag() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/incomes")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "incomes": [{
+                        "amount": 5000,
+                        "date": "2025-04-01",
+                        "walletId": 100,
+                        "tagIds": [1, 100]
+                    }]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [
+                    { "index": 0, "field": "walletId", "reason": "Wallet 100 not found" },
+                    { "index": 0, "field": "tagIds", "reason": "Tag 100 not found" }
+                ]
+            })
+        );
+        assert_eq!(*repo.saved_tag_ids.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_with_tags() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 5000,
+                    "date": "2025-04-01",
+                    "walletId": 1,
+                    "tagIds": []
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        // An empty list clears the tags, unlike an omitted one
+        assert_eq!(*repo.updated_tag_ids.lock().unwrap(), Some(Some(vec![])));
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_unknown_wallet() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "amount": 5000,
+                    "date": "2025-04-01",
+                    "walletId": 100
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{ "field": "walletId", "reason": "Wallet 100 not found" }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_update_handler_description_only() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PATCH")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "description": "Annual bonus" }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            *repo.patched_income.lock().unwrap(),
+            Some(UpdateIncome {
+                amount: None,
+                date: None,
+                description: Some(Some("Annual bonus".to_string())),
+                wallet_id: None,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_update_handler_not_found() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("PATCH")
+            .uri("/incomes/100")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "amount": 5000 }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_partial_update_handler_empty_body() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PATCH")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*repo.patched_income.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_partial_update_handler_unknown_wallet() {
+        // Prepare
+        let repo = MockIncomeRepository::new();
+        let app = income_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PATCH")
+            .uri("/incomes/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "walletId": 100 }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*repo.patched_income.lock().unwrap(), None);
     }
 }
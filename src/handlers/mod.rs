@@ -1,5 +1,12 @@
+pub mod dashboard;
 pub mod expense;
+pub mod health;
 pub mod income;
 pub mod summary;
 pub mod util;
 pub mod wallet;
+
+use axum::http::HeaderName;
+
+/// The header carrying the number of records matching a listing query, regardless of its pagination.
+pub static X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
@@ -1,38 +1,194 @@
-use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::post,
+};
 use axum_extra::extract::WithRejection;
+use lazy_static::lazy_static;
 use std::sync::Arc;
+use time::OffsetDateTime;
 
 use crate::{
-    common::errors::AppError, dtos::summary::GenerateSummaryRequest, repositories::summary,
+    common::{csv, env, errors::AppError},
+    constants::DEFAULT_MAX_SUMMARY_DAYS,
+    dtos::{
+        query_result::ShowSummary,
+        summary::{GenerateSummaryRequest, GenerateTimeseriesRequest, ShowTimeseriesResponse},
+    },
+    middlewares::auth::{self, AuthenticatedUser},
+    repositories::summary,
+    services::timeseries,
+    state::AppState,
 };
 
+lazy_static! {
+    /// The longest date range of a summary in days, read once from the `MAX_SUMMARY_DAYS` environment variable.
+    static ref MAX_SUMMARY_DAYS: i64 = env::parse_or("MAX_SUMMARY_DAYS", DEFAULT_MAX_SUMMARY_DAYS);
+}
+
 /// Handles the routes related to summary operations.
-pub fn summary_routes() -> Router<Arc<dyn summary::RepositoryOperation>> {
+pub fn summary_routes() -> Router<AppState> {
     Router::new().nest(
         "/summaries/generate",
-        Router::new().route("/raw", post(generate)),
+        Router::new()
+            .route("/raw", post(generate))
+            .route("/csv", post(generate_csv))
+            .route("/timeseries", post(generate_timeseries)),
     )
 }
 
 /// Handles the generation of a summary based on request parameters.
 async fn generate(
     State(summary_repository): State<Arc<dyn summary::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
     WithRejection(Json(body), _): WithRejection<Json<GenerateSummaryRequest>, AppError>,
 ) -> Result<impl IntoResponse, AppError> {
-    let summary = summary_repository.generate_raw(&body).await?;
+    validate_summary_dates(&body)?;
+    let summary = summary_repository
+        .generate_raw(&body, auth::user_id(&user))
+        .await?;
 
     Ok((StatusCode::OK, Json(summary)))
 }
 
+/// Handles the generation of a summary as a CSV attachment.
+async fn generate_csv(
+    State(summary_repository): State<Arc<dyn summary::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    WithRejection(Json(body), _): WithRejection<Json<GenerateSummaryRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError> {
+    validate_summary_dates(&body)?;
+
+    let summary = summary_repository
+        .generate_raw(&body, auth::user_id(&user))
+        .await?;
+    let filename = format!("summary_{}_{}.csv", body.start_date, body.end_date);
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        summary_csv(&summary),
+    ))
+}
+
+/// Handles the generation of a time series of income and expense totals.
+/// Buckets without any record within the requested date range are included with zero amounts.
+async fn generate_timeseries(
+    State(summary_repository): State<Arc<dyn summary::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    WithRejection(Json(body), _): WithRejection<Json<GenerateTimeseriesRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError> {
+    if body.start_date > body.end_date {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "startDate must not be after endDate".to_string(),
+        ));
+    }
+
+    let totals = summary_repository
+        .generate_timeseries(&body, auth::user_id(&user))
+        .await?;
+    let points =
+        timeseries::fill_buckets(body.start_date, body.end_date, body.granularity, &totals);
+
+    Ok((StatusCode::OK, Json(ShowTimeseriesResponse { points })))
+}
+
+/// Rejects summary requests with a date after the current UTC date when `rejectFutureDates` is set,
+/// or spanning more days than the configured maximum, so that a mistyped year doesn't scan the whole history.
+pub fn validate_summary_dates(body: &GenerateSummaryRequest) -> Result<(), AppError> {
+    let today = OffsetDateTime::now_utc().date();
+    let days = (body.end_date - body.start_date).whole_days();
+
+    if days > *MAX_SUMMARY_DAYS {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "The date range spans {days} days, exceeding the maximum of {}",
+                *MAX_SUMMARY_DAYS
+            ),
+        ));
+    }
+
+    if body.reject_future_dates && (body.start_date > today || body.end_date > today) {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            format!("startDate and endDate must not be after {today}"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Flattens a summary into CSV rows of `section,parent_category,category,amount`.
+/// Rows without a parent category and category hold the total of their section,
+/// and rows without a category hold the subtotal of their parent category.
+/// Income rows hold the wallet name in the category column.
+fn summary_csv(summary: &ShowSummary) -> String {
+    let mut content = csv::line(["section", "parent_category", "category", "amount"]);
+
+    content.push_str(&csv::line([
+        "expense",
+        "",
+        "",
+        &summary.expense.amount.to_string(),
+    ]));
+
+    for parent_category in &summary.expense.group_summary.parent_categories {
+        content.push_str(&csv::line([
+            "expense",
+            &parent_category.name,
+            "",
+            &parent_category.amount.to_string(),
+        ]));
+
+        for category in &parent_category.categories {
+            content.push_str(&csv::line([
+                "expense",
+                &parent_category.name,
+                &category.name,
+                &category.amount.to_string(),
+            ]));
+        }
+    }
+
+    content.push_str(&csv::line([
+        "income",
+        "",
+        "",
+        &summary.income.amount.to_string(),
+    ]));
+
+    for wallet in &summary.income.group_summary.wallets {
+        content.push_str(&csv::line([
+            "income",
+            "",
+            &wallet.name,
+            &wallet.amount.to_string(),
+        ]));
+    }
+
+    content
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::dtos::{
         query_result::{
-            ExpenseGroupedSummary, ExpenseParentCategory, ExpensePriority, ExpenseSummary,
-            IncomeGroupedSummary, IncomeSummary, ShowSummary, SimpleAmountEntity,
+            ExpenseCategory, ExpenseGroupedSummary, ExpenseParentCategory, ExpensePriority,
+            ExpenseSummary, IncomeGroupedSummary, IncomeSummary, ShowSummary, SimpleAmountEntity,
+            TimeseriesTotal, TransferNetFlow,
         },
-        summary::GenerateSummaryRequest,
+        summary::{GenerateSummaryRequest, TimeseriesPoint},
     };
 
     use async_trait::async_trait;
@@ -42,14 +198,20 @@ mod tests {
     };
     use serde_json;
     use sqlx::Error as SqlxError;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use time::Date;
     use tower::ServiceExt;
 
-    pub struct MockSummaryRepository;
+    pub struct MockSummaryRepository {
+        /// The parent category of the last generated summary, if any summary was generated.
+        parent_category_id: Mutex<Option<Option<i32>>>,
+    }
 
     impl MockSummaryRepository {
         pub fn new() -> Arc<Self> {
-            Arc::new(Self)
+            Arc::new(Self {
+                parent_category_id: Mutex::new(None),
+            })
         }
     }
 
@@ -57,19 +219,25 @@ mod tests {
         ShowSummary {
             expense: sqlx::types::Json(ExpenseSummary {
                 amount: 5000,
+                expense_count: 6,
+                transfer_fees: 150,
                 group_summary: ExpenseGroupedSummary {
                     parent_categories: vec![
                         ExpenseParentCategory {
                             name: "Daily Expenses".to_string(),
                             amount: 3000,
                             categories: vec![
-                                SimpleAmountEntity {
+                                ExpenseCategory {
                                     name: "Food".to_string(),
                                     amount: 2000,
+                                    budget: Some(1500),
+                                    over_budget: true,
                                 },
-                                SimpleAmountEntity {
+                                ExpenseCategory {
                                     name: "Transportation".to_string(),
                                     amount: 1000,
+                                    budget: Some(1000),
+                                    over_budget: false,
                                 },
                             ],
                         },
@@ -77,13 +245,17 @@ mod tests {
                             name: "Monthly Bills".to_string(),
                             amount: 2000,
                             categories: vec![
-                                SimpleAmountEntity {
+                                ExpenseCategory {
                                     name: "Rent".to_string(),
                                     amount: 1500,
+                                    budget: None,
+                                    over_budget: false,
                                 },
-                                SimpleAmountEntity {
+                                ExpenseCategory {
                                     name: "Utilities".to_string(),
                                     amount: 500,
+                                    budget: None,
+                                    over_budget: false,
                                 },
                             ],
                         },
@@ -106,6 +278,7 @@ mod tests {
             }),
             income: sqlx::types::Json(IncomeSummary {
                 amount: 8000,
+                income_count: 2,
                 group_summary: IncomeGroupedSummary {
                     wallets: vec![
                         SimpleAmountEntity {
@@ -119,6 +292,19 @@ mod tests {
                     ],
                 },
             }),
+            transfer_net_flow: sqlx::types::Json(TransferNetFlow {
+                volume: 3000,
+                fees: 150,
+                transfer_count: 2,
+            }),
+        }
+    }
+
+    /// Builds the app state holding the given mock repository.
+    fn state(repo: Arc<MockSummaryRepository>) -> AppState {
+        AppState {
+            summary_repository: repo,
+            ..AppState::mocked()
         }
     }
 
@@ -126,17 +312,32 @@ mod tests {
     impl summary::RepositoryOperation for MockSummaryRepository {
         async fn generate_raw(
             &self,
-            _request: &GenerateSummaryRequest,
+            request: &GenerateSummaryRequest,
+            _user_id: Option<&str>,
         ) -> Result<ShowSummary, SqlxError> {
+            *self.parent_category_id.lock().unwrap() = Some(request.parent_category_id);
+
             Ok(show_summary_response())
         }
+
+        async fn generate_timeseries(
+            &self,
+            _request: &GenerateTimeseriesRequest,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<TimeseriesTotal>, SqlxError> {
+            Ok(vec![TimeseriesTotal {
+                bucket: Date::from_calendar_date(2025, time::Month::March, 31).unwrap(),
+                expense: 1500,
+                income: 8000,
+            }])
+        }
     }
 
     #[tokio::test]
     async fn test_generate_handler() {
         // Prepare
         let repo = MockSummaryRepository::new();
-        let app = summary_routes().with_state(repo);
+        let app = summary_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("POST")
@@ -163,5 +364,333 @@ mod tests {
 
         assert_eq!(body.expense.amount, show_summary_response().expense.amount);
         assert_eq!(body.income.amount, show_summary_response().income.amount);
+        assert_eq!(body.expense.expense_count, 6);
+        assert_eq!(body.expense.transfer_fees, 150);
+        assert_eq!(body.income.income_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_generate_handler_reports_transfer_net_flow() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/raw")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2025-03-01",
+                    "endDate": "2025-04-01",
+                    "excludeCategoryIds": []
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body["transferNetFlow"],
+            serde_json::json!({ "volume": 3000, "fees": 150, "transferCount": 2 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_handler_with_parent_category() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/raw")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2025-03-01",
+                    "endDate": "2025-04-01",
+                    "excludeCategoryIds": [],
+                    "parentCategoryId": 2
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*repo.parent_category_id.lock().unwrap(), Some(Some(2)));
+    }
+
+    #[tokio::test]
+    async fn test_generate_handler_invalid_parent_category() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/raw")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2025-03-01",
+                    "endDate": "2025-04-01",
+                    "excludeCategoryIds": [],
+                    "parentCategoryId": 0
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(*repo.parent_category_id.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_generate_handler_rejects_future_dates() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+        let tomorrow = OffsetDateTime::now_utc().date().next_day().unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/raw")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": tomorrow.to_string(),
+                    "endDate": tomorrow.to_string(),
+                    "excludeCategoryIds": [],
+                    "rejectFutureDates": true
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_generate_handler_rejects_long_date_range() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/raw")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2023-01-01",
+                    "endDate": "2025-01-01",
+                    "excludeCategoryIds": []
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body["message"],
+            "The date range spans 731 days, exceeding the maximum of 366"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_handler_allows_future_dates_by_default() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+        let tomorrow = OffsetDateTime::now_utc().date().next_day().unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/raw")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": tomorrow.to_string(),
+                    "endDate": tomorrow.to_string(),
+                    "excludeCategoryIds": []
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_generate_csv_handler() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/csv")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2025-03-01",
+                    "endDate": "2025-04-01",
+                    "excludeCategoryIds": []
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/csv; charset=utf-8"
+        );
+        assert_eq!(
+            response.headers().get(header::CONTENT_DISPOSITION).unwrap(),
+            "attachment; filename=\"summary_2025-03-01_2025-04-01.csv\""
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+        assert_eq!(
+            body.lines().next(),
+            Some("section,parent_category,category,amount")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_timeseries_handler() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/timeseries")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2025-03-25",
+                    "endDate": "2025-04-08",
+                    "granularity": "week"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<ShowTimeseriesResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.points,
+            vec![
+                TimeseriesPoint {
+                    label: "2025-W13".to_string(),
+                    expense: 0,
+                    income: 0,
+                },
+                TimeseriesPoint {
+                    label: "2025-W14".to_string(),
+                    expense: 1500,
+                    income: 8000,
+                },
+                TimeseriesPoint {
+                    label: "2025-W15".to_string(),
+                    expense: 0,
+                    income: 0,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_timeseries_handler_reversed_range() {
+        // Prepare
+        let repo = MockSummaryRepository::new();
+        let app = summary_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/summaries/generate/timeseries")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "startDate": "2025-04-08",
+                    "endDate": "2025-03-25",
+                    "granularity": "day"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_summary_csv() {
+        let expected = "\
+section,parent_category,category,amount
+expense,,,5000
+expense,Daily Expenses,,3000
+expense,Daily Expenses,Food,2000
+expense,Daily Expenses,Transportation,1000
+expense,Monthly Bills,,2000
+expense,Monthly Bills,Rent,1500
+expense,Monthly Bills,Utilities,500
+income,,,8000
+income,,Salary,6000
+income,,Freelance,2000
+";
+
+        assert_eq!(summary_csv(&show_summary_response()), expected);
     }
 }
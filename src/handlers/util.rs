@@ -1,51 +1,71 @@
 use axum::{
-    Json, Router,
-    extract::{Query, State},
+    Extension, Json, Router,
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
-    routing::get,
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
 };
+use axum_extra::extract::WithRejection;
 use std::sync::Arc;
 
 use crate::{
-    common::errors::AppError,
-    dtos::{
-        Pagination,
-        util::{
-            IndexCategoriesResponse, IndexParentCategoriesResponse, IndexTagsQuery,
-            IndexTagsResponse,
-        },
+    common::{
+        errors::{AppError, FieldError},
+        extract::ValidatedJson,
     },
-    repositories::util,
+    dtos::util::{
+        IndexCategoriesQuery, IndexCategoriesResponse, IndexCategoriesWithParentResponse,
+        IndexParentCategoriesQuery, IndexParentCategoriesResponse, IndexTagsQuery,
+        IndexTagsResponse, ReparentCategoryRequest, SaveCategoryBudgetRequest,
+        SetTagImportanceRequest, SetTagImportanceResponse,
+    },
+    middlewares::auth::{self, AuthenticatedUser},
+    repositories::util::{self, ReparentOutcome},
+    state::AppState,
 };
 
-pub fn util_routes() -> Router<Arc<dyn util::RepositoryOperation>> {
+pub fn util_routes() -> Router<AppState> {
     Router::new()
         .route("/categories", get(index_categories))
+        .route("/categories/{id}/parent", put(reparent_category))
+        .route(
+            "/categories/{id}/budget",
+            get(show_category_budget).put(save_category_budget),
+        )
         .route("/parent-categories", get(index_parent_categories))
         .route("/tags", get(index_tags))
+        .route("/tags/importance", post(set_tag_importance))
 }
 
 /// Handler to list all categories.
+/// The parent category of each category is included when `includeParent` is `true`.
 async fn index_categories(
     State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
-    Query(query): Query<Pagination>,
-) -> Result<impl IntoResponse, AppError> {
-    let categories = util_repository
-        .find_many_categories(query.offset(), query.limit())
-        .await?;
+    Query(query): Query<IndexCategoriesQuery>,
+) -> Result<Response, AppError> {
+    if query.include_parent.unwrap_or(false) {
+        let categories = util_repository
+            .find_many_categories_with_parent(&query)
+            .await?;
+
+        return Ok((
+            StatusCode::OK,
+            Json(IndexCategoriesWithParentResponse { categories }),
+        )
+            .into_response());
+    }
 
-    Ok((StatusCode::OK, Json(IndexCategoriesResponse { categories })))
+    let categories = util_repository.find_many_categories(&query).await?;
+
+    Ok((StatusCode::OK, Json(IndexCategoriesResponse { categories })).into_response())
 }
 
 /// Handler to list all parent categories.
 async fn index_parent_categories(
     State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
-    Query(query): Query<Pagination>,
+    Query(query): Query<IndexParentCategoriesQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let parent_categories = util_repository
-        .find_many_parent_categories(query.offset(), query.limit())
-        .await?;
+    let parent_categories = util_repository.find_many_parent_categories(&query).await?;
 
     Ok((
         StatusCode::OK,
@@ -58,21 +78,84 @@ async fn index_tags(
     State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
     Query(query): Query<IndexTagsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let tags = util_repository
-        .find_many_tags(
-            query.mark_important_value,
-            query.pagination.offset(),
-            query.pagination.limit(),
-        )
-        .await?;
+    let tags = util_repository.find_many_tags(&query).await?;
 
     Ok((StatusCode::OK, Json(IndexTagsResponse { tags })))
 }
 
+/// Handler to mark several tags as important or not at once.
+async fn set_tag_importance(
+    State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
+    ValidatedJson(body): ValidatedJson<SetTagImportanceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if body.tag_ids.is_empty() {
+        return Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "No tags to update".to_string(),
+        ));
+    }
+
+    let updated = util_repository
+        .set_importance_bulk(&body.tag_ids, body.is_important)
+        .await?;
+
+    Ok((StatusCode::OK, Json(SetTagImportanceResponse { updated })))
+}
+
+/// Handler to move a category to a different parent category.
+async fn reparent_category(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
+    ValidatedJson(body): ValidatedJson<ReparentCategoryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let outcome = util_repository
+        .reparent_category(id as i32, body.parent_category_id)
+        .await?;
+
+    match outcome {
+        ReparentOutcome::Moved => Ok(StatusCode::NO_CONTENT),
+        ReparentOutcome::UnknownParent => Err(AppError::InvalidReferences(vec![FieldError {
+            index: None,
+            field: "parentCategoryId".into(),
+            reason: format!("Parent category {} not found", body.parent_category_id),
+        }])),
+    }
+}
+
+/// Handler to show the monthly budget of a category.
+async fn show_category_budget(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let budget = util_repository
+        .find_category_budget(id as i32, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(budget)))
+}
+
+/// Handler to set the monthly budget of a category, replacing the existing one if any.
+async fn save_category_budget(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(util_repository): State<Arc<dyn util::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedJson(body): ValidatedJson<SaveCategoryBudgetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let budget = util_repository
+        .upsert_category_budget(id as i32, body.monthly_limit, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(budget)))
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use crate::dtos::query_result::{ParentCategory, SimpleEntity, Tag};
+    use crate::dtos::{
+        SortDirection,
+        query_result::{CategoryBudget, CategoryWithParent, ParentCategory, SimpleEntity, Tag},
+    };
     use async_trait::async_trait;
     use axum::{
         body::{Body, to_bytes},
@@ -80,26 +163,62 @@ mod tests {
     };
     use serde_json;
     use sqlx::Error as SqlxError;
-    use std::sync::Arc;
+    use std::{
+        cmp::Reverse,
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
     use tower::ServiceExt;
 
-    pub struct MockUtilRepository;
+    /// Referenced IDs from this value onwards are treated as nonexistent by the mock repository.
+    const UNKNOWN_ID: i32 = 100;
+
+    pub struct MockUtilRepository {
+        /// The monthly limits set so far, keyed by category ID.
+        budgets: Mutex<HashMap<i32, i32>>,
+        /// The arguments of the last bulk importance update, if any.
+        importance_update: Mutex<Option<(Vec<i32>, bool)>>,
+    }
 
     impl MockUtilRepository {
         pub fn new() -> Arc<Self> {
-            Arc::new(Self)
+            Arc::new(Self {
+                budgets: Mutex::new(HashMap::new()),
+                importance_update: Mutex::new(None),
+            })
         }
     }
 
     fn categories_response() -> Vec<SimpleEntity> {
+        categories_with_parent_response()
+            .into_iter()
+            .map(|category| SimpleEntity {
+                id: category.id,
+                name: category.name,
+            })
+            .collect()
+    }
+
+    fn categories_with_parent_response() -> Vec<CategoryWithParent> {
         vec![
-            SimpleEntity {
+            CategoryWithParent {
                 id: 1,
                 name: "Food".to_string(),
+                parent_category_id: 1,
+                parent_name: "Daily Expenses".to_string(),
             },
-            SimpleEntity {
+            CategoryWithParent {
                 id: 2,
                 name: "Transportation".to_string(),
+                parent_category_id: 1,
+                parent_name: "Daily Expenses".to_string(),
+            },
+            CategoryWithParent {
+                id: 3,
+                name: "Rent".to_string(),
+                parent_category_id: 2,
+                parent_name: "Monthly Bills".to_string(),
             },
         ]
     }
@@ -149,34 +268,156 @@ mod tests {
                 name: "Groceries".to_string(),
                 is_important: false,
             },
+            Tag {
+                id: 3,
+                name: "Rent".to_string(),
+                is_important: false,
+            },
         ]
     }
 
+    /// Builds the app state holding the given mock repository.
+    fn state(repo: Arc<MockUtilRepository>) -> AppState {
+        AppState {
+            util_repository: repo,
+            ..AppState::mocked()
+        }
+    }
+
     #[async_trait]
     impl util::RepositoryOperation for MockUtilRepository {
         async fn find_many_categories(
             &self,
-            _offset: i64,
-            _limit: i64,
+            query: &IndexCategoriesQuery,
         ) -> Result<Vec<SimpleEntity>, SqlxError> {
-            Ok(categories_response())
+            let categories = self
+                .find_many_categories_with_parent(query)
+                .await?
+                .into_iter()
+                .map(|category| SimpleEntity {
+                    id: category.id,
+                    name: category.name,
+                })
+                .collect();
+
+            Ok(categories)
+        }
+
+        async fn find_many_categories_with_parent(
+            &self,
+            query: &IndexCategoriesQuery,
+        ) -> Result<Vec<CategoryWithParent>, SqlxError> {
+            let mut categories = categories_with_parent_response()
+                .into_iter()
+                .filter(|category| match query.parent_category_id {
+                    Some(id) => category.parent_category_id == i64::from(id),
+                    None => true,
+                })
+                .collect::<Vec<_>>();
+
+            if SortDirection::is_descending(query.sort_dir) {
+                categories.sort_by_key(|category| Reverse(category.name.to_lowercase()));
+            }
+
+            Ok(categories)
         }
 
         async fn find_many_parent_categories(
             &self,
-            _offset: i64,
-            _limit: i64,
+            query: &IndexParentCategoriesQuery,
         ) -> Result<Vec<ParentCategory>, SqlxError> {
-            Ok(parent_categories_response())
+            let parent_categories = parent_categories_response()
+                .into_iter()
+                .filter(|parent_category| match &query.q {
+                    Some(q) => parent_category
+                        .name
+                        .to_lowercase()
+                        .contains(&q.to_lowercase()),
+                    None => true,
+                })
+                .collect();
+
+            Ok(parent_categories)
+        }
+
+        async fn find_many_tags(&self, query: &IndexTagsQuery) -> Result<Vec<Tag>, SqlxError> {
+            let mut tags = tags_response()
+                .into_iter()
+                .filter(|tag| match &query.q {
+                    Some(q) => tag.name.to_lowercase().contains(&q.to_lowercase()),
+                    None => true,
+                })
+                .collect::<Vec<_>>();
+
+            if SortDirection::is_descending(query.sort_dir) {
+                tags.sort_by_key(|tag| (!tag.is_important, Reverse(tag.name.to_lowercase())));
+            }
+
+            Ok(tags)
+        }
+
+        async fn reparent_category(
+            &self,
+            id: i32,
+            parent_category_id: i32,
+        ) -> Result<ReparentOutcome, SqlxError> {
+            if id >= UNKNOWN_ID {
+                return Err(SqlxError::RowNotFound);
+            }
+
+            if parent_category_id >= UNKNOWN_ID {
+                return Ok(ReparentOutcome::UnknownParent);
+            }
+
+            Ok(ReparentOutcome::Moved)
         }
 
-        async fn find_many_tags(
+        async fn set_importance_bulk(
             &self,
-            _mark_important_value: Option<bool>,
-            _offset: i64,
-            _limit: i64,
-        ) -> Result<Vec<Tag>, SqlxError> {
-            Ok(tags_response())
+            ids: &[i32],
+            is_important: bool,
+        ) -> Result<u64, SqlxError> {
+            *self.importance_update.lock().unwrap() = Some((ids.to_vec(), is_important));
+            Ok(ids.iter().filter(|&&id| id < UNKNOWN_ID).count() as u64)
+        }
+
+        async fn find_category_budget(
+            &self,
+            category_id: i32,
+            _user_id: Option<&str>,
+        ) -> Result<CategoryBudget, SqlxError> {
+            let budgets = self.budgets.lock().unwrap();
+            let monthly_limit = budgets.get(&category_id).ok_or(SqlxError::RowNotFound)?;
+
+            Ok(CategoryBudget {
+                category_id,
+                monthly_limit: *monthly_limit,
+            })
+        }
+
+        async fn upsert_category_budget(
+            &self,
+            category_id: i32,
+            monthly_limit: i32,
+            _user_id: Option<&str>,
+        ) -> Result<CategoryBudget, SqlxError> {
+            if category_id >= UNKNOWN_ID {
+                return Err(SqlxError::RowNotFound);
+            }
+
+            self.budgets
+                .lock()
+                .unwrap()
+                .insert(category_id, monthly_limit);
+
+            Ok(CategoryBudget {
+                category_id,
+                monthly_limit,
+            })
+        }
+
+        async fn ping(&self) -> Result<Duration, SqlxError> {
+            Ok(Duration::from_millis(3))
         }
     }
 
@@ -184,7 +425,7 @@ mod tests {
     async fn test_index_categories_handler() {
         // Prepare
         let repo = MockUtilRepository::new();
-        let app = util_routes().with_state(repo);
+        let app = util_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -204,11 +445,119 @@ mod tests {
         assert_eq!(body.categories, categories_response());
     }
 
+    #[tokio::test]
+    async fn test_index_categories_handler_descending() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/categories?sortDir=desc")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexCategoriesResponse>(&body_bytes).unwrap();
+        let names = body
+            .categories
+            .into_iter()
+            .map(|category| category.name)
+            .collect::<Vec<String>>();
+
+        assert_eq!(names, vec!["Transportation", "Rent", "Food"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_categories_handler_malformed_pagination() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/categories?limit=abc&offset=xyz")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexCategoriesResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body.categories, categories_response());
+    }
+
+    #[tokio::test]
+    async fn test_index_categories_handler_by_parent_category() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/categories?parentCategoryId=2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexCategoriesResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.categories,
+            vec![SimpleEntity {
+                id: 3,
+                name: "Rent".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_categories_handler_with_parent() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/categories?includeParent=true&limit=10")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body =
+            serde_json::from_slice::<IndexCategoriesWithParentResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body.categories, categories_with_parent_response());
+    }
+
     #[tokio::test]
     async fn test_index_parent_categories_handler() {
         // Prepare
         let repo = MockUtilRepository::new();
-        let app = util_routes().with_state(repo);
+        let app = util_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -228,11 +577,41 @@ mod tests {
         assert_eq!(body.parent_categories, parent_categories_response());
     }
 
+    #[tokio::test]
+    async fn test_index_parent_categories_handler_with_name_search() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/parent-categories?q=daily")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexParentCategoriesResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.parent_categories,
+            parent_categories_response()
+                .into_iter()
+                .filter(|parent_category| parent_category.id == 1)
+                .collect::<Vec<ParentCategory>>()
+        );
+    }
+
     #[tokio::test]
     async fn test_index_tags_handler() {
         // Prepare
         let repo = MockUtilRepository::new();
-        let app = util_routes().with_state(repo);
+        let app = util_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -251,4 +630,369 @@ mod tests {
 
         assert_eq!(body.tags, tags_response());
     }
+
+    #[tokio::test]
+    async fn test_index_tags_handler_descending() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/tags?sortDir=desc")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexTagsResponse>(&body_bytes).unwrap();
+
+        // Important tags still come first, regardless of the direction
+        let names = body
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["Essential", "Rent", "Groceries"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_tags_handler_with_name_search() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/tags?q=ess")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexTagsResponse>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body.tags,
+            vec![Tag {
+                id: 1,
+                name: "Essential".to_string(),
+                is_important: true,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reparent_category_handler() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/categories/1/parent")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "parentCategoryId": 2 }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_reparent_category_handler_not_found() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri(format!("/categories/{UNKNOWN_ID}/parent"))
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "parentCategoryId": 2 }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_reparent_category_handler_unknown_parent() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/categories/1/parent")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "parentCategoryId": UNKNOWN_ID }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{
+                    "field": "parentCategoryId",
+                    "reason": format!("Parent category {UNKNOWN_ID} not found")
+                }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reparent_category_handler_invalid_parent() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/categories/1/parent")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "parentCategoryId": 0 }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_set_tag_importance_handler() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/tags/importance")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "tagIds": [1, 2, UNKNOWN_ID], "isImportant": true })
+                    .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *repo.importance_update.lock().unwrap(),
+            Some((vec![1, 2, UNKNOWN_ID], true))
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SetTagImportanceResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body, SetTagImportanceResponse { updated: 2 });
+    }
+
+    #[tokio::test]
+    async fn test_set_tag_importance_handler_empty_ids() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/tags/importance")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "tagIds": [], "isImportant": false }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(repo.importance_update.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_tag_importance_handler_invalid_id() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/tags/importance")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "tagIds": [1, 0], "isImportant": true }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn save_category_budget_request(id: i32, monthly_limit: i32) -> Request<Body> {
+        Request::builder()
+            .method("PUT")
+            .uri(format!("/categories/{id}/budget"))
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "monthlyLimit": monthly_limit }).to_string(),
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_save_category_budget_handler() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = save_category_budget_request(1, 500000);
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<CategoryBudget>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            CategoryBudget {
+                category_id: 1,
+                monthly_limit: 500000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_category_budget_handler_replaces_budget() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/categories/1/budget")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        for monthly_limit in [500000, 0] {
+            let response = app
+                .clone()
+                .oneshot(save_category_budget_request(1, monthly_limit))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<CategoryBudget>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            CategoryBudget {
+                category_id: 1,
+                monthly_limit: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_category_budget_handler_negative_limit() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = save_category_budget_request(1, -1);
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_save_category_budget_handler_unknown_category() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = save_category_budget_request(UNKNOWN_ID, 500000);
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_show_category_budget_handler_without_budget() {
+        // Prepare
+        let repo = MockUtilRepository::new();
+        let app = util_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/categories/1/budget")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -1,44 +1,204 @@
 use crate::{
-    common::errors::AppError,
+    common::{env, errors::AppError, extract::ValidatedJson, priority},
+    constants::{DEFAULT_TRANSFER_FEE_CATEGORY_ID, DEFAULT_TRANSFER_FEE_PRIORITY},
     dtos::{
-        Pagination,
-        wallet::{IndexWalletsResponse, SaveWalletTransferRequest},
+        DateRangeQuery,
+        wallet::{
+            IndexWalletTransferQuery, IndexWalletTransfersResponse, IndexWalletsQuery,
+            IndexWalletsResponse, SaveWalletQuery, SaveWalletRequest, SaveWalletTransferRequest,
+            UpdateWalletRequest,
+        },
     },
     entities::wallet::{SaveWalletTransfer, SaveWalletTransferFee},
-    repositories::wallet,
+    middlewares::auth::{self, AuthenticatedUser},
+    repositories::wallet::{self, CreateOutcome, DeleteOutcome, RenameOutcome, TransferOutcome},
+    state::AppState,
 };
 use axum::{
-    Json, Router,
-    extract::{Query, State},
+    Extension, Json, Router,
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use axum_extra::extract::WithRejection;
+use lazy_static::lazy_static;
 use std::sync::Arc;
 
-pub fn wallet_routes() -> Router<Arc<dyn wallet::RepositoryOperation>> {
+lazy_static! {
+    /// The priority level of the fee expenses, read once from the `TRANSFER_FEE_PRIORITY` environment variable.
+    static ref TRANSFER_FEE_PRIORITY: i32 = transfer_fee_priority_or_default(
+        env::parse_or("TRANSFER_FEE_PRIORITY", DEFAULT_TRANSFER_FEE_PRIORITY),
+        priority::max_priority(),
+    );
+}
+
+/// Reads the priority level of the fee expenses, warning when it isn't a valid one.
+/// Meant to be called once at startup, so the priority is checked before serving any request.
+pub fn init() {
+    lazy_static::initialize(&TRANSFER_FEE_PRIORITY);
+}
+
+/// Falls back to the default fee priority when the given one isn't a valid priority level,
+/// i.e. between 0 and the given maximum priority.
+fn transfer_fee_priority_or_default(priority: i32, max_priority: i32) -> i32 {
+    match priority::check_priority(priority, max_priority) {
+        Ok(priority) => priority,
+        Err(message) => {
+            tracing::warn!("Invalid TRANSFER_FEE_PRIORITY: {message}, falling back to the default");
+            DEFAULT_TRANSFER_FEE_PRIORITY
+        }
+    }
+}
+
+/// Builds the fee expense of a wallet transfer with the given priority, or `None` when the transfer has no fee.
+/// Without a fee description, it's derived from the description of the transfer.
+fn transfer_fee(body: &SaveWalletTransferRequest, priority: i32) -> Option<SaveWalletTransferFee> {
+    if body.fee == 0 {
+        return None;
+    }
+
+    let description = body.fee_description.clone().or_else(|| {
+        body.description
+            .as_ref()
+            .map(|description| format!("Wallet transfer fee: {description}"))
+    });
+
+    Some(SaveWalletTransferFee {
+        priority,
+        wallet_id: body.source_wallet_id,
+        category_id: DEFAULT_TRANSFER_FEE_CATEGORY_ID,
+        amount: body.fee,
+        date: body.date,
+        description,
+    })
+}
+
+pub fn wallet_routes() -> Router<AppState> {
     Router::new().nest(
         "/wallets",
         Router::new()
-            .route("/", get(index))
-            .route("/transfer", post(transfer)),
+            .route("/", get(index).post(save))
+            .route("/{id}", get(show).put(update).delete(destroy))
+            .route("/transfer", post(transfer))
+            .route("/transfers", get(index_transfers))
+            .route("/transfers/summary", get(summarize_transfers))
+            .route("/transfers/{id}", delete(destroy_transfer)),
     )
 }
 
+/// Handles the deletion of a wallet by ID.
+/// Wallets still referenced by expenses, incomes or transfers are kept, responding with a conflict.
+async fn destroy(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let outcome = wallet_repository
+        .delete(id as i32, auth::user_id(&user))
+        .await?;
+
+    match outcome {
+        DeleteOutcome::Deleted => Ok(StatusCode::NO_CONTENT),
+        DeleteOutcome::InUse => Err(AppError::Message(
+            StatusCode::CONFLICT,
+            format!("Wallet {id} is in use by expenses, incomes or transfers"),
+        )),
+    }
+}
+
+/// Handles the deletion of a wallet transfer by ID, along with its fee expense if any.
+async fn destroy_transfer(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    wallet_repository
+        .delete_wallet_transfer(id as i32, auth::user_id(&user))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn index(
     State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
-    Query(query): Query<Pagination>,
+    Query(query): Query<IndexWalletsQuery>,
+    user: Option<Extension<AuthenticatedUser>>,
 ) -> Result<impl IntoResponse, AppError> {
     let wallets = wallet_repository
-        .find_many(query.offset(), query.limit())
+        .find_many(&query, auth::user_id(&user))
         .await?;
 
     Ok((StatusCode::OK, Json(IndexWalletsResponse { wallets })))
 }
 
+/// Handles listing the wallet transfers within an optional date range.
+async fn index_transfers(
+    Query(query): Query<IndexWalletTransferQuery>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let transfers = wallet_repository
+        .find_many_transfers(&query, auth::user_id(&user))
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(IndexWalletTransfersResponse { transfers }),
+    ))
+}
+
+/// Handles summing the wallet transfers within an optional date range for each pair of wallets.
+async fn summarize_transfers(
+    Query(query): Query<DateRangeQuery>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let summary = wallet_repository
+        .summarize_transfers(query.start_date, query.end_date, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(summary)))
+}
+
+/// Handles the creation of a wallet.
+/// With `getOrCreate`, the existing wallet of the same name is returned instead of a conflict.
+async fn save(
+    Query(query): Query<SaveWalletQuery>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedJson(body): ValidatedJson<SaveWalletRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let outcome = wallet_repository
+        .insert(&body.name, query.is_get_or_create(), auth::user_id(&user))
+        .await?;
+
+    match outcome {
+        CreateOutcome::Created(wallet) => Ok((StatusCode::CREATED, Json(wallet))),
+        CreateOutcome::Existing(wallet) => Ok((StatusCode::OK, Json(wallet))),
+        CreateOutcome::Duplicate => Err(AppError::Message(
+            StatusCode::CONFLICT,
+            format!("A wallet named {} already exists", body.name),
+        )),
+    }
+}
+
+/// Handles the retrieval of a specific wallet by ID.
+async fn show(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+) -> Result<impl IntoResponse, AppError> {
+    let wallet = wallet_repository
+        .find_one(id as i32, auth::user_id(&user))
+        .await?;
+
+    Ok((StatusCode::OK, Json(wallet)))
+}
+
 async fn transfer(
     State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
     WithRejection(Json(body), _): WithRejection<Json<SaveWalletTransferRequest>, AppError>,
 ) -> Result<impl IntoResponse, AppError> {
     if body.source_wallet_id == body.target_wallet_id {
@@ -53,35 +213,63 @@ async fn transfer(
         description: body.description.clone(),
     };
 
-    let save_transfer_fee = match body.fee {
-        0 => None,
-        _ => {
-            let description = body
-                .description
-                .map(|description| format!("Wallet transfer fee: {description}"));
-
-            Some(SaveWalletTransferFee {
-                priority: 2, // Default to secondary priority
-                wallet_id: body.source_wallet_id,
-                category_id: 25, // Default to a specific category ID. Change as needed.
-                amount: body.fee,
-                date: body.date,
-                description,
-            })
-        }
-    };
+    let save_transfer_fee = transfer_fee(&body, *TRANSFER_FEE_PRIORITY);
 
-    wallet_repository
-        .insert_wallet_transfer_with_fee(&save_transfer, save_transfer_fee.as_ref())
+    let outcome = wallet_repository
+        .insert_wallet_transfer_with_fee(
+            &save_transfer,
+            save_transfer_fee.as_ref(),
+            body.enforce_balance,
+            body.reject_duplicates,
+            auth::user_id(&user),
+        )
+        .await?;
+
+    match outcome {
+        TransferOutcome::Saved => Ok(StatusCode::CREATED),
+        TransferOutcome::InsufficientBalance => Err(AppError::Message(
+            StatusCode::BAD_REQUEST,
+            "Insufficient balance in the source wallet".to_string(),
+        )),
+        TransferOutcome::Duplicate => Err(AppError::Message(
+            StatusCode::CONFLICT,
+            format!("An identical transfer already exists on {}", body.date),
+        )),
+    }
+}
+
+/// Handles renaming a wallet.
+async fn update(
+    WithRejection(Path(id), _): WithRejection<Path<u32>, AppError>,
+    State(wallet_repository): State<Arc<dyn wallet::RepositoryOperation>>,
+    user: Option<Extension<AuthenticatedUser>>,
+    ValidatedJson(body): ValidatedJson<UpdateWalletRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let outcome = wallet_repository
+        .update(id as i32, &body.name, auth::user_id(&user))
         .await?;
 
-    Ok(StatusCode::CREATED)
+    match outcome {
+        RenameOutcome::Renamed => Ok(StatusCode::NO_CONTENT),
+        RenameOutcome::Duplicate => Err(AppError::Message(
+            StatusCode::CONFLICT,
+            format!("A wallet named {} already exists", body.name),
+        )),
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use crate::{dtos::query_result::SimpleEntity, entities::wallet::SaveWalletTransferFee};
+    use crate::{
+        dtos::{
+            SortDirection,
+            query_result::{
+                IndexWalletTransferElement, SimpleEntity, WalletTransferSummaryElement,
+            },
+        },
+        entities::wallet::SaveWalletTransferFee,
+    };
 
     use async_trait::async_trait;
     use axum::{
@@ -90,14 +278,44 @@ mod tests {
     };
     use serde_json;
     use sqlx::Error as SqlxError;
-    use std::sync::Arc;
+    use std::{
+        cmp::Reverse,
+        sync::{Arc, Mutex},
+    };
+    use time::{Date, Month};
     use tower::ServiceExt;
 
-    pub struct MockWalletRepository;
+    /// The balance of every wallet in the mock repository.
+    const WALLET_BALANCE: i32 = 5000;
+
+    pub struct MockWalletRepository {
+        /// The description of the last saved fee expense, if any transfer with a fee was saved.
+        fee_description: Mutex<Option<Option<String>>>,
+        /// The priority of the last saved fee expense, if any transfer with a fee was saved.
+        fee_priority: Mutex<Option<i32>>,
+        /// The transfers saved so far.
+        saved_transfers: Mutex<Vec<SaveWalletTransfer>>,
+        /// The date range of the last transfer summary, if any was requested.
+        summary_range: Mutex<Option<(Option<Date>, Option<Date>)>>,
+        /// The date range of the last transfer listing, if any was requested.
+        transfers_range: Mutex<Option<(Option<Date>, Option<Date>)>>,
+        /// The ID and new name of the last renamed wallet, if any.
+        renamed_wallet: Mutex<Option<(i32, String)>>,
+        /// The name of the last created wallet, if any.
+        created_wallet: Mutex<Option<String>>,
+    }
 
     impl MockWalletRepository {
         pub fn new() -> Arc<Self> {
-            Arc::new(Self)
+            Arc::new(Self {
+                fee_description: Mutex::new(None),
+                fee_priority: Mutex::new(None),
+                saved_transfers: Mutex::new(Vec::new()),
+                summary_range: Mutex::new(None),
+                transfers_range: Mutex::new(None),
+                renamed_wallet: Mutex::new(None),
+                created_wallet: Mutex::new(None),
+            })
         }
     }
 
@@ -116,22 +334,196 @@ mod tests {
         }
     }
 
+    fn transfer_element(id: i32, date: &str) -> IndexWalletTransferElement {
+        let wallets = index_wallets_response().wallets;
+        let mut wallets = wallets.into_iter();
+
+        IndexWalletTransferElement {
+            id,
+            amount: 1000,
+            date: date.to_string(),
+            description: None,
+            source_wallet: sqlx::types::Json(wallets.next().unwrap()),
+            target_wallet: sqlx::types::Json(wallets.next().unwrap()),
+        }
+    }
+
+    fn index_transfers_response() -> IndexWalletTransfersResponse {
+        IndexWalletTransfersResponse {
+            transfers: vec![
+                transfer_element(1, "2025-04-30"),
+                transfer_element(2, "2025-05-01"),
+                transfer_element(3, "2025-05-31"),
+                transfer_element(4, "2025-06-01"),
+            ],
+        }
+    }
+
+    /// Builds the app state holding the given mock repository.
+    fn state(repo: Arc<MockWalletRepository>) -> AppState {
+        AppState {
+            wallet_repository: repo,
+            ..AppState::mocked()
+        }
+    }
+
     #[async_trait]
     impl wallet::RepositoryOperation for MockWalletRepository {
         async fn find_many(
             &self,
-            _offset: i64,
-            _limit: i64,
+            query: &IndexWalletsQuery,
+            _user_id: Option<&str>,
         ) -> Result<Vec<SimpleEntity>, SqlxError> {
-            Ok(index_wallets_response().wallets)
+            let mut wallets = index_wallets_response().wallets;
+
+            if SortDirection::is_descending(query.sort_dir) {
+                wallets.sort_by_key(|wallet| Reverse(wallet.name.to_lowercase()));
+            }
+
+            Ok(wallets)
+        }
+
+        async fn find_one(
+            &self,
+            id: i32,
+            _user_id: Option<&str>,
+        ) -> Result<SimpleEntity, SqlxError> {
+            index_wallets_response()
+                .wallets
+                .into_iter()
+                .find(|wallet| wallet.id == i64::from(id))
+                .ok_or(SqlxError::RowNotFound)
+        }
+
+        async fn find_many_transfers(
+            &self,
+            query: &IndexWalletTransferQuery,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<IndexWalletTransferElement>, SqlxError> {
+            *self.transfers_range.lock().unwrap() = Some((query.start_date, query.end_date));
+
+            Ok(index_transfers_response().transfers)
+        }
+
+        async fn summarize_transfers(
+            &self,
+            start_date: Option<Date>,
+            end_date: Option<Date>,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<WalletTransferSummaryElement>, SqlxError> {
+            *self.summary_range.lock().unwrap() = Some((start_date, end_date));
+
+            let wallets = index_wallets_response().wallets;
+            let mut wallets = wallets.into_iter();
+
+            Ok(vec![WalletTransferSummaryElement {
+                source: sqlx::types::Json(wallets.next().unwrap()),
+                target: sqlx::types::Json(wallets.next().unwrap()),
+                total_amount: 3000,
+                total_fee: 20,
+                count: 3,
+            }])
         }
 
         async fn insert_wallet_transfer_with_fee(
             &self,
-            _money_transfer_record: &SaveWalletTransfer,
-            _fee_record: Option<&SaveWalletTransferFee>,
+            money_transfer_record: &SaveWalletTransfer,
+            fee_record: Option<&SaveWalletTransferFee>,
+            enforce_balance: bool,
+            reject_duplicates: bool,
+            _user_id: Option<&str>,
+        ) -> Result<TransferOutcome, SqlxError> {
+            let mut saved_transfers = self.saved_transfers.lock().unwrap();
+            if reject_duplicates && saved_transfers.contains(money_transfer_record) {
+                return Ok(TransferOutcome::Duplicate);
+            }
+
+            if let Some(fee_record) = fee_record {
+                *self.fee_description.lock().unwrap() = Some(fee_record.description.clone());
+                *self.fee_priority.lock().unwrap() = Some(fee_record.priority);
+            }
+
+            let fee = fee_record.map_or(0, |fee_record| fee_record.amount);
+            if enforce_balance && money_transfer_record.amount + fee > WALLET_BALANCE {
+                return Ok(TransferOutcome::InsufficientBalance);
+            }
+
+            saved_transfers.push(money_transfer_record.clone());
+            Ok(TransferOutcome::Saved)
+        }
+
+        async fn delete(
+            &self,
+            id: i32,
+            _user_id: Option<&str>,
+        ) -> Result<DeleteOutcome, SqlxError> {
+            match id {
+                1 => Ok(DeleteOutcome::InUse),
+                2 => Ok(DeleteOutcome::Deleted),
+                _ => Err(SqlxError::RowNotFound),
+            }
+        }
+
+        async fn delete_wallet_transfer(
+            &self,
+            id: i32,
+            _user_id: Option<&str>,
         ) -> Result<(), SqlxError> {
-            Ok(())
+            match id {
+                1 => Ok(()),
+                _ => Err(SqlxError::RowNotFound),
+            }
+        }
+
+        async fn insert(
+            &self,
+            name: &str,
+            get_or_create: bool,
+            _user_id: Option<&str>,
+        ) -> Result<CreateOutcome, SqlxError> {
+            let wallets = index_wallets_response().wallets;
+
+            let wallet_count = wallets.len() as i64;
+
+            if let Some(wallet) = wallets
+                .into_iter()
+                .find(|wallet| wallet.name.to_lowercase() == name.to_lowercase())
+            {
+                return Ok(match get_or_create {
+                    true => CreateOutcome::Existing(wallet),
+                    false => CreateOutcome::Duplicate,
+                });
+            }
+
+            *self.created_wallet.lock().unwrap() = Some(name.to_string());
+
+            Ok(CreateOutcome::Created(SimpleEntity {
+                id: wallet_count + 1,
+                name: name.to_string(),
+            }))
+        }
+
+        async fn update(
+            &self,
+            id: i32,
+            name: &str,
+            _user_id: Option<&str>,
+        ) -> Result<RenameOutcome, SqlxError> {
+            let wallets = index_wallets_response().wallets;
+
+            if !wallets.iter().any(|wallet| wallet.id == i64::from(id)) {
+                return Err(SqlxError::RowNotFound);
+            }
+
+            if wallets.iter().any(|wallet| {
+                wallet.id != i64::from(id) && wallet.name.to_lowercase() == name.to_lowercase()
+            }) {
+                return Ok(RenameOutcome::Duplicate);
+            }
+
+            *self.renamed_wallet.lock().unwrap() = Some((id, name.to_string()));
+
+            Ok(RenameOutcome::Renamed)
         }
     }
 
@@ -139,7 +531,7 @@ mod tests {
     async fn test_index_handler() {
         // Prepare
         let repo = MockWalletRepository::new();
-        let app = wallet_routes().with_state(repo);
+        let app = wallet_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("GET")
@@ -159,11 +551,174 @@ mod tests {
         assert_eq!(body, index_wallets_response());
     }
 
+    #[tokio::test]
+    async fn test_index_handler_descending() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/wallets?sortDir=desc")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexWalletsResponse>(&body_bytes).unwrap();
+        let names = body
+            .wallets
+            .into_iter()
+            .map(|wallet| wallet.name)
+            .collect::<Vec<String>>();
+
+        assert_eq!(names, vec!["Cash", "Bank Account"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_transfers_handler_with_date_range() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/wallets/transfers?startDate=2025-05-01&endDate=2025-05-31&limit=10")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *repo.transfers_range.lock().unwrap(),
+            Some((
+                Some(Date::from_calendar_date(2025, Month::May, 1).unwrap()),
+                Some(Date::from_calendar_date(2025, Month::May, 31).unwrap())
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_transfers_handler_with_invalid_dates() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/wallets/transfers?startDate=invalid&endDate=")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*repo.transfers_range.lock().unwrap(), Some((None, None)));
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<IndexWalletTransfersResponse>(&body_bytes).unwrap();
+
+        assert_eq!(body, index_transfers_response());
+    }
+
+    #[tokio::test]
+    async fn test_summarize_transfers_handler() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/wallets/transfers/summary?startDate=2025-05-01&endDate=invalid")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *repo.summary_range.lock().unwrap(),
+            Some((
+                Some(Date::from_calendar_date(2025, Month::May, 1).unwrap()),
+                None
+            ))
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "source": { "id": 1, "name": "Cash" },
+                "target": { "id": 2, "name": "Bank Account" },
+                "totalAmount": 3000,
+                "totalFee": 20,
+                "count": 3
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_handler() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/wallets/2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SimpleEntity>(&body_bytes).unwrap();
+
+        assert_eq!(body, index_wallets_response().wallets[1]);
+    }
+
+    #[tokio::test]
+    async fn test_show_handler_not_found() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/wallets/3")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_transfer_handler_without_fee() {
         // Prepare
         let repo = MockWalletRepository::new();
-        let app = wallet_routes().with_state(repo);
+        let app = wallet_routes().with_state(state(repo));
 
         let request = Request::builder()
             .method("POST")
@@ -189,11 +744,44 @@ mod tests {
         assert_eq!(response.status(), StatusCode::CREATED);
     }
 
+    #[test]
+    fn test_transfer_fee_priority_or_default() {
+        assert_eq!(transfer_fee_priority_or_default(0, 2), 0);
+        assert_eq!(transfer_fee_priority_or_default(1, 2), 1);
+        assert_eq!(transfer_fee_priority_or_default(3, 3), 3);
+        assert_eq!(
+            transfer_fee_priority_or_default(-1, 2),
+            DEFAULT_TRANSFER_FEE_PRIORITY
+        );
+        assert_eq!(
+            transfer_fee_priority_or_default(3, 2),
+            DEFAULT_TRANSFER_FEE_PRIORITY
+        );
+    }
+
+    #[test]
+    fn test_transfer_fee_with_configured_priority() {
+        let json_str = r#"{
+            "sourceWalletId": 1,
+            "targetWalletId": 2,
+            "amount": 1000,
+            "fee": 10,
+            "date": "2025-05-06"
+        }"#;
+        let body = serde_json::from_str::<SaveWalletTransferRequest>(json_str).unwrap();
+
+        let fee = transfer_fee(&body, 0).unwrap();
+
+        assert_eq!(fee.priority, 0);
+        assert_eq!(fee.amount, 10);
+        assert_eq!(fee.wallet_id, 1);
+    }
+
     #[tokio::test]
     async fn test_transfer_handler_with_fee() {
         // Prepare
         let repo = MockWalletRepository::new();
-        let app = wallet_routes().with_state(repo);
+        let app = wallet_routes().with_state(state(repo.clone()));
 
         let request = Request::builder()
             .method("POST")
@@ -217,13 +805,17 @@ mod tests {
 
         // Assert
         assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            *repo.fee_priority.lock().unwrap(),
+            Some(*TRANSFER_FEE_PRIORITY)
+        );
     }
 
     #[tokio::test]
-    async fn test_transfer_handler_without_description() {
+    async fn test_transfer_handler_with_derived_fee_description() {
         // Prepare
         let repo = MockWalletRepository::new();
-        let app = wallet_routes().with_state(repo);
+        let app = wallet_routes().with_state(state(repo.clone()));
 
         let request = Request::builder()
             .method("POST")
@@ -234,8 +826,9 @@ mod tests {
                     "sourceWalletId": 1,
                     "targetWalletId": 2,
                     "amount": 1000,
-                    "fee": 5,
-                    "date": "2025-05-06"
+                    "fee": 10,
+                    "date": "2025-05-06",
+                    "description": "Savings"
                 })
                 .to_string(),
             ))
@@ -246,13 +839,17 @@ mod tests {
 
         // Assert
         assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            *repo.fee_description.lock().unwrap(),
+            Some(Some("Wallet transfer fee: Savings".to_string()))
+        );
     }
 
     #[tokio::test]
-    async fn test_transfer_handler_with_same_wallet() {
+    async fn test_transfer_handler_with_custom_fee_description() {
         // Prepare
         let repo = MockWalletRepository::new();
-        let app = wallet_routes().with_state(repo);
+        let app = wallet_routes().with_state(state(repo.clone()));
 
         let request = Request::builder()
             .method("POST")
@@ -261,10 +858,12 @@ mod tests {
             .body(Body::from(
                 serde_json::json!({
                     "sourceWalletId": 1,
-                    "targetWalletId": 1,
+                    "targetWalletId": 2,
                     "amount": 1000,
-                    "fee": 5,
-                    "date": "2025-05-06"
+                    "fee": 10,
+                    "date": "2025-05-06",
+                    "description": "Savings",
+                    "feeDescription": "Bank admin fee"
                 })
                 .to_string(),
             ))
@@ -274,6 +873,520 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
 
         // Assert
-        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            *repo.fee_description.lock().unwrap(),
+            Some(Some("Bank admin fee".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_without_description() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets/transfer")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "sourceWalletId": 1,
+                    "targetWalletId": 2,
+                    "amount": 1000,
+                    "fee": 5,
+                    "date": "2025-05-06"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_with_same_wallet() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets/transfer")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "sourceWalletId": 1,
+                    "targetWalletId": 1,
+                    "amount": 1000,
+                    "fee": 5,
+                    "date": "2025-05-06"
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_with_enforced_balance() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets/transfer")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "sourceWalletId": 1,
+                    "targetWalletId": 2,
+                    "amount": 4990,
+                    "fee": 10,
+                    "date": "2025-05-06",
+                    "enforceBalance": true
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_with_insufficient_balance() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets/transfer")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "sourceWalletId": 1,
+                    "targetWalletId": 2,
+                    "amount": 4991,
+                    "fee": 10,
+                    "date": "2025-05-06",
+                    "enforceBalance": true
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({ "message": "Insufficient balance in the source wallet" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_with_duplicate() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let transfer_request = || {
+            Request::builder()
+                .method("POST")
+                .uri("/wallets/transfer")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "sourceWalletId": 1,
+                        "targetWalletId": 2,
+                        "amount": 1000,
+                        "fee": 0,
+                        "date": "2025-05-06",
+                        "description": "Savings",
+                        "rejectDuplicates": true
+                    })
+                    .to_string(),
+                ))
+                .unwrap()
+        };
+
+        // Execute
+        let first_response = app.clone().oneshot(transfer_request()).await.unwrap();
+        let second_response = app.oneshot(transfer_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(first_response.status(), StatusCode::CREATED);
+        assert_eq!(second_response.status(), StatusCode::CONFLICT);
+
+        let body_bytes = to_bytes(second_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({ "message": "An identical transfer already exists on 2025-05-06" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transfer_handler_allows_duplicate_by_default() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let transfer_request = || {
+            Request::builder()
+                .method("POST")
+                .uri("/wallets/transfer")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "sourceWalletId": 1,
+                        "targetWalletId": 2,
+                        "amount": 1000,
+                        "fee": 0,
+                        "date": "2025-05-06"
+                    })
+                    .to_string(),
+                ))
+                .unwrap()
+        };
+
+        // Execute
+        let first_response = app.clone().oneshot(transfer_request()).await.unwrap();
+        let second_response = app.oneshot(transfer_request()).await.unwrap();
+
+        // Assert
+        assert_eq!(first_response.status(), StatusCode::CREATED);
+        assert_eq!(second_response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_destroy_handler() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/wallets/2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_destroy_handler_in_use() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/wallets/1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({ "message": "Wallet 1 is in use by expenses, incomes or transfers" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_destroy_handler_not_found() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/wallets/3")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_destroy_transfer_handler() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/wallets/transfers/1")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_destroy_transfer_handler_not_found() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo));
+
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/wallets/transfers/2")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_save_handler() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets?getOrCreate=true")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": " Savings " }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            *repo.created_wallet.lock().unwrap(),
+            Some("Savings".to_string())
+        );
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SimpleEntity>(&body_bytes).unwrap();
+
+        assert_eq!(
+            body,
+            SimpleEntity {
+                id: 3,
+                name: "Savings".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_handler_get_existing() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets?getOrCreate=true")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": "cash" }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*repo.created_wallet.lock().unwrap(), None);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice::<SimpleEntity>(&body_bytes).unwrap();
+
+        assert_eq!(body, index_wallets_response().wallets[0]);
+    }
+
+    #[tokio::test]
+    async fn test_save_handler_duplicate_name() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/wallets")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": "Cash" }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(*repo.created_wallet.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/wallets/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": " Pocket Money " }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            *repo.renamed_wallet.lock().unwrap(),
+            Some((1, "Pocket Money".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_same_name() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/wallets/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": "cash" }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_not_found() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/wallets/3")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": "Savings" }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(*repo.renamed_wallet.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_duplicate_name() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/wallets/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::json!({ "name": "bank account" }).to_string(),
+            ))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(*repo.renamed_wallet.lock().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_handler_blank_name() {
+        // Prepare
+        let repo = MockWalletRepository::new();
+        let app = wallet_routes().with_state(state(repo.clone()));
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/wallets/1")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::json!({ "name": "   " }).to_string()))
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(*repo.renamed_wallet.lock().unwrap(), None);
     }
 }
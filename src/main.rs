@@ -6,42 +6,79 @@ mod handlers;
 mod middlewares;
 mod repositories;
 mod services;
+mod state;
 
-use axum::{Router, http::StatusCode, middleware, routing::get};
+use axum::{Router, middleware};
 use handlers::{
-    expense::expense_routes, income::income_routes, summary::summary_routes, util::util_routes,
-    wallet::wallet_routes,
+    dashboard::dashboard_routes, expense::expense_routes, health::health_routes,
+    income::income_routes, summary::summary_routes, util::util_routes, wallet::wallet_routes,
+};
+use middlewares::{
+    auth::{authenticate_request, authorize_request},
+    compression::compression_layer,
+    request_id::propagate_request_id,
+    timeout::timeout_layer,
+    trace::http_trace_layer,
 };
-use middlewares::{auth::authenticate_request, trace::http_trace_layer};
 use repositories::{expense, income, summary, util};
-use std::{env, sync::Arc};
-use tower_http::compression::CompressionLayer;
+use state::AppState;
+use std::{env, sync::Arc, time::Duration};
 use tracing::info;
 
 #[tokio::main]
 async fn main() {
     middlewares::trace::init();
+    middlewares::auth::init();
+    handlers::wallet::init();
     let pg_pool = Arc::new(common::database::init().await.unwrap());
 
     let expense_repository = Arc::new(expense::Repository::new(Arc::clone(&pg_pool)));
     let income_repository = Arc::new(income::Repository::new(Arc::clone(&pg_pool)));
-    let summary_repository = Arc::new(summary::SummaryRepository::new(Arc::clone(&pg_pool)));
-    let util_repository = Arc::new(util::Repository::new(Arc::clone(&pg_pool)));
+    let summary_repository = Arc::new(summary::CachedSummaryRepository::new(
+        Arc::new(summary::SummaryRepository::new(
+            Arc::clone(&pg_pool),
+            common::env::parse_list_or(
+                "TRANSFER_FEE_CATEGORY_IDS",
+                vec![constants::DEFAULT_TRANSFER_FEE_CATEGORY_ID],
+            ),
+            common::priority::max_priority(),
+        )),
+        Duration::from_secs(common::env::parse_or("SUMMARY_CACHE_TTL_SECS", 60)),
+    ));
+    let util_repository = Arc::new(util::Repository::new(
+        Arc::clone(&pg_pool),
+        util::ListingSort::from_env(),
+    ));
     let wallet_repository = Arc::new(repositories::wallet::Repository::new(Arc::clone(&pg_pool)));
 
+    let app_state = AppState {
+        expense_repository,
+        income_repository,
+        summary_repository,
+        util_repository,
+        wallet_repository,
+    };
+
     let auth_required_router = Router::new()
-        .merge(expense_routes().with_state(expense_repository))
-        .merge(income_routes().with_state(income_repository))
-        .merge(summary_routes().with_state(summary_repository))
-        .merge(util_routes().with_state(util_repository))
-        .merge(wallet_routes().with_state(wallet_repository))
+        .merge(dashboard_routes())
+        .merge(expense_routes())
+        .merge(income_routes())
+        .merge(summary_routes())
+        .merge(util_routes())
+        .merge(wallet_routes())
+        .route_layer(middleware::from_fn(authorize_request))
         .route_layer(middleware::from_fn(authenticate_request));
 
-    let app = Router::new()
-        .route("/health", get(|| async { StatusCode::OK }))
+    let routes = Router::new()
+        .merge(health_routes())
         .merge(auth_required_router)
-        .layer(CompressionLayer::new())
-        .layer(http_trace_layer());
+        .with_state(app_state);
+
+    let app = common::routing::with_base_path(routes, env::var("BASE_PATH").ok().as_deref())
+        .layer(timeout_layer())
+        .layer(compression_layer())
+        .layer(http_trace_layer())
+        .layer(middleware::from_fn(propagate_request_id));
 
     let port = env::var("PORT")
         .inspect_err(|_| {
@@ -4,8 +4,9 @@ use std::{
 };
 
 use axum::{
+    Extension,
     extract::Request,
-    http::{StatusCode, header},
+    http::{Method, StatusCode, header},
     middleware::Next,
     response::IntoResponse,
 };
@@ -13,21 +14,138 @@ use jsonwebtoken::{DecodingKey, Validation, decode};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 
+use crate::common;
+
 lazy_static! {
     static ref JWT_SECRET: String = env::var("JWT_SECRET")
         .inspect_err(|_| {
             tracing::error!("JWT_SECRET environment variable is not set");
         })
         .unwrap();
+    /// Whether every request is let through without a token, read once from the `AUTH_DISABLED` environment variable.
+    static ref AUTH_DISABLED: bool = common::env::parse_or("AUTH_DISABLED", false);
+}
+
+/// Reads whether authentication is disabled, warning loudly when it is.
+/// Meant to be called once at startup, so the flag is read before serving any request.
+pub fn init() {
+    if *AUTH_DISABLED {
+        tracing::warn!(
+            "AUTH_DISABLED is set: every request is let through without a token. Never enable it outside local development!"
+        );
+    }
 }
 
 /// The payload of the JWT token.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct Claim {
     /// The expiration time of the token in unix timestamp.
     exp: usize,
     /// The unix timestamp before which the token is not valid.
     nbf: usize,
+    /// The subject of the token, identifying the authenticated user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sub: Option<String>,
+    /// The space-separated scopes granted to the token.
+    /// Tokens without a scope are granted every scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+}
+
+impl Claim {
+    /// The claim of requests let through while authentication is disabled, granted every scope.
+    fn unrestricted() -> Self {
+        Self {
+            exp: usize::MAX,
+            nbf: 0,
+            sub: None,
+            scope: None,
+        }
+    }
+
+    /// Checks whether the token is granted the given scope.
+    fn has_scope(&self, required_scope: &str) -> bool {
+        match &self.scope {
+            Some(scope) => scope
+                .split_whitespace()
+                .any(|scope| scope == required_scope),
+            None => true,
+        }
+    }
+}
+
+/// The user the request is authenticated as, taken from the `sub` claim of the token.
+/// Only present in the request extensions when the token carries a subject.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthenticatedUser(pub String);
+
+/// Gets the ID of the user the request is authenticated as, if any.
+pub fn user_id(user: &Option<Extension<AuthenticatedUser>>) -> Option<&str> {
+    user.as_ref()
+        .map(|Extension(AuthenticatedUser(sub))| sub.as_str())
+}
+
+/// A scope required to call the routes under a path prefix with any of the listed methods.
+struct ScopeRule {
+    path_prefix: &'static str,
+    methods: &'static [Method],
+    scope: &'static str,
+}
+
+/// The methods that modify records.
+const WRITE_METHODS: &[Method] = &[Method::POST, Method::PUT, Method::PATCH, Method::DELETE];
+
+/// The scopes required per route group.
+/// Requests that don't match any rule only need to be authenticated.
+const SCOPE_RULES: &[ScopeRule] = &[
+    ScopeRule {
+        path_prefix: "/categories",
+        methods: WRITE_METHODS,
+        scope: "write",
+    },
+    ScopeRule {
+        path_prefix: "/expenses",
+        methods: WRITE_METHODS,
+        scope: "write",
+    },
+    ScopeRule {
+        path_prefix: "/incomes",
+        methods: WRITE_METHODS,
+        scope: "write",
+    },
+    ScopeRule {
+        path_prefix: "/tags",
+        methods: WRITE_METHODS,
+        scope: "write",
+    },
+    ScopeRule {
+        path_prefix: "/wallets",
+        methods: WRITE_METHODS,
+        scope: "write",
+    },
+];
+
+/// Finds the scopes required to call the given method on the given path.
+fn required_scopes(method: &Method, path: &str) -> impl Iterator<Item = &'static str> {
+    SCOPE_RULES
+        .iter()
+        .filter(move |rule| rule.methods.contains(method) && path.starts_with(rule.path_prefix))
+        .map(|rule| rule.scope)
+}
+
+/// Extracts the token from the value of an `Authorization` header using the bearer scheme.
+/// The scheme is matched case-insensitively and any whitespace around the token is tolerated.
+/// Returns `None` for any other scheme, or when the token is missing or contains whitespace.
+fn bearer_token(header_value: &str) -> Option<&str> {
+    let mut parts = header_value.split_whitespace();
+    let scheme = parts.next()?;
+    let token = parts.next()?;
+
+    if !scheme.eq_ignore_ascii_case("bearer") || parts.next().is_some() {
+        return None;
+    }
+
+    Some(token)
 }
 
 /// Helper function to get the current unix timestamp.
@@ -47,10 +165,26 @@ fn get_current_unix_timestamp() -> Result<usize, StatusCode> {
 }
 
 /// Middleware to authenticate requests using JWT tokens.
+/// Every request is let through when authentication is disabled with `AUTH_DISABLED`.
 pub async fn authenticate_request(
     request: Request,
     next: Next,
 ) -> Result<impl IntoResponse, StatusCode> {
+    authenticate(request, next, *AUTH_DISABLED).await
+}
+
+/// Authenticates the request using its JWT token, unless authentication is disabled.
+/// Requests let through while disabled are granted every scope, without an authenticated user.
+async fn authenticate(
+    mut request: Request,
+    next: Next,
+    auth_disabled: bool,
+) -> Result<impl IntoResponse, StatusCode> {
+    if auth_disabled {
+        request.extensions_mut().insert(Claim::unrestricted());
+        return Ok(next.run(request).await);
+    }
+
     let auth_header = request
         .headers()
         .get(header::AUTHORIZATION)
@@ -58,9 +192,8 @@ pub async fn authenticate_request(
 
     let raw_token = auth_header
         .to_str()
-        .map_err(|_| StatusCode::UNAUTHORIZED)?
-        .split("Bearer ")
-        .nth(1)
+        .ok()
+        .and_then(bearer_token)
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
     let mut validation = Validation::default();
@@ -77,6 +210,34 @@ pub async fn authenticate_request(
         return Err(StatusCode::UNAUTHORIZED);
     }
 
+    if let Some(sub) = &token.claims.sub {
+        request
+            .extensions_mut()
+            .insert(AuthenticatedUser(sub.clone()));
+    }
+    request.extensions_mut().insert(token.claims);
+
+    Ok(next.run(request).await)
+}
+
+/// Middleware to authorize requests against the scopes required by the route.
+/// Must run after `authenticate_request`, which puts the claim into the request extensions.
+pub async fn authorize_request(
+    request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, StatusCode> {
+    let claim = request
+        .extensions()
+        .get::<Claim>()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let is_authorized = required_scopes(request.method(), request.uri().path())
+        .all(|required_scope| claim.has_scope(required_scope));
+
+    if !is_authorized {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     Ok(next.run(request).await)
 }
 
@@ -84,11 +245,11 @@ pub async fn authenticate_request(
 mod tests {
     use super::*;
     use axum::{
-        Router,
+        Extension, Router,
         body::Body,
         http::{Request, StatusCode, header},
         middleware::from_fn,
-        routing::get,
+        routing::{get, post},
     };
     use jsonwebtoken::{EncodingKey, Header, encode};
     use serial_test::serial;
@@ -106,6 +267,8 @@ mod tests {
         Claim {
             exp: (now as i64 + exp_offset_secs) as usize,
             nbf: (now as i64 + nbf_offset_secs) as usize,
+            sub: None,
+            scope: None,
         }
     }
 
@@ -128,6 +291,37 @@ mod tests {
             .route_layer(from_fn(authenticate_request))
     }
 
+    // Setup the router with both the authentication and authorization middlewares
+    fn setup_scoped_test_router() -> Router {
+        unsafe { std::env::set_var("JWT_SECRET", TEST_JWT_SECRET) };
+
+        Router::new()
+            .route("/expenses", get(|| async { StatusCode::OK }))
+            .route("/expenses", post(|| async { StatusCode::CREATED }))
+            .route("/summaries/generate/raw", post(|| async { StatusCode::OK }))
+            .route_layer(from_fn(authorize_request))
+            .route_layer(from_fn(authenticate_request))
+    }
+
+    /// Helper to send a request with a token granted the given scope to the scoped test router.
+    async fn send_scoped_request(method: &str, uri: &str, scope: Option<&str>) -> StatusCode {
+        let app = setup_scoped_test_router();
+        let claim = Claim {
+            scope: scope.map(str::to_string),
+            ..create_test_claim(3600, -60)
+        };
+        let token = generate_test_token(&claim, TEST_JWT_SECRET);
+
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+
+        app.oneshot(request).await.unwrap().status()
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_authenticate_request_valid_token() {
@@ -164,6 +358,33 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[tokio::test]
+    async fn test_authenticate_request_disabled_without_auth_header() {
+        // Prepare
+        let app = Router::new()
+            .route(
+                "/expenses",
+                post(|user: Option<Extension<AuthenticatedUser>>| async move {
+                    assert_eq!(user_id(&user), None);
+                    StatusCode::CREATED
+                }),
+            )
+            .route_layer(from_fn(authorize_request))
+            .route_layer(from_fn(|request, next| authenticate(request, next, true)));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/expenses")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_authenticate_request_invalid_header_format() {
@@ -183,6 +404,58 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[test]
+    fn test_bearer_token_lowercase_scheme() {
+        assert_eq!(bearer_token("bearer abc.def.ghi"), Some("abc.def.ghi"));
+    }
+
+    #[test]
+    fn test_bearer_token_mixed_case_scheme() {
+        assert_eq!(bearer_token("BeArEr abc.def.ghi"), Some("abc.def.ghi"));
+    }
+
+    #[test]
+    fn test_bearer_token_extra_whitespace() {
+        assert_eq!(
+            bearer_token("  Bearer \t  abc.def.ghi  "),
+            Some("abc.def.ghi")
+        );
+    }
+
+    #[test]
+    fn test_bearer_token_rejects_other_schemes() {
+        assert_eq!(bearer_token("Basic dXNlcjpwYXNz"), None);
+        assert_eq!(bearer_token("Bearerabc.def.ghi"), None);
+    }
+
+    #[test]
+    fn test_bearer_token_rejects_missing_or_split_token() {
+        assert_eq!(bearer_token("Bearer"), None);
+        assert_eq!(bearer_token("Bearer   "), None);
+        assert_eq!(bearer_token("Bearer abc def"), None);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authenticate_request_case_insensitive_scheme() {
+        // Prepare
+        let app = setup_test_router();
+        let claim = create_test_claim(3600, -60);
+        let token = generate_test_token(&claim, TEST_JWT_SECRET);
+
+        let request = Request::builder()
+            .uri("/test")
+            .header(header::AUTHORIZATION, format!("bearer   {token}"))
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_authenticate_request_invalid_token_signature() {
@@ -324,4 +597,91 @@ mod tests {
         // Assert
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authorize_request_read_only_token_on_write_route() {
+        // Execute
+        let status = send_scoped_request("POST", "/expenses", Some("read")).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authorize_request_read_only_token_on_read_route() {
+        // Execute
+        let status = send_scoped_request("GET", "/expenses", Some("read")).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authorize_request_read_only_token_on_unscoped_route() {
+        // Execute
+        let status = send_scoped_request("POST", "/summaries/generate/raw", Some("read")).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authorize_request_write_token_on_write_route() {
+        // Execute
+        let status = send_scoped_request("POST", "/expenses", Some("read write")).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authorize_request_unscoped_token_on_write_route() {
+        // Execute
+        let status = send_scoped_request("POST", "/expenses", None).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_authenticate_request_exposes_subject() {
+        // Prepare
+        unsafe { std::env::set_var("JWT_SECRET", TEST_JWT_SECRET) };
+        let app = Router::new()
+            .route(
+                "/test",
+                get(|user: Option<Extension<AuthenticatedUser>>| async move {
+                    user.map(|Extension(AuthenticatedUser(sub))| sub)
+                        .unwrap_or_default()
+                }),
+            )
+            .route_layer(from_fn(authenticate_request));
+        let claim = Claim {
+            sub: Some("alice".to_string()),
+            ..create_test_claim(3600, -60)
+        };
+        let token = generate_test_token(&claim, TEST_JWT_SECRET);
+
+        let request = Request::builder()
+            .uri("/test")
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"alice");
+    }
 }
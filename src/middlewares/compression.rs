@@ -0,0 +1,241 @@
+use std::env;
+use tower_http::{
+    CompressionLevel,
+    compression::{
+        CompressionLayer, Predicate,
+        predicate::{NotForContentType, SizeAbove},
+    },
+};
+
+/// The compression algorithms that can be enabled through `COMPRESSION_ALGORITHMS`.
+const ALGORITHMS: [&str; 4] = ["br", "deflate", "gzip", "zstd"];
+
+/// Responses smaller than this many bytes are sent uncompressed unless `COMPRESSION_MIN_SIZE` says otherwise.
+const DEFAULT_MIN_SIZE: u16 = 256;
+
+/// Content types that are streamed to the client and must never be buffered by the compressor.
+const NDJSON: NotForContentType = NotForContentType::const_new("application/x-ndjson");
+
+/// The tunable settings of the response compression.
+#[derive(Debug, PartialEq, Eq)]
+struct CompressionConfig {
+    level: CompressionLevel,
+    br: bool,
+    deflate: bool,
+    gzip: bool,
+    zstd: bool,
+    min_size: u16,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: CompressionLevel::Default,
+            br: true,
+            deflate: true,
+            gzip: true,
+            zstd: true,
+            min_size: DEFAULT_MIN_SIZE,
+        }
+    }
+}
+
+/// Parses a compression level, either `fastest`, `best`, `default` or a precise numeric quality.
+fn parse_level(raw_level: &str) -> Option<CompressionLevel> {
+    let raw_level = raw_level.trim().to_ascii_lowercase();
+
+    match raw_level.as_str() {
+        "fastest" => Some(CompressionLevel::Fastest),
+        "best" => Some(CompressionLevel::Best),
+        "default" => Some(CompressionLevel::Default),
+        _ => raw_level.parse().ok().map(CompressionLevel::Precise),
+    }
+}
+
+/// Parses a comma-separated list of compression algorithms.
+/// Returns `None` if any of the algorithms is unknown or if none is given.
+fn parse_algorithms(raw_algorithms: &str) -> Option<Vec<String>> {
+    let algorithms = raw_algorithms
+        .split(',')
+        .map(|algorithm| algorithm.trim().to_ascii_lowercase())
+        .filter(|algorithm| !algorithm.is_empty())
+        .collect::<Vec<_>>();
+
+    let all_known = algorithms
+        .iter()
+        .all(|algorithm| ALGORITHMS.contains(&algorithm.as_str()));
+
+    (all_known && !algorithms.is_empty()).then_some(algorithms)
+}
+
+impl CompressionConfig {
+    /// Builds the configuration from the raw `COMPRESSION_LEVEL`, `COMPRESSION_ALGORITHMS`
+    /// and `COMPRESSION_MIN_SIZE` values.
+    /// Missing or invalid values fall back to the defaults.
+    fn from_raw(
+        raw_level: Option<&str>,
+        raw_algorithms: Option<&str>,
+        raw_min_size: Option<&str>,
+    ) -> Self {
+        let mut config = Self::default();
+
+        if let Some(raw_level) = raw_level {
+            match parse_level(raw_level) {
+                Some(level) => config.level = level,
+                None => {
+                    tracing::warn!("Invalid value for COMPRESSION_LEVEL, falling back to default")
+                }
+            }
+        }
+
+        if let Some(raw_algorithms) = raw_algorithms {
+            match parse_algorithms(raw_algorithms) {
+                Some(algorithms) => {
+                    let enabled = |name: &str| algorithms.iter().any(|algorithm| algorithm == name);
+
+                    config.br = enabled("br");
+                    config.deflate = enabled("deflate");
+                    config.gzip = enabled("gzip");
+                    config.zstd = enabled("zstd");
+                }
+                None => tracing::warn!(
+                    "Invalid value for COMPRESSION_ALGORITHMS, falling back to all algorithms"
+                ),
+            }
+        }
+
+        if let Some(raw_min_size) = raw_min_size {
+            match raw_min_size.trim().parse() {
+                Ok(min_size) => config.min_size = min_size,
+                Err(_) => {
+                    tracing::warn!(
+                        "Invalid value for COMPRESSION_MIN_SIZE, falling back to default"
+                    )
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Decides which responses get compressed: bodies of a known size below `min_size` are skipped,
+    /// as are images, gRPC and streamed content types (NDJSON and server-sent events).
+    fn predicate(&self) -> impl Predicate + use<> {
+        SizeAbove::new(self.min_size)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE)
+            .and(NDJSON)
+    }
+}
+
+/// Creates the response compression layer.
+/// The quality is read from the `COMPRESSION_LEVEL` environment variable
+/// (`fastest`, `best`, `default` or a numeric quality), and the enabled algorithms
+/// from the comma-separated `COMPRESSION_ALGORITHMS` environment variable (`br`, `deflate`, `gzip`, `zstd`).
+/// All algorithms are enabled with their default quality when the variables are unset.
+/// Responses smaller than `COMPRESSION_MIN_SIZE` bytes (256 by default) and streamed
+/// responses (`application/x-ndjson`, `text/event-stream`) are never compressed.
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    let raw_level = env::var("COMPRESSION_LEVEL").ok();
+    let raw_algorithms = env::var("COMPRESSION_ALGORITHMS").ok();
+    let raw_min_size = env::var("COMPRESSION_MIN_SIZE").ok();
+    let config = CompressionConfig::from_raw(
+        raw_level.as_deref(),
+        raw_algorithms.as_deref(),
+        raw_min_size.as_deref(),
+    );
+
+    CompressionLayer::new()
+        .quality(config.level)
+        .br(config.br)
+        .deflate(config.deflate)
+        .gzip(config.gzip)
+        .zstd(config.zstd)
+        .compress_when(config.predicate())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Response, header},
+    };
+
+    fn response(content_type: &str, body_size: usize) -> Response<Body> {
+        Response::builder()
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from("a".repeat(body_size)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compression_config_default() {
+        assert_eq!(
+            CompressionConfig::from_raw(None, None, None),
+            CompressionConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_compression_config_level() {
+        assert_eq!(
+            CompressionConfig::from_raw(Some("Best"), None, None).level,
+            CompressionLevel::Best
+        );
+        assert_eq!(
+            CompressionConfig::from_raw(Some(" 5 "), None, None).level,
+            CompressionLevel::Precise(5)
+        );
+        assert_eq!(
+            CompressionConfig::from_raw(Some("maximum"), None, None).level,
+            CompressionLevel::Default
+        );
+    }
+
+    #[test]
+    fn test_compression_config_algorithms() {
+        let config = CompressionConfig::from_raw(None, Some("br, GZIP"), None);
+
+        assert!(config.br);
+        assert!(config.gzip);
+        assert!(!config.deflate);
+        assert!(!config.zstd);
+    }
+
+    #[test]
+    fn test_compression_config_invalid_algorithms() {
+        assert_eq!(
+            CompressionConfig::from_raw(None, Some("br,lz4"), None),
+            CompressionConfig::default()
+        );
+        assert_eq!(
+            CompressionConfig::from_raw(None, Some(" , "), None),
+            CompressionConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_compression_config_min_size() {
+        assert_eq!(
+            CompressionConfig::from_raw(None, None, Some(" 1024 ")).min_size,
+            1024
+        );
+        assert_eq!(
+            CompressionConfig::from_raw(None, None, Some("-1")).min_size,
+            DEFAULT_MIN_SIZE
+        );
+    }
+
+    #[test]
+    fn test_compression_predicate() {
+        let predicate = CompressionConfig::from_raw(None, None, Some("64")).predicate();
+
+        assert!(predicate.should_compress(&response("application/json", 128)));
+        assert!(!predicate.should_compress(&response("application/json", 32)));
+        assert!(!predicate.should_compress(&response("application/x-ndjson", 128)));
+        assert!(!predicate.should_compress(&response("text/event-stream", 128)));
+        assert!(!predicate.should_compress(&response("image/png", 128)));
+    }
+}
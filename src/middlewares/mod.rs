@@ -1,2 +1,5 @@
 pub mod auth;
+pub mod compression;
+pub mod request_id;
+pub mod timeout;
 pub mod trace;
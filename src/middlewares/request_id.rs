@@ -0,0 +1,138 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+/// The header carrying the request ID, both on the request and on the response.
+pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The maximum length of a client supplied request ID.
+/// Longer values are replaced with a generated one to keep logs tidy.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    /// The ID of the request currently being handled.
+    /// Used to attach the request ID to error responses.
+    pub static REQUEST_ID: String;
+}
+
+/// The ID of the current request, stored in the request extensions.
+#[derive(Clone, Debug)]
+pub struct RequestId(pub String);
+
+/// Reads the request ID from the `X-Request-Id` header, or generates a new one if it's missing or invalid.
+fn request_id_from(request: &Request) -> String {
+    request
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LENGTH)
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Middleware to propagate a request ID through the request lifecycle.
+/// The ID is stored in the request extensions, made available to the handler through [`REQUEST_ID`],
+/// and echoed back in the `X-Request-Id` response header.
+pub async fn propagate_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request_id_from(&request);
+
+    // The value only contains visible ASCII characters at this point, so the conversion never fails.
+    let header_value = HeaderValue::from_str(&request_id).unwrap();
+
+    request
+        .headers_mut()
+        .insert(X_REQUEST_ID.clone(), header_value.clone());
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
+
+    let mut response = REQUEST_ID.scope(request_id, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(X_REQUEST_ID.clone(), header_value);
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Extension, Router, body::Body, http::StatusCode, middleware::from_fn, routing::get,
+    };
+    use tower::ServiceExt;
+
+    fn setup_test_router() -> Router {
+        Router::new()
+            .route(
+                "/test",
+                get(
+                    |Extension(RequestId(request_id)): Extension<RequestId>| async move {
+                        assert_eq!(request_id, REQUEST_ID.get());
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .layer(from_fn(propagate_request_id))
+    }
+
+    #[tokio::test]
+    async fn test_propagate_request_id_echoes_header() {
+        // Prepare
+        let app = setup_test_router();
+
+        let request = Request::builder()
+            .uri("/test")
+            .header(&X_REQUEST_ID, "abc-123")
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(&X_REQUEST_ID).unwrap(), "abc-123");
+    }
+
+    #[tokio::test]
+    async fn test_propagate_request_id_generates_missing_header() {
+        // Prepare
+        let app = setup_test_router();
+
+        let request = Request::builder().uri("/test").body(Body::empty()).unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let request_id = response.headers().get(&X_REQUEST_ID).unwrap();
+        assert!(Uuid::parse_str(request_id.to_str().unwrap()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_propagate_request_id_replaces_oversized_header() {
+        // Prepare
+        let app = setup_test_router();
+        let oversized_id = "a".repeat(MAX_REQUEST_ID_LENGTH + 1);
+
+        let request = Request::builder()
+            .uri("/test")
+            .header(&X_REQUEST_ID, &oversized_id)
+            .body(Body::empty())
+            .unwrap();
+
+        // Execute
+        let response = app.oneshot(request).await.unwrap();
+
+        // Assert
+        let request_id = response.headers().get(&X_REQUEST_ID).unwrap();
+        assert!(Uuid::parse_str(request_id.to_str().unwrap()).is_ok());
+    }
+}
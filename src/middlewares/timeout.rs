@@ -0,0 +1,111 @@
+use std::time::Duration;
+use tower_http::timeout::TimeoutLayer;
+
+use crate::common::env;
+
+/// The default number of seconds a request may take, used when `REQUEST_TIMEOUT_SECS` is unset or invalid.
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Creates the layer aborting requests that take too long with a `408 Request Timeout`.
+/// The timeout is read from the `REQUEST_TIMEOUT_SECS` environment variable, defaulting to 30 seconds.
+pub fn timeout_layer() -> TimeoutLayer {
+    let timeout_secs = env::parse_or("REQUEST_TIMEOUT_SECS", DEFAULT_REQUEST_TIMEOUT_SECS);
+
+    TimeoutLayer::new(Duration::from_secs(timeout_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+    };
+    use serial_test::serial;
+    use tower::ServiceExt;
+
+    /// Builds a router behind the configured timeout layer, whose only route takes the given duration.
+    fn setup_test_router(duration: Duration) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(move || async move {
+                    tokio::time::sleep(duration).await;
+                    StatusCode::OK
+                }),
+            )
+            .layer(timeout_layer())
+    }
+
+    /// Sends a request to the router, returning the status of the response.
+    async fn send_request(app: Router) -> StatusCode {
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_timeout_layer_aborts_slow_request() {
+        // Prepare
+        unsafe { std::env::set_var("REQUEST_TIMEOUT_SECS", "1") };
+        let app = setup_test_router(Duration::from_secs(5));
+
+        // Execute
+        let status = send_request(app).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::REQUEST_TIMEOUT);
+
+        unsafe { std::env::remove_var("REQUEST_TIMEOUT_SECS") };
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_timeout_layer_passes_fast_request() {
+        // Prepare
+        unsafe { std::env::set_var("REQUEST_TIMEOUT_SECS", "1") };
+        let app = setup_test_router(Duration::ZERO);
+
+        // Execute
+        let status = send_request(app).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+
+        unsafe { std::env::remove_var("REQUEST_TIMEOUT_SECS") };
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_timeout_layer_defaults_when_unset() {
+        // Prepare
+        unsafe { std::env::remove_var("REQUEST_TIMEOUT_SECS") };
+        // Slower than a one second timeout, well within the default one
+        let app = setup_test_router(Duration::from_millis(1500));
+
+        // Execute
+        let status = send_request(app).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_timeout_layer_defaults_when_invalid() {
+        // Prepare
+        unsafe { std::env::set_var("REQUEST_TIMEOUT_SECS", "soon") };
+        let app = setup_test_router(Duration::from_millis(1500));
+
+        // Execute
+        let status = send_request(app).await;
+
+        // Assert
+        assert_eq!(status, StatusCode::OK);
+
+        unsafe { std::env::remove_var("REQUEST_TIMEOUT_SECS") };
+    }
+}
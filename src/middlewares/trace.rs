@@ -1,21 +1,95 @@
+use axum::{body::Body, http::Request};
+use std::{env, str::FromStr};
 use tower_http::trace::{self, HttpMakeClassifier, TraceLayer};
+use tracing::Span;
 use tracing_subscriber::EnvFilter;
 
+use crate::middlewares::request_id::RequestId;
+
+/// The output format of the log lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum LogFormat {
+    #[default]
+    Compact,
+    Json,
+    Pretty,
+}
+
+impl FromStr for LogFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "compact" => Ok(Self::Compact),
+            "json" => Ok(Self::Json),
+            "pretty" => Ok(Self::Pretty),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Initialize tracing subscriber.
+/// The output format is read from the `LOG_FORMAT` environment variable (`compact`, `json` or `pretty`),
+/// defaulting to `compact`.
 pub fn init() {
-    tracing_subscriber::fmt()
+    let raw_format = env::var("LOG_FORMAT").ok();
+    let parsed_format = raw_format.as_deref().map(LogFormat::from_str);
+
+    let subscriber = tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env())
-        .with_target(true)
-        .compact()
-        .init();
+        .with_target(true);
+
+    match parsed_format
+        .unwrap_or(Ok(LogFormat::default()))
+        .unwrap_or_default()
+    {
+        LogFormat::Compact => subscriber.compact().init(),
+        LogFormat::Json => subscriber.json().init(),
+        LogFormat::Pretty => subscriber.pretty().init(),
+    }
+
+    if let Some(Err(())) = parsed_format {
+        tracing::warn!("Invalid value for LOG_FORMAT, falling back to compact");
+    }
 }
 
 /// This module provides a function to create a `TraceLayer` for HTTP requests.
 /// It uses the `tower_http` crate to create a layer that can be used with a `tower` service.
-pub fn http_trace_layer() -> TraceLayer<HttpMakeClassifier> {
+pub fn http_trace_layer() -> TraceLayer<HttpMakeClassifier, fn(&Request<Body>) -> Span> {
     TraceLayer::new_for_http()
-        .make_span_with(trace::DefaultMakeSpan::new().include_headers(true))
+        .make_span_with(make_span as fn(&Request<Body>) -> Span)
         .on_request(trace::DefaultOnRequest::new().level(tracing::Level::INFO))
         .on_response(trace::DefaultOnResponse::new().level(tracing::Level::INFO))
         .on_failure(trace::DefaultOnFailure::new().level(tracing::Level::ERROR))
 }
+
+/// Creates the span of an HTTP request, including the request ID set by the request ID middleware.
+fn make_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|RequestId(request_id)| request_id.as_str())
+        .unwrap_or_default();
+
+    tracing::debug_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+        request_id = %request_id,
+        headers = ?request.headers(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!("compact".parse(), Ok(LogFormat::Compact));
+        assert_eq!(" JSON ".parse(), Ok(LogFormat::Json));
+        assert_eq!("pretty".parse(), Ok(LogFormat::Pretty));
+        assert_eq!("xml".parse::<LogFormat>(), Err(()));
+    }
+}
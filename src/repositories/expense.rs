@@ -1,12 +1,54 @@
+use async_stream::try_stream;
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, QueryBuilder, Row, query, query_as};
-use std::sync::Arc;
+use futures::{TryStreamExt, stream::BoxStream};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, query, query_as, query_scalar};
+use std::{sync::Arc, time::Instant};
+use time::Date;
 
-use crate::dtos::{
-    expense::{IndexExpenseQuery, SaveExpense},
-    query_result::{IndexExpenseElement, ShowExpense, ShowLatestExpense, SimpleEntity, Tag},
+use crate::{
+    common::{
+        sql::escape_like,
+        timing::{TimedExt, log_elapsed},
+    },
+    dtos::{
+        expense::{AssignExpenseTags, IndexExpenseQuery, SaveExpense, TagAssignmentMode},
+        query_result::{
+            CategoryTotal, IndexExpenseElement, PriorityLevel, ShowExpense, ShowLatestExpense,
+            SimpleEntity, Tag, UnknownReferences,
+        },
+    },
+    repositories::Page,
 };
 
+/// The outcome of inserting multiple expenses at once.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BulkInsertOutcome {
+    /// The IDs of the inserted expenses, in the same order as the input. Skipped expenses are left out.
+    pub ids: Vec<i32>,
+    /// The number of expenses skipped because their external ID was already recorded.
+    pub skipped: usize,
+}
+
+/// Pairs each expense, given by its external ID, with the ID of its inserted row, or `None` if it was skipped.
+/// The skipped expenses are missing from the returned rows, which otherwise follow the order of the input.
+/// An expense was thus inserted when it has no external ID, or when the next row holds its external ID.
+fn pair_inserted_rows<'a>(
+    external_ids: impl IntoIterator<Item = Option<&'a str>>,
+    rows: Vec<(i32, Option<String>)>,
+) -> Vec<Option<i32>> {
+    let mut rows = rows.into_iter().peekable();
+
+    external_ids
+        .into_iter()
+        .map(|external_id| {
+            rows.next_if(|(_, row_external_id)| {
+                external_id.is_none() || row_external_id.as_deref() == external_id
+            })
+            .map(|(id, _)| id)
+        })
+        .collect()
+}
+
 /// Repository to interact with the `expense` table in the database.
 pub struct Repository {
     /// The PostgreSQL connection pool.
@@ -18,66 +60,367 @@ impl Repository {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
     }
+
+    /// Counts the expenses matching the filters of the query, regardless of its pagination.
+    async fn count(
+        &self,
+        query: &IndexExpenseQuery,
+        user_id: Option<&str>,
+    ) -> Result<i64, sqlx::Error> {
+        query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "total!"
+            FROM expense
+            WHERE
+                ($1::DATE IS NULL OR date >= $1::DATE)
+                AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $5
+                AND (
+                    $3::INT[] IS NULL
+                    OR id IN (
+                        SELECT expense_id
+                        FROM expense_tag
+                        WHERE tag_id = ANY($3::INT[])
+                        GROUP BY expense_id
+                        HAVING
+                            NOT $4::BOOLEAN
+                            OR COUNT(DISTINCT tag_id) = (
+                                SELECT COUNT(DISTINCT filtered.tag_id)
+                                FROM UNNEST($3::INT[]) AS filtered(tag_id)
+                            )
+                    )
+                )
+                AND (
+                    NOT $6::BOOLEAN
+                    OR NOT EXISTS (SELECT 1 FROM expense_tag et WHERE et.expense_id = expense.id)
+                )
+                AND ($7::TEXT IS NULL OR description = $7)
+                AND ($8::TEXT IS NULL OR description ILIKE '%' || $8 || '%')
+            "#,
+            query.start_date,
+            query.end_date,
+            query.tag_ids.as_deref(),
+            query.match_all_tags(),
+            user_id,
+            query.only_untagged(),
+            query.exact_description(),
+            query.contained_description().map(escape_like),
+        )
+        .fetch_one(&*self.pool)
+        .timed("expense.count_all")
+        .await
+    }
 }
 
 /// Trait defining operations for the `expense` table.
+/// Every operation is scoped to the expenses owned by the given user.
+/// Expenses without an owner are only visible when no user is given.
 #[async_trait]
 pub trait RepositoryOperation: Send + Sync {
+    /// Assigns the tags to every given expense in a single transaction.
+    /// In `replace` mode the existing tags of the expenses are removed first,
+    /// otherwise tags already assigned to an expense are left untouched.
+    /// Expenses owned by another user are skipped.
+    async fn assign_tags(
+        &self,
+        assignment: &AssignExpenseTags,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
     /// Deletes an expense from the database.
-    async fn delete(&self, id: i32) -> Result<(), sqlx::Error>;
-    /// Finds all expenses from the database.
+    async fn delete(&self, id: i32, user_id: Option<&str>) -> Result<(), sqlx::Error>;
+    /// Finds the distinct dates with at least one expense within the optional date range, in ascending order.
+    /// The dates are formatted as `YYYY-MM-DD`.
+    async fn find_active_dates(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<String>, sqlx::Error>;
+    /// Finds the categories with at least one expense within the optional date range, along with
+    /// the total of their expenses, in ascending order by name. Refunds are subtracted from the totals.
+    async fn find_used_categories(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<CategoryTotal>, sqlx::Error>;
+    /// Finds a page of the expenses matching the query from the database.
+    /// Count-only queries skip fetching the expenses and count them instead.
+    /// When tag IDs are given, only expenses with any, or all when requested, of the tags are included.
+    /// When untagged expenses are requested, only expenses without any tag are included.
     async fn find_all(
         &self,
         query: &IndexExpenseQuery,
-    ) -> Result<Vec<IndexExpenseElement>, sqlx::Error>;
-    /// Finds the latest expense from the database.
-    async fn find_latest(&self) -> Result<ShowLatestExpense, sqlx::Error>;
+        user_id: Option<&str>,
+    ) -> Result<Page<IndexExpenseElement>, sqlx::Error>;
+    /// Finds which of the given category IDs require expenses to have a description.
+    async fn find_description_required_category_ids(
+        &self,
+        category_ids: &[i32],
+    ) -> Result<Vec<i32>, sqlx::Error>;
+    /// Finds the expenses with the given IDs from the database, in the same order as the IDs.
+    /// The category and wallet are left empty, rather than hiding the expense, if they no longer exist.
+    async fn find_many_by_ids(
+        &self,
+        ids: &[i32],
+        user_id: Option<&str>,
+    ) -> Result<Vec<ShowLatestExpense>, sqlx::Error>;
+    /// Finds the most recent expenses from the database, newest first.
+    /// The category and wallet are left empty, rather than hiding the expense, if they no longer exist.
+    async fn find_recent(
+        &self,
+        limit: i64,
+        user_id: Option<&str>,
+    ) -> Result<Vec<ShowLatestExpense>, sqlx::Error>;
     /// Finds a specific expense by ID from the database.
-    async fn find_one(&self, id: i32) -> Result<ShowExpense, sqlx::Error>;
+    /// The category and wallet are left empty, rather than hiding the expense, if they no longer exist.
+    async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<ShowExpense, sqlx::Error>;
+    /// Finds the category, wallet and tag IDs referenced by the given expenses that don't exist in the database.
+    /// Wallets owned by another user are treated as nonexistent.
+    async fn find_unknown_references(
+        &self,
+        expenses: &[&SaveExpense],
+        user_id: Option<&str>,
+    ) -> Result<UnknownReferences, sqlx::Error>;
     /// Inserts multiple expenses into the database.
-    async fn insert_bulk(&self, expenses: &[SaveExpense]) -> Result<(), sqlx::Error>;
+    /// Expenses whose external ID is already recorded for the user, including earlier in the same batch, are skipped.
+    /// The expenses are owned by, and recorded as created by, the given user, if any.
+    async fn insert_bulk(
+        &self,
+        expenses: &[SaveExpense],
+        user_id: Option<&str>,
+    ) -> Result<BulkInsertOutcome, sqlx::Error>;
+    /// Streams all expenses matching the query from the database, ignoring the limit of its pagination.
+    /// Rows are yielded as they arrive from the database instead of being buffered.
+    fn stream_all(
+        &self,
+        query: &IndexExpenseQuery,
+        user_id: Option<&str>,
+    ) -> BoxStream<'static, Result<IndexExpenseElement, sqlx::Error>>;
     /// Updates an existing expense in the database.
-    async fn update(&self, id: i32, expense: &SaveExpense) -> Result<(), sqlx::Error>;
+    /// The tags of the expense are only replaced when `tag_ids` is provided.
+    /// The creator is only recorded if the expense doesn't have one yet.
+    async fn update(
+        &self,
+        id: i32,
+        expense: &SaveExpense,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
 }
 
 #[async_trait]
 impl RepositoryOperation for Repository {
+    async fn assign_tags(
+        &self,
+        assignment: &AssignExpenseTags,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        async {
+            let mut tx = self.pool.begin().await?;
+
+            let expense_ids = query_scalar!(
+                r#"
+                SELECT id
+                FROM expense
+                WHERE id = ANY($1::INT[]) AND user_id IS NOT DISTINCT FROM $2
+                "#,
+                &assignment.expense_ids,
+                user_id,
+            )
+            .fetch_all(&mut *tx)
+            .await?;
+
+            if assignment.mode == TagAssignmentMode::Replace {
+                query!(
+                    "DELETE FROM expense_tag WHERE expense_id = ANY($1::INT[])",
+                    &expense_ids,
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            query!(
+                r#"
+                INSERT INTO expense_tag (expense_id, tag_id)
+                SELECT e.id, t.id
+                FROM UNNEST($1::INT[]) AS e(id)
+                CROSS JOIN UNNEST($2::INT[]) AS t(id)
+                ON CONFLICT DO NOTHING
+                "#,
+                &expense_ids,
+                &assignment.tag_ids,
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            // Touch the expenses so that their `updated_at`, and thus their ETag, reflects the new tags.
+            query!(
+                "UPDATE expense SET updated_at = NOW() WHERE id = ANY($1::INT[])",
+                &expense_ids,
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        }
+        .timed("expense.assign_tags")
+        .await
+    }
+
+    async fn find_active_dates(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let active_dates = query_scalar!(
+            r#"
+            SELECT DISTINCT TO_CHAR(date, 'YYYY-MM-DD') AS "date!"
+            FROM expense
+            WHERE
+                ($1::DATE IS NULL OR date >= $1::DATE)
+                AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $3
+            ORDER BY 1
+            "#,
+            start_date,
+            end_date,
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("expense.find_active_dates")
+        .await?;
+
+        Ok(active_dates)
+    }
+
+    async fn find_used_categories(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<CategoryTotal>, sqlx::Error> {
+        let used_categories = query_as!(
+            CategoryTotal,
+            r#"
+            SELECT
+                c.name,
+                SUM(CASE WHEN e.is_refund THEN -e.amount ELSE e.amount END) AS "amount!"
+            FROM expense e
+            JOIN category c ON e.category_id = c.id
+            WHERE
+                ($1::DATE IS NULL OR e.date >= $1::DATE)
+                AND ($2::DATE IS NULL OR e.date <= $2::DATE)
+                AND e.user_id IS NOT DISTINCT FROM $3
+            GROUP BY c.id
+            ORDER BY LOWER(c.name), c.id
+            "#,
+            start_date,
+            end_date,
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("expense.find_used_categories")
+        .await?;
+
+        Ok(used_categories)
+    }
+
     async fn find_all(
         &self,
         query: &IndexExpenseQuery,
-    ) -> Result<Vec<IndexExpenseElement>, sqlx::Error> {
-        let expenses = query_as!(
-            IndexExpenseElement,
+        user_id: Option<&str>,
+    ) -> Result<Page<IndexExpenseElement>, sqlx::Error> {
+        if query.pagination.is_count_only() {
+            let total = self.count(query, user_id).await?;
+
+            return Ok(Page::count_only(total, query.pagination.offset()));
+        }
+
+        let limit = query.pagination.limit();
+        let rows = query!(
             r#"
             SELECT
                 id,
                 amount,
                 TO_CHAR(date, 'YYYY-MM-DD') AS "date!",
-                description
+                description,
+                COUNT(*) OVER () AS "total!"
             FROM
                 expense
             WHERE
                 ($1::DATE IS NULL OR date >= $1::DATE)
                 AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $7
+                AND (
+                    $5::INT[] IS NULL
+                    OR id IN (
+                        SELECT expense_id
+                        FROM expense_tag
+                        WHERE tag_id = ANY($5::INT[])
+                        GROUP BY expense_id
+                        HAVING
+                            NOT $6::BOOLEAN
+                            OR COUNT(DISTINCT tag_id) = (
+                                SELECT COUNT(DISTINCT filtered.tag_id)
+                                FROM UNNEST($5::INT[]) AS filtered(tag_id)
+                            )
+                    )
+                )
+                AND (
+                    NOT $8::BOOLEAN
+                    OR NOT EXISTS (SELECT 1 FROM expense_tag et WHERE et.expense_id = expense.id)
+                )
+                AND ($9::TEXT IS NULL OR description = $9)
+                AND ($10::TEXT IS NULL OR description ILIKE '%' || $10 || '%')
             ORDER BY id
             LIMIT $3 OFFSET $4
             "#,
             query.start_date,
             query.end_date,
-            query.pagination.limit(),
+            limit + 1,
             query.pagination.offset(),
+            query.tag_ids.as_deref(),
+            query.match_all_tags(),
+            user_id,
+            query.only_untagged(),
+            query.exact_description(),
+            query.contained_description().map(escape_like),
         )
         .fetch_all(&*self.pool)
+        .timed("expense.find_all")
         .await?;
 
-        Ok(expenses)
+        // The window count is only known when a row is returned, i.e. not past the last page.
+        let total = match rows.first() {
+            Some(row) => row.total,
+            None => self.count(query, user_id).await?,
+        };
+        let expenses = rows
+            .into_iter()
+            .map(|row| IndexExpenseElement {
+                id: row.id,
+                amount: row.amount,
+                date: row.date,
+                description: row.description,
+            })
+            .collect();
+
+        Ok(Page::from_rows(expenses, limit, total))
     }
 
-    async fn delete(&self, id: i32) -> Result<(), sqlx::Error> {
-        let rows_affected = query!("DELETE FROM expense WHERE id = $1", id)
-            .execute(&*self.pool)
-            .await?
-            .rows_affected();
+    async fn delete(&self, id: i32, user_id: Option<&str>) -> Result<(), sqlx::Error> {
+        let rows_affected = query!(
+            "DELETE FROM expense WHERE id = $1 AND user_id IS NOT DISTINCT FROM $2",
+            id,
+            user_id,
+        )
+        .execute(&*self.pool)
+        .timed("expense.delete")
+        .await?
+        .rows_affected();
 
         if rows_affected == 0 {
             return Err(sqlx::Error::RowNotFound);
@@ -86,8 +429,74 @@ impl RepositoryOperation for Repository {
         Ok(())
     }
 
-    async fn find_latest(&self) -> Result<ShowLatestExpense, sqlx::Error> {
-        let latest_expense = query_as!(
+    async fn find_many_by_ids(
+        &self,
+        ids: &[i32],
+        user_id: Option<&str>,
+    ) -> Result<Vec<ShowLatestExpense>, sqlx::Error> {
+        let expenses = query_as!(
+            ShowLatestExpense,
+            r#"
+            SELECT
+                e.id,
+                e.amount,
+                TO_CHAR(e.date, 'YYYY-MM-DD') AS "date!",
+                e.description,
+                e.priority AS "priority: PriorityLevel",
+                e.is_refund,
+                CASE WHEN c.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', c.id,
+                        'name', c.name
+                    )
+                END AS "category: sqlx::types::Json<SimpleEntity>",
+                CASE WHEN w.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', w.id,
+                        'name', w.name
+                    )
+                END AS "wallet: sqlx::types::Json<SimpleEntity>",
+                COALESCE(
+                    JSONB_AGG(
+                        JSONB_BUILD_OBJECT(
+                            'id', t.id,
+                            'name', t.name,
+                            'is_important', t.is_important
+                        ) ORDER BY (CASE WHEN t.is_important IS true THEN 0 ELSE 1 END), t.name
+                    ) FILTER (WHERE t.id IS NOT NULL),
+                    '[]'
+                ) AS "tags!: sqlx::types::Json<Vec<Tag>>"
+            FROM
+                expense e
+            LEFT JOIN
+                category c ON e.category_id = c.id
+            LEFT JOIN
+                wallet w ON e.wallet_id = w.id
+            LEFT JOIN
+                expense_tag et ON e.id = et.expense_id
+            LEFT JOIN
+                tag t ON et.tag_id = t.id
+            WHERE e.id = ANY($1::INT[]) AND e.user_id IS NOT DISTINCT FROM $2
+            GROUP BY
+                e.id, c.id, w.id
+            ORDER BY ARRAY_POSITION($1::INT[], e.id)
+            "#,
+            ids,
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("expense.find_many_by_ids")
+        .await?;
+
+        Ok(expenses)
+    }
+
+    async fn find_recent(
+        &self,
+        limit: i64,
+        user_id: Option<&str>,
+    ) -> Result<Vec<ShowLatestExpense>, sqlx::Error> {
+        let recent_expenses = query_as!(
             ShowLatestExpense,
             r#"
             SELECT
@@ -95,15 +504,20 @@ impl RepositoryOperation for Repository {
                 e.amount,
                 TO_CHAR(e.date, 'YYYY-MM-DD') AS "date!",
                 e.description,
-                e.priority,
-                JSONB_BUILD_OBJECT(
-                    'id', c.id,
-                    'name', c.name
-                ) AS "category!: sqlx::types::Json<SimpleEntity>",
-                JSONB_BUILD_OBJECT(
-                    'id', w.id,
-                    'name', w.name
-                ) AS "wallet!: sqlx::types::Json<SimpleEntity>",
+                e.priority AS "priority: PriorityLevel",
+                e.is_refund,
+                CASE WHEN c.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', c.id,
+                        'name', c.name
+                    )
+                END AS "category: sqlx::types::Json<SimpleEntity>",
+                CASE WHEN w.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', w.id,
+                        'name', w.name
+                    )
+                END AS "wallet: sqlx::types::Json<SimpleEntity>",
                 COALESCE(
                     JSONB_AGG(
                         JSONB_BUILD_OBJECT(
@@ -116,27 +530,31 @@ impl RepositoryOperation for Repository {
                 ) AS "tags!: sqlx::types::Json<Vec<Tag>>"
             FROM
                 expense e
-            JOIN
+            LEFT JOIN
                 category c ON e.category_id = c.id
-            JOIN
+            LEFT JOIN
                 wallet w ON e.wallet_id = w.id
             LEFT JOIN
                 expense_tag et ON e.id = et.expense_id
             LEFT JOIN 
                 tag t ON et.tag_id = t.id
+            WHERE e.user_id IS NOT DISTINCT FROM $2
             GROUP BY
                 e.id, c.id, w.id
             ORDER BY id DESC
-            LIMIT 1
-            "#
+            LIMIT $1
+            "#,
+            limit,
+            user_id,
         )
-        .fetch_one(&*self.pool)
+        .fetch_all(&*self.pool)
+        .timed("expense.find_recent")
         .await?;
 
-        Ok(latest_expense)
+        Ok(recent_expenses)
     }
 
-    async fn find_one(&self, id: i32) -> Result<ShowExpense, sqlx::Error> {
+    async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<ShowExpense, sqlx::Error> {
         let latest_expense = query_as!(
             ShowExpense,
             r#"
@@ -144,15 +562,20 @@ impl RepositoryOperation for Repository {
                 e.amount,
                 TO_CHAR(e.date, 'YYYY-MM-DD') AS "date!",
                 e.description,
-                e.priority,
-                JSONB_BUILD_OBJECT(
-                    'id', c.id,
-                    'name', c.name
-                ) AS "category!: sqlx::types::Json<SimpleEntity>",
-                JSONB_BUILD_OBJECT(
-                    'id', w.id,
-                    'name', w.name
-                ) AS "wallet!: sqlx::types::Json<SimpleEntity>",
+                e.priority AS "priority: PriorityLevel",
+                e.is_refund,
+                CASE WHEN c.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', c.id,
+                        'name', c.name
+                    )
+                END AS "category: sqlx::types::Json<SimpleEntity>",
+                CASE WHEN w.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', w.id,
+                        'name', w.name
+                    )
+                END AS "wallet: sqlx::types::Json<SimpleEntity>",
                 COALESCE(
                     JSONB_AGG(
                         JSONB_BUILD_OBJECT(
@@ -162,138 +585,662 @@ impl RepositoryOperation for Repository {
                         ) ORDER BY (CASE WHEN t.is_important IS true THEN 0 ELSE 1 END), t.name
                     ) FILTER (WHERE t.id IS NOT NULL), 
                     '[]'
-                ) AS "tags!: sqlx::types::Json<Vec<Tag>>"
+                ) AS "tags!: sqlx::types::Json<Vec<Tag>>",
+                e.updated_at
             FROM
                 expense e
-            JOIN
+            LEFT JOIN
                 category c ON e.category_id = c.id
-            JOIN
+            LEFT JOIN
                 wallet w ON e.wallet_id = w.id
             LEFT JOIN
                 expense_tag et ON e.id = et.expense_id
             LEFT JOIN 
                 tag t ON et.tag_id = t.id
-            WHERE e.id = $1
+            WHERE e.id = $1 AND e.user_id IS NOT DISTINCT FROM $2
             GROUP BY
                 e.id, c.id, w.id
             "#,
             id,
+            user_id,
         )
         .fetch_one(&*self.pool)
+        .timed("expense.find_one")
         .await?;
 
         Ok(latest_expense)
     }
 
-    async fn insert_bulk(&self, expenses: &[SaveExpense]) -> Result<(), sqlx::Error> {
-        let mut expense_query = QueryBuilder::<Postgres>::new(
-            "INSERT INTO expense (amount, date, description, category_id, wallet_id, priority) ",
-        );
-
-        expense_query.push_values(expenses, |mut builder, expense| {
-            builder
-                .push_bind(expense.amount)
-                .push_bind(expense.date)
-                .push_bind(expense.description.clone())
-                .push_bind(expense.category_id)
-                .push_bind(expense.wallet_id)
-                .push_bind(expense.priority);
-        });
-        expense_query.push(" RETURNING id");
-
-        let mut tx = self.pool.begin().await?;
-
-        let expense_inserted_ids = expense_query
-            .build()
-            .fetch_all(&mut *tx)
-            .await?
+    async fn find_description_required_category_ids(
+        &self,
+        category_ids: &[i32],
+    ) -> Result<Vec<i32>, sqlx::Error> {
+        query_scalar!(
+            r#"
+            SELECT id
+            FROM category
+            WHERE id = ANY($1::INT[]) AND require_description
+            ORDER BY id
+            "#,
+            category_ids,
+        )
+        .fetch_all(&*self.pool)
+        .timed("expense.find_description_required_category_ids")
+        .await
+    }
+
+    async fn find_unknown_references(
+        &self,
+        expenses: &[&SaveExpense],
+        user_id: Option<&str>,
+    ) -> Result<UnknownReferences, sqlx::Error> {
+        let category_ids = expenses
+            .iter()
+            .map(|expense| expense.category_id)
+            .collect::<Vec<i32>>();
+        let wallet_ids = expenses
             .iter()
-            .map(|row| row.try_get(0).unwrap())
+            .map(|expense| expense.wallet_id)
+            .collect::<Vec<i32>>();
+        let tag_ids = expenses
+            .iter()
+            .flat_map(|expense| expense.tag_ids.iter().flatten().copied())
             .collect::<Vec<i32>>();
 
-        drop(expense_query);
+        let unknown_references = query_as!(
+            UnknownReferences,
+            r#"
+            SELECT
+                ARRAY(
+                    SELECT DISTINCT u.id
+                    FROM UNNEST($1::INT[]) AS u(id)
+                    WHERE NOT EXISTS (SELECT 1 FROM category c WHERE c.id = u.id)
+                    ORDER BY u.id
+                ) AS "category_ids!",
+                ARRAY(
+                    SELECT DISTINCT u.id
+                    FROM UNNEST($2::INT[]) AS u(id)
+                    WHERE NOT EXISTS (
+                        SELECT 1
+                        FROM wallet w
+                        WHERE w.id = u.id AND w.user_id IS NOT DISTINCT FROM $4
+                    )
+                    ORDER BY u.id
+                ) AS "wallet_ids!",
+                ARRAY(
+                    SELECT DISTINCT u.id
+                    FROM UNNEST($3::INT[]) AS u(id)
+                    WHERE NOT EXISTS (SELECT 1 FROM tag t WHERE t.id = u.id)
+                    ORDER BY u.id
+                ) AS "tag_ids!"
+            "#,
+            &category_ids,
+            &wallet_ids,
+            &tag_ids,
+            user_id,
+        )
+        .fetch_one(&*self.pool)
+        .timed("expense.find_unknown_references")
+        .await?;
 
-        // Array of tuples to hold the values for the expense_tag table.
-        // The order of the tuple is (expense_id, tag_id).
-        let mut expense_tag_values = Vec::<(i32, i32)>::new();
+        Ok(unknown_references)
+    }
 
-        for i in 0..expenses.len() {
-            let expense_tag_ids = &expenses[i].tag_ids;
-            let expense_id = expense_inserted_ids[i];
+    async fn insert_bulk(
+        &self,
+        expenses: &[SaveExpense],
+        user_id: Option<&str>,
+    ) -> Result<BulkInsertOutcome, sqlx::Error> {
+        async {
+            if expenses.is_empty() {
+                return Ok(BulkInsertOutcome {
+                    ids: vec![],
+                    skipped: 0,
+                });
+            }
+
+            let mut expense_query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO expense (amount, date, description, category_id, wallet_id, priority, is_refund, external_id, created_by, user_id) ",
+            );
+
+            expense_query.push_values(expenses, |mut builder, expense| {
+                builder
+                    .push_bind(expense.amount)
+                    .push_bind(expense.date)
+                    .push_bind(expense.description.clone())
+                    .push_bind(expense.category_id)
+                    .push_bind(expense.wallet_id)
+                    .push_bind(expense.priority)
+                    .push_bind(expense.is_refund)
+                    .push_bind(expense.external_id.clone())
+                    .push_bind(user_id)
+                    .push_bind(user_id);
+            });
+            expense_query.push(
+                " ON CONFLICT (user_id, external_id) WHERE external_id IS NOT NULL DO NOTHING RETURNING id, external_id",
+            );
+
+            let mut tx = self.pool.begin().await?;
+
+            let inserted_rows = expense_query
+                .build()
+                .fetch_all(&mut *tx)
+                .await?
+                .iter()
+                .map(|row| (row.try_get(0).unwrap(), row.try_get(1).unwrap()))
+                .collect::<Vec<(i32, Option<String>)>>();
+
+            drop(expense_query);
+
+            let inserted_ids = pair_inserted_rows(
+                expenses.iter().map(|expense| expense.external_id.as_deref()),
+                inserted_rows,
+            );
+            let inserted_expenses = expenses
+                .iter()
+                .zip(inserted_ids)
+                .filter_map(|(expense, expense_id)| Some((expense, expense_id?)))
+                .collect::<Vec<(&SaveExpense, i32)>>();
+
+            let outcome = BulkInsertOutcome {
+                ids: inserted_expenses.iter().map(|&(_, id)| id).collect(),
+                skipped: expenses.len() - inserted_expenses.len(),
+            };
 
-            for tag_id in expense_tag_ids {
-                expense_tag_values.push((expense_id, *tag_id));
+            // Array of tuples to hold the values for the expense_tag table.
+            // The order of the tuple is (expense_id, tag_id).
+            let mut expense_tag_values = Vec::<(i32, i32)>::new();
+
+            for (expense, expense_id) in inserted_expenses {
+                let expense_tag_ids = expense.tag_ids.as_deref().unwrap_or_default();
+
+                for tag_id in expense_tag_ids {
+                    expense_tag_values.push((expense_id, *tag_id));
+                }
+            }
+
+            if expense_tag_values.is_empty() {
+                tx.commit().await?;
+                return Ok(outcome);
             }
+
+            let mut expense_tag_query =
+                QueryBuilder::<Postgres>::new("INSERT INTO expense_tag (expense_id, tag_id) ");
+
+            expense_tag_query.push_values(expense_tag_values, |mut builder, (expense_id, tag_id)| {
+                builder.push_bind(expense_id).push_bind(tag_id);
+            });
+
+            expense_tag_query.build().execute(&mut *tx).await?;
+
+            tx.commit().await?;
+
+            Ok(outcome)
         }
+        .timed("expense.insert_bulk")
+        .await
+    }
+
+    fn stream_all(
+        &self,
+        query: &IndexExpenseQuery,
+        user_id: Option<&str>,
+    ) -> BoxStream<'static, Result<IndexExpenseElement, sqlx::Error>> {
+        let pool = Arc::clone(&self.pool);
+        let user_id = user_id.map(str::to_string);
+        let start_date = query.start_date;
+        let end_date = query.end_date;
+        let offset = query.pagination.offset();
+        let tag_ids = query.tag_ids.clone();
+        let match_all_tags = query.match_all_tags();
+        let only_untagged = query.only_untagged();
+        let exact_description = query.exact_description().map(str::to_string);
+        let contained_description = query.contained_description().map(escape_like);
+
+        Box::pin(try_stream! {
+            let started_at = Instant::now();
+            let mut rows = query!(
+                r#"
+                SELECT
+                    id,
+                    amount,
+                    TO_CHAR(date, 'YYYY-MM-DD') AS "date!",
+                    description
+                FROM
+                    expense
+                WHERE
+                    ($1::DATE IS NULL OR date >= $1::DATE)
+                    AND ($2::DATE IS NULL OR date <= $2::DATE)
+                    AND user_id IS NOT DISTINCT FROM $6
+                    AND (
+                        $4::INT[] IS NULL
+                        OR id IN (
+                            SELECT expense_id
+                            FROM expense_tag
+                            WHERE tag_id = ANY($4::INT[])
+                            GROUP BY expense_id
+                            HAVING
+                                NOT $5::BOOLEAN
+                                OR COUNT(DISTINCT tag_id) = (
+                                    SELECT COUNT(DISTINCT filtered.tag_id)
+                                    FROM UNNEST($4::INT[]) AS filtered(tag_id)
+                                )
+                        )
+                    )
+                    AND (
+                        NOT $7::BOOLEAN
+                        OR NOT EXISTS (SELECT 1 FROM expense_tag et WHERE et.expense_id = expense.id)
+                    )
+                    AND ($8::TEXT IS NULL OR description = $8)
+                    AND ($9::TEXT IS NULL OR description ILIKE '%' || $9 || '%')
+                ORDER BY id
+                OFFSET $3
+                "#,
+                start_date,
+                end_date,
+                offset,
+                tag_ids.as_deref(),
+                match_all_tags,
+                user_id.as_deref(),
+                only_untagged,
+                exact_description.as_deref(),
+                contained_description.as_deref(),
+            )
+            .fetch(&*pool);
+
+            while let Some(row) = rows.try_next().await? {
+                let expense = IndexExpenseElement {
+                    id: row.id,
+                    amount: row.amount,
+                    date: row.date,
+                    description: row.description,
+                };
+
+                yield expense;
+            }
+
+            log_elapsed("expense.stream_all", started_at);
+        })
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        expense: &SaveExpense,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        async {
+            let mut tx = self.pool.begin().await?;
+
+            let rows_affected = query!(
+                r#"
+                UPDATE expense
+                SET amount = $1,
+                    date = $2,
+                    description = $3,
+                    category_id = $4,
+                    wallet_id = $5,
+                    priority = $6,
+                    is_refund = $7,
+                    created_by = COALESCE(created_by, $8)
+                WHERE id = $9 AND user_id IS NOT DISTINCT FROM $8
+                "#,
+                expense.amount,
+                expense.date,
+                expense.description.clone(),
+                expense.category_id,
+                expense.wallet_id,
+                expense.priority,
+                expense.is_refund,
+                user_id,
+                id
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+            if rows_affected == 0 {
+                tx.rollback().await?;
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            // Tags are only replaced when the caller explicitly provides them.
+            let Some(tag_ids) = &expense.tag_ids else {
+                tx.commit().await?;
+                return Ok(());
+            };
+
+            query!("DELETE FROM expense_tag WHERE expense_id = $1", id)
+                .execute(&mut *tx)
+                .await?;
+
+            if tag_ids.is_empty() {
+                tx.commit().await?;
+                return Ok(());
+            }
+
+            let mut expense_tag_query =
+                QueryBuilder::<Postgres>::new("INSERT INTO expense_tag (expense_id, tag_id) ");
+            expense_tag_query.push_values(tag_ids, |mut builder, tag_id| {
+                builder.push_bind(id).push_bind(*tag_id);
+            });
+
+            expense_tag_query.build().execute(&mut *tx).await?;
 
-        if expense_tag_values.is_empty() {
             tx.commit().await?;
-            return Ok(());
+            Ok(())
         }
+        .timed("expense.update")
+        .await
+    }
+}
 
-        let mut expense_tag_query =
-            QueryBuilder::<Postgres>::new("INSERT INTO expense_tag (expense_id, tag_id) ");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
 
-        expense_tag_query.push_values(expense_tag_values, |mut builder, (expense_id, tag_id)| {
-            builder.push_bind(expense_id).push_bind(tag_id);
-        });
+    #[test]
+    fn test_pair_inserted_rows_skipped_in_the_middle() {
+        let rows = vec![(1, Some("a".to_string())), (3, Some("c".to_string()))];
 
-        expense_tag_query.build().execute(&mut *tx).await?;
+        let ids = pair_inserted_rows([Some("a"), Some("b"), Some("c")], rows);
 
-        tx.commit().await?;
+        assert_eq!(ids, vec![Some(1), None, Some(3)]);
+    }
 
-        Ok(())
+    #[test]
+    fn test_pair_inserted_rows_skipped_at_the_end() {
+        let rows = vec![(1, Some("a".to_string())), (2, Some("b".to_string()))];
+
+        let ids = pair_inserted_rows([Some("a"), Some("b"), Some("c"), Some("d")], rows);
+
+        assert_eq!(ids, vec![Some(1), Some(2), None, None]);
     }
 
-    async fn update(&self, id: i32, expense: &SaveExpense) -> Result<(), sqlx::Error> {
-        let mut tx = self.pool.begin().await?;
+    #[test]
+    fn test_pair_inserted_rows_without_external_ids() {
+        let rows = vec![(1, None), (3, None), (4, Some("b".to_string()))];
 
-        let rows_affected = query!(
+        let ids = pair_inserted_rows([None, Some("a"), None, Some("b")], rows);
+
+        assert_eq!(ids, vec![Some(1), None, Some(3), Some(4)]);
+    }
+
+    /// Builds the query of the expense listing from its query string parameters.
+    fn index_query(params: serde_json::Value) -> IndexExpenseQuery {
+        serde_json::from_str(&params.to_string()).unwrap()
+    }
+
+    /// Saves an expense tagged with both tags of the base fixture, another one tagged with the first tag only,
+    /// and a last one without any tag. Returns the IDs of the expenses in that order.
+    async fn insert_tagged_expenses(pool: &PgPool) -> Vec<i32> {
+        let ids = query_scalar!(
             r#"
-            UPDATE expense
-            SET amount = $1,
-                date = $2,
-                description = $3,
-                category_id = $4,
-                wallet_id = $5,
-                priority = $6
-            WHERE id = $7
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            VALUES (1, 1, 1000, '2025-04-01', 0), (1, 1, 2000, '2025-04-02', 0), (1, 1, 3000, '2025-04-03', 0)
+            RETURNING id
+            "#
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+
+        query!(
+            "INSERT INTO expense_tag (expense_id, tag_id) VALUES ($1, 1), ($1, 2), ($2, 1)",
+            ids[0],
+            ids[1],
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        ids
+    }
+
+    /// Saves an expense in the first category and wallet, and another one in the second category and wallet,
+    /// before removing the second category and wallet out from under it. Returns the IDs of both expenses.
+    async fn insert_orphaned_expense(pool: &PgPool) -> (i32, i32) {
+        let ids = query_scalar!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            VALUES (1, 1, 1000, '2025-04-01', 0), (2, 2, 2000, '2025-04-02', 0)
+            RETURNING id
+            "#
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+
+        sqlx::raw_sql(
+            r#"
+            ALTER TABLE expense DROP CONSTRAINT expense_category_id_fkey;
+            ALTER TABLE expense DROP CONSTRAINT expense_wallet_id_fkey;
+            DELETE FROM category WHERE id = 2;
+            DELETE FROM wallet WHERE id = 2;
             "#,
-            expense.amount,
-            expense.date,
-            expense.description.clone(),
-            expense.category_id,
-            expense.wallet_id,
-            expense.priority,
-            id
         )
-        .execute(&mut *tx)
-        .await?
-        .rows_affected();
+        .execute(pool)
+        .await
+        .unwrap();
 
-        if rows_affected == 0 {
-            tx.rollback().await?;
-            return Err(sqlx::Error::RowNotFound);
-        }
+        (ids[0], ids[1])
+    }
 
-        query!("DELETE FROM expense_tag WHERE expense_id = $1", id)
-            .execute(&mut *tx)
-            .await?;
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_stream_all_ignores_pagination_limit(pool: PgPool) {
+        // Prepare
+        query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            SELECT 1, 1, 1000, '2025-01-01', 0
+            FROM GENERATE_SERIES(1, 150)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
 
-        if expense.tag_ids.is_empty() {
-            tx.commit().await?;
-            return Ok(());
-        }
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let expenses = repository
+            .stream_all(&index_query(serde_json::json!({})), None)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
 
-        let mut expense_tag_query =
-            QueryBuilder::<Postgres>::new("INSERT INTO expense_tag (expense_id, tag_id) ");
-        expense_tag_query.push_values(&expense.tag_ids, |mut builder, tag_id| {
-            builder.push_bind(id).push_bind(*tag_id);
-        });
+        // Assert
+        assert_eq!(expenses.len(), 150);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_bulk_accepts_priority_above_default_max(pool: PgPool) {
+        // Prepare
+        let expense = serde_json::from_str::<SaveExpense>(
+            r#"{
+                "amount": 1000,
+                "date": "2025-04-01",
+                "priority": 2,
+                "categoryId": 1,
+                "walletId": 1
+            }"#,
+        )
+        .unwrap();
+        let expense = SaveExpense {
+            priority: 3,
+            ..expense
+        };
 
-        expense_tag_query.build().execute(&mut *tx).await?;
+        let repository = Repository::new(Arc::new(pool.clone()));
 
-        tx.commit().await?;
-        Ok(())
+        // Execute
+        let outcome = repository.insert_bulk(&[expense], None).await.unwrap();
+
+        // Assert
+        let priority = query_scalar!("SELECT priority FROM expense WHERE id = $1", outcome.ids[0])
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(priority, 3);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_all_with_all_tags_excludes_partially_tagged(pool: PgPool) {
+        // Prepare
+        let ids = insert_tagged_expenses(&pool).await;
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let page = repository
+            .find_all(
+                &index_query(serde_json::json!({ "tagIds": "1,2", "tagMatch": "all" })),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        let expense_ids = page
+            .items
+            .iter()
+            .map(|expense| expense.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(expense_ids, vec![ids[0]]);
+        assert_eq!(page.total, 1);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_all_untagged_excludes_tagged(pool: PgPool) {
+        // Prepare
+        let ids = insert_tagged_expenses(&pool).await;
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let page = repository
+            .find_all(
+                &index_query(serde_json::json!({ "untagged": "true" })),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Assert
+        let expense_ids = page
+            .items
+            .iter()
+            .map(|expense| expense.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(expense_ids, vec![ids[2]]);
+        assert_eq!(page.total, 1);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_all_has_more_only_past_the_limit(pool: PgPool) {
+        // Prepare
+        let insert_expenses = |count: i32| {
+            query!(
+                r#"
+                INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+                SELECT 1, 1, 1000, '2025-04-01', 0 FROM GENERATE_SERIES(1, $1)
+                "#,
+                count,
+            )
+            .execute(&pool)
+        };
+        insert_expenses(3).await.unwrap();
+        let repository = Repository::new(Arc::new(pool.clone()));
+        let query = index_query(serde_json::json!({ "limit": "3" }));
+
+        // Execute
+        let full_page = repository.find_all(&query, None).await.unwrap();
+        insert_expenses(1).await.unwrap();
+        let overflowing_page = repository.find_all(&query, None).await.unwrap();
+
+        // Assert
+        assert_eq!(full_page.items.len(), 3);
+        assert!(!full_page.has_more);
+        assert_eq!(full_page.total, 3);
+        assert_eq!(overflowing_page.items.len(), 3);
+        assert!(overflowing_page.has_more);
+        assert_eq!(overflowing_page.total, 4);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_recent_keeps_orphaned_expenses(pool: PgPool) {
+        // Prepare
+        let (expense_id, orphaned_id) = insert_orphaned_expense(&pool).await;
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let expenses = repository.find_recent(10, None).await.unwrap();
+
+        // Assert
+        assert_eq!(expenses.len(), 2);
+        assert_eq!(expenses[0].id, orphaned_id);
+        assert!(expenses[0].category.is_none());
+        assert!(expenses[0].wallet.is_none());
+        assert_eq!(expenses[1].id, expense_id);
+        assert_eq!(expenses[1].category.as_ref().unwrap().name, "Groceries");
+        assert_eq!(expenses[1].wallet.as_ref().unwrap().name, "Cash");
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_many_by_ids_keeps_orphaned_expenses(pool: PgPool) {
+        // Prepare
+        let (expense_id, orphaned_id) = insert_orphaned_expense(&pool).await;
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let expenses = repository
+            .find_many_by_ids(&[expense_id, orphaned_id], None)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(expenses.len(), 2);
+        assert_eq!(expenses[0].category.as_ref().unwrap().name, "Groceries");
+        assert_eq!(expenses[0].wallet.as_ref().unwrap().name, "Cash");
+        assert_eq!(expenses[1].id, orphaned_id);
+        assert!(expenses[1].category.is_none());
+        assert!(expenses[1].wallet.is_none());
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_used_categories_sums_beyond_i32(pool: PgPool) {
+        // Prepare
+        query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            VALUES (1, 1, 2000000000, '2025-04-01', 0), (1, 1, 2000000000, '2025-04-02', 0)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let used_categories = repository
+            .find_used_categories(None, None, None)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(used_categories.len(), 1);
+        assert_eq!(used_categories[0].name, "Groceries");
+        assert_eq!(used_categories[0].amount, 4_000_000_000);
     }
 }
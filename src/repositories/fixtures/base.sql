@@ -0,0 +1,8 @@
+-- The supporting records every repository test relies on, with IDs starting from 1.
+INSERT INTO parent_category (name) VALUES ('Food');
+
+INSERT INTO category (parent_category_id, name) VALUES (1, 'Groceries'), (1, 'Restaurant');
+
+INSERT INTO wallet (name) VALUES ('Cash'), ('Bank Account');
+
+INSERT INTO tag (name, is_important) VALUES ('Trip', true), ('Gift', false);
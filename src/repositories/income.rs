@@ -1,10 +1,20 @@
+use async_stream::try_stream;
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, QueryBuilder, query, query_as};
-use std::sync::Arc;
+use futures::{TryStreamExt, stream::BoxStream};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, query, query_as, query_scalar};
+use std::{sync::Arc, time::Instant};
+use time::Date;
 
-use crate::dtos::{
-    income::{IndexIncomeQuery, SaveIncome},
-    query_result::{IndexIncomeElement, ShowIncome, ShowLatestIncome, SimpleEntity},
+use crate::{
+    common::timing::{TimedExt, log_elapsed},
+    dtos::{
+        income::{IndexIncomeQuery, SaveIncome, UpdateIncome},
+        query_result::{
+            ExportIncomeElement, IncomeStats, IndexIncomeElement, ShowIncome, ShowLatestIncome,
+            SimpleEntity, Tag,
+        },
+    },
+    repositories::Page,
 };
 
 /// Repository to interact with the `income` table in the database.
@@ -18,66 +28,210 @@ impl Repository {
     pub fn new(pool: Arc<PgPool>) -> Self {
         Self { pool }
     }
+
+    /// Counts the incomes matching the filters of the query, regardless of its pagination.
+    async fn count(
+        &self,
+        query: &IndexIncomeQuery,
+        user_id: Option<&str>,
+    ) -> Result<i64, sqlx::Error> {
+        query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "total!"
+            FROM income
+            WHERE
+                ($1::DATE IS NULL OR date >= $1::DATE)
+                AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $3
+            "#,
+            query.start_date,
+            query.end_date,
+            user_id,
+        )
+        .fetch_one(&*self.pool)
+        .timed("income.count_all")
+        .await
+    }
 }
 
 /// Trait defining operations for the `income` table.
+/// Every operation is scoped to the incomes owned by the given user.
+/// Incomes without an owner are only visible when no user is given.
 #[async_trait]
 pub trait RepositoryOperation: Send + Sync {
     /// Deletes an income from the database.
-    async fn delete(&self, id: i32) -> Result<(), sqlx::Error>;
-    /// Finds all incomes from the database.
+    async fn delete(&self, id: i32, user_id: Option<&str>) -> Result<(), sqlx::Error>;
+    /// Deletes the incomes with the given IDs from the database, returning the number of deleted rows.
+    async fn delete_bulk(&self, ids: &[i32], user_id: Option<&str>) -> Result<u64, sqlx::Error>;
+    /// Finds the distinct dates with at least one income within the optional date range, in ascending order.
+    /// The dates are formatted as `YYYY-MM-DD`.
+    async fn find_active_dates(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<String>, sqlx::Error>;
+    /// Finds a page of the incomes matching the query from the database.
+    /// Count-only queries skip fetching the incomes and count them instead.
     async fn find_all(
         &self,
         query: &IndexIncomeQuery,
-    ) -> Result<Vec<IndexIncomeElement>, sqlx::Error>;
-    /// Finds the latest income from the database.
-    async fn find_latest(&self) -> Result<ShowLatestIncome, sqlx::Error>;
+        user_id: Option<&str>,
+    ) -> Result<Page<IndexIncomeElement>, sqlx::Error>;
+    /// Finds the most recent incomes from the database, newest first.
+    /// The wallet is left empty, rather than hiding the income, if it no longer exists.
+    async fn find_recent(
+        &self,
+        limit: i64,
+        user_id: Option<&str>,
+    ) -> Result<Vec<ShowLatestIncome>, sqlx::Error>;
     /// Finds a specific income by ID from the database.
-    async fn find_one(&self, id: i32) -> Result<ShowIncome, sqlx::Error>;
-    /// Inserts multiple incomes into the database.
-    async fn insert_bulk(&self, incomes: Vec<SaveIncome>) -> Result<(), sqlx::Error>;
+    /// The wallet is left empty, rather than hiding the income, if it no longer exists.
+    async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<ShowIncome, sqlx::Error>;
+    /// Aggregates the count, sum, average, minimum, and maximum of the incomes within the optional date range.
+    async fn find_stats(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<IncomeStats, sqlx::Error>;
+    /// Finds the given tag IDs that don't exist in the database, in ascending order.
+    async fn find_unknown_tag_ids(&self, tag_ids: &[i32]) -> Result<Vec<i32>, sqlx::Error>;
+    /// Finds the given wallet IDs that don't exist in the database, in ascending order.
+    /// Wallets owned by another user are treated as nonexistent.
+    async fn find_unknown_wallet_ids(
+        &self,
+        wallet_ids: &[i32],
+        user_id: Option<&str>,
+    ) -> Result<Vec<i32>, sqlx::Error>;
+    /// Streams the incomes matching the query along with their wallet name.
+    /// Rows are yielded as they arrive from the database instead of being buffered.
+    fn stream_export(
+        &self,
+        query: &IndexIncomeQuery,
+        user_id: Option<&str>,
+    ) -> BoxStream<'static, Result<ExportIncomeElement, sqlx::Error>>;
+    /// Inserts multiple incomes along with their tags into the database.
+    /// The incomes are owned by, and recorded as created by, the given user, if any.
+    async fn insert_bulk(
+        &self,
+        incomes: Vec<SaveIncome>,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
+    /// Updates only the supplied fields of an existing income in the database.
+    async fn patch(
+        &self,
+        id: i32,
+        income: &UpdateIncome,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
     /// Updates an existing income in the database.
-    async fn update(&self, id: i32, income: &SaveIncome) -> Result<(), sqlx::Error>;
+    /// The tags of the income are only replaced when `tag_ids` is provided.
+    /// The creator is only recorded if the income doesn't have one yet.
+    async fn update(
+        &self,
+        id: i32,
+        income: &SaveIncome,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
 }
 
 #[async_trait]
 impl RepositoryOperation for Repository {
+    async fn find_active_dates(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let active_dates = query_scalar!(
+            r#"
+            SELECT DISTINCT TO_CHAR(date, 'YYYY-MM-DD') AS "date!"
+            FROM income
+            WHERE
+                ($1::DATE IS NULL OR date >= $1::DATE)
+                AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $3
+            ORDER BY 1
+            "#,
+            start_date,
+            end_date,
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("income.find_active_dates")
+        .await?;
+
+        Ok(active_dates)
+    }
+
     async fn find_all(
         &self,
         query: &IndexIncomeQuery,
-    ) -> Result<Vec<IndexIncomeElement>, sqlx::Error> {
-        let incomes = query_as!(
-            IndexIncomeElement,
+        user_id: Option<&str>,
+    ) -> Result<Page<IndexIncomeElement>, sqlx::Error> {
+        if query.pagination.is_count_only() {
+            let total = self.count(query, user_id).await?;
+
+            return Ok(Page::count_only(total, query.pagination.offset()));
+        }
+
+        let limit = query.pagination.limit();
+        let rows = query!(
             r#"
             SELECT
                 id,
                 amount,
                 TO_CHAR(date, 'YYYY-MM-DD') AS "date!",
-                description
+                description,
+                COUNT(*) OVER () AS "total!"
             FROM
                 income
             WHERE
                 ($1::DATE IS NULL OR date >= $1::DATE)
                 AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $5
             ORDER BY id
             LIMIT $3 OFFSET $4
             "#,
             query.start_date,
             query.end_date,
-            query.pagination.limit(),
+            limit + 1,
             query.pagination.offset(),
+            user_id,
         )
         .fetch_all(&*self.pool)
+        .timed("income.find_all")
         .await?;
 
-        Ok(incomes)
+        // The window count is only known when a row is returned, i.e. not past the last page.
+        let total = match rows.first() {
+            Some(row) => row.total,
+            None => self.count(query, user_id).await?,
+        };
+        let incomes = rows
+            .into_iter()
+            .map(|row| IndexIncomeElement {
+                id: row.id,
+                amount: row.amount,
+                date: row.date,
+                description: row.description,
+            })
+            .collect();
+
+        Ok(Page::from_rows(incomes, limit, total))
     }
 
-    async fn delete(&self, id: i32) -> Result<(), sqlx::Error> {
-        let rows_affected = query!("DELETE FROM income WHERE id = $1", id)
-            .execute(&*self.pool)
-            .await?
-            .rows_affected();
+    async fn delete(&self, id: i32, user_id: Option<&str>) -> Result<(), sqlx::Error> {
+        let rows_affected = query!(
+            "DELETE FROM income WHERE id = $1 AND user_id IS NOT DISTINCT FROM $2",
+            id,
+            user_id,
+        )
+        .execute(&*self.pool)
+        .timed("income.delete")
+        .await?
+        .rows_affected();
 
         if rows_affected == 0 {
             return Err(sqlx::Error::RowNotFound);
@@ -86,8 +240,26 @@ impl RepositoryOperation for Repository {
         Ok(())
     }
 
-    async fn find_latest(&self) -> Result<ShowLatestIncome, sqlx::Error> {
-        let latest_income = query_as!(
+    async fn delete_bulk(&self, ids: &[i32], user_id: Option<&str>) -> Result<u64, sqlx::Error> {
+        let rows_affected = query!(
+            "DELETE FROM income WHERE id = ANY($1) AND user_id IS NOT DISTINCT FROM $2",
+            ids,
+            user_id,
+        )
+        .execute(&*self.pool)
+        .timed("income.delete_bulk")
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected)
+    }
+
+    async fn find_recent(
+        &self,
+        limit: i64,
+        user_id: Option<&str>,
+    ) -> Result<Vec<ShowLatestIncome>, sqlx::Error> {
+        let recent_incomes = query_as!(
             ShowLatestIncome,
             r#"
             SELECT
@@ -95,25 +267,47 @@ impl RepositoryOperation for Repository {
                 i.amount,
                 TO_CHAR(i.date, 'YYYY-MM-DD') AS "date!",
                 i.description,
-                JSONB_BUILD_OBJECT(
-                    'id', w.id,
-                    'name', w.name
-                ) AS "wallet!: sqlx::types::Json<SimpleEntity>"
+                CASE WHEN w.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', w.id,
+                        'name', w.name
+                    )
+                END AS "wallet: sqlx::types::Json<SimpleEntity>",
+                COALESCE(
+                    JSONB_AGG(
+                        JSONB_BUILD_OBJECT(
+                            'id', t.id,
+                            'name', t.name,
+                            'is_important', t.is_important
+                        ) ORDER BY (CASE WHEN t.is_important IS true THEN 0 ELSE 1 END), t.name
+                    ) FILTER (WHERE t.id IS NOT NULL),
+                    '[]'
+                ) AS "tags!: sqlx::types::Json<Vec<Tag>>"
             FROM
                 income i
-            JOIN
+            LEFT JOIN
                 wallet w ON i.wallet_id = w.id
+            LEFT JOIN
+                income_tag it ON i.id = it.income_id
+            LEFT JOIN
+                tag t ON it.tag_id = t.id
+            WHERE i.user_id IS NOT DISTINCT FROM $2
+            GROUP BY
+                i.id, w.id
             ORDER BY id DESC
-            LIMIT 1
-            "#
+            LIMIT $1
+            "#,
+            limit,
+            user_id,
         )
-        .fetch_one(&*self.pool)
+        .fetch_all(&*self.pool)
+        .timed("income.find_recent")
         .await?;
 
-        Ok(latest_income)
+        Ok(recent_incomes)
     }
 
-    async fn find_one(&self, id: i32) -> Result<ShowIncome, sqlx::Error> {
+    async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<ShowIncome, sqlx::Error> {
         let income = query_as!(
             ShowIncome,
             r#"
@@ -121,63 +315,283 @@ impl RepositoryOperation for Repository {
                 i.amount,
                 TO_CHAR(i.date, 'YYYY-MM-DD') AS "date!",
                 i.description,
-                JSONB_BUILD_OBJECT(
-                    'id', w.id,
-                    'name', w.name
-                ) AS "wallet!: sqlx::types::Json<SimpleEntity>"
+                CASE WHEN w.id IS NOT NULL THEN
+                    JSONB_BUILD_OBJECT(
+                        'id', w.id,
+                        'name', w.name
+                    )
+                END AS "wallet: sqlx::types::Json<SimpleEntity>",
+                COALESCE(
+                    JSONB_AGG(
+                        JSONB_BUILD_OBJECT(
+                            'id', t.id,
+                            'name', t.name,
+                            'is_important', t.is_important
+                        ) ORDER BY (CASE WHEN t.is_important IS true THEN 0 ELSE 1 END), t.name
+                    ) FILTER (WHERE t.id IS NOT NULL),
+                    '[]'
+                ) AS "tags!: sqlx::types::Json<Vec<Tag>>"
             FROM
                 income i
-            JOIN
+            LEFT JOIN
                 wallet w ON i.wallet_id = w.id
-            WHERE i.id = $1
+            LEFT JOIN
+                income_tag it ON i.id = it.income_id
+            LEFT JOIN
+                tag t ON it.tag_id = t.id
+            WHERE i.id = $1 AND i.user_id IS NOT DISTINCT FROM $2
+            GROUP BY
+                i.id, w.id
             "#,
             id,
+            user_id,
         )
         .fetch_one(&*self.pool)
+        .timed("income.find_one")
         .await?;
 
         Ok(income)
     }
 
-    async fn insert_bulk(&self, incomes: Vec<SaveIncome>) -> Result<(), sqlx::Error> {
-        let mut income_query = QueryBuilder::<Postgres>::new(
-            "INSERT INTO income (amount, date, description, wallet_id) ",
-        );
+    async fn find_stats(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<IncomeStats, sqlx::Error> {
+        let stats = query_as!(
+            IncomeStats,
+            r#"
+            SELECT
+                COUNT(*) AS "count!",
+                COALESCE(SUM(amount), 0)::BIGINT AS "sum!",
+                ROUND(AVG(amount))::BIGINT AS average,
+                MIN(amount) AS min,
+                MAX(amount) AS max
+            FROM income
+            WHERE
+                ($1::DATE IS NULL OR date >= $1::DATE)
+                AND ($2::DATE IS NULL OR date <= $2::DATE)
+                AND user_id IS NOT DISTINCT FROM $3
+            "#,
+            start_date,
+            end_date,
+            user_id,
+        )
+        .fetch_one(&*self.pool)
+        .timed("income.find_stats")
+        .await?;
 
-        income_query.push_values(&incomes, |mut builder, income| {
-            builder
-                .push_bind(income.amount)
-                .push_bind(income.date)
-                .push_bind(income.description.clone())
-                .push_bind(income.wallet_id);
-        });
+        Ok(stats)
+    }
 
-        let mut tx = self.pool.begin().await?;
-        income_query.build().execute(&mut *tx).await?;
-        tx.commit().await?;
+    async fn find_unknown_tag_ids(&self, tag_ids: &[i32]) -> Result<Vec<i32>, sqlx::Error> {
+        let unknown_tag_ids = query_scalar!(
+            r#"
+            SELECT DISTINCT u.id AS "id!"
+            FROM UNNEST($1::INT[]) AS u(id)
+            WHERE NOT EXISTS (SELECT 1 FROM tag t WHERE t.id = u.id)
+            ORDER BY u.id
+            "#,
+            tag_ids,
+        )
+        .fetch_all(&*self.pool)
+        .timed("income.find_unknown_tag_ids")
+        .await?;
 
-        Ok(())
+        Ok(unknown_tag_ids)
     }
 
-    async fn update(&self, id: i32, income: &SaveIncome) -> Result<(), sqlx::Error> {
-        let rows_affected = query!(
+    async fn find_unknown_wallet_ids(
+        &self,
+        wallet_ids: &[i32],
+        user_id: Option<&str>,
+    ) -> Result<Vec<i32>, sqlx::Error> {
+        let unknown_wallet_ids = query!(
             r#"
-            UPDATE income
-            SET amount = $1,
-                date = $2,
-                description = $3,
-                wallet_id = $4
-            WHERE id = $5
+            SELECT DISTINCT u.id AS "id!"
+            FROM UNNEST($1::INT[]) AS u(id)
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM wallet w
+                WHERE w.id = u.id AND w.user_id IS NOT DISTINCT FROM $2
+            )
+            ORDER BY u.id
             "#,
-            income.amount,
-            income.date,
-            income.description.clone(),
-            income.wallet_id,
-            id
+            wallet_ids,
+            user_id,
         )
-        .execute(&*self.pool)
+        .fetch_all(&*self.pool)
+        .timed("income.find_unknown_wallet_ids")
         .await?
-        .rows_affected();
+        .into_iter()
+        .map(|row| row.id)
+        .collect();
+
+        Ok(unknown_wallet_ids)
+    }
+
+    fn stream_export(
+        &self,
+        query: &IndexIncomeQuery,
+        user_id: Option<&str>,
+    ) -> BoxStream<'static, Result<ExportIncomeElement, sqlx::Error>> {
+        let pool = Arc::clone(&self.pool);
+        let user_id = user_id.map(str::to_string);
+        let start_date = query.start_date;
+        let end_date = query.end_date;
+        let limit = query.pagination.limit();
+        let offset = query.pagination.offset();
+
+        Box::pin(try_stream! {
+            let started_at = Instant::now();
+            let mut rows = query_as!(
+                ExportIncomeElement,
+                r#"
+                SELECT
+                    i.id,
+                    i.amount,
+                    TO_CHAR(i.date, 'YYYY-MM-DD') AS "date!",
+                    i.description,
+                    w.name AS "wallet_name?"
+                FROM
+                    income i
+                LEFT JOIN
+                    wallet w ON i.wallet_id = w.id
+                WHERE
+                    ($1::DATE IS NULL OR i.date >= $1::DATE)
+                    AND ($2::DATE IS NULL OR i.date <= $2::DATE)
+                    AND i.user_id IS NOT DISTINCT FROM $5
+                ORDER BY i.id
+                LIMIT $3 OFFSET $4
+                "#,
+                start_date,
+                end_date,
+                limit,
+                offset,
+                user_id.as_deref(),
+            )
+            .fetch(&*pool);
+
+            while let Some(income) = rows.try_next().await? {
+                yield income;
+            }
+
+            log_elapsed("income.stream_export", started_at);
+        })
+    }
+
+    async fn insert_bulk(
+        &self,
+        incomes: Vec<SaveIncome>,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        async {
+            if incomes.is_empty() {
+                return Ok(());
+            }
+
+            let mut income_query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO income (amount, date, description, wallet_id, created_by, user_id) ",
+            );
+
+            income_query.push_values(&incomes, |mut builder, income| {
+                builder
+                    .push_bind(income.amount)
+                    .push_bind(income.date)
+                    .push_bind(income.description.clone())
+                    .push_bind(income.wallet_id)
+                    .push_bind(user_id)
+                    .push_bind(user_id);
+            });
+            income_query.push(" RETURNING id");
+
+            let mut tx = self.pool.begin().await?;
+
+            let income_inserted_ids = income_query
+                .build()
+                .fetch_all(&mut *tx)
+                .await?
+                .iter()
+                .map(|row| row.try_get(0).unwrap())
+                .collect::<Vec<i32>>();
+
+            drop(income_query);
+
+            // Array of tuples to hold the values for the income_tag table.
+            // The order of the tuple is (income_id, tag_id).
+            let income_tag_values = incomes
+                .iter()
+                .zip(&income_inserted_ids)
+                .flat_map(|(income, &income_id)| {
+                    income
+                        .tag_ids
+                        .iter()
+                        .flatten()
+                        .map(move |&tag_id| (income_id, tag_id))
+                })
+                .collect::<Vec<(i32, i32)>>();
+
+            if income_tag_values.is_empty() {
+                tx.commit().await?;
+                return Ok(());
+            }
+
+            let mut income_tag_query =
+                QueryBuilder::<Postgres>::new("INSERT INTO income_tag (income_id, tag_id) ");
+
+            income_tag_query.push_values(income_tag_values, |mut builder, (income_id, tag_id)| {
+                builder.push_bind(income_id).push_bind(tag_id);
+            });
+
+            income_tag_query.build().execute(&mut *tx).await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        }
+        .timed("income.insert_bulk")
+        .await
+    }
+
+    async fn patch(
+        &self,
+        id: i32,
+        income: &UpdateIncome,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let mut patch_query = QueryBuilder::<Postgres>::new("UPDATE income SET ");
+        let mut assignments = patch_query.separated(", ");
+
+        if let Some(amount) = income.amount {
+            assignments.push("amount = ").push_bind_unseparated(amount);
+        }
+        if let Some(date) = income.date {
+            assignments.push("date = ").push_bind_unseparated(date);
+        }
+        if let Some(description) = &income.description {
+            assignments
+                .push("description = ")
+                .push_bind_unseparated(description.clone());
+        }
+        if let Some(wallet_id) = income.wallet_id {
+            assignments
+                .push("wallet_id = ")
+                .push_bind_unseparated(wallet_id);
+        }
+
+        patch_query
+            .push(" WHERE id = ")
+            .push_bind(id)
+            .push(" AND user_id IS NOT DISTINCT FROM ")
+            .push_bind(user_id);
+
+        let rows_affected = patch_query
+            .build()
+            .execute(&*self.pool)
+            .timed("income.patch")
+            .await?
+            .rows_affected();
 
         if rows_affected == 0 {
             return Err(sqlx::Error::RowNotFound);
@@ -185,4 +599,121 @@ impl RepositoryOperation for Repository {
 
         Ok(())
     }
+
+    async fn update(
+        &self,
+        id: i32,
+        income: &SaveIncome,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        async {
+            let mut tx = self.pool.begin().await?;
+
+            let rows_affected = query!(
+                r#"
+                UPDATE income
+                SET amount = $1,
+                    date = $2,
+                    description = $3,
+                    wallet_id = $4,
+                    created_by = COALESCE(created_by, $5)
+                WHERE id = $6 AND user_id IS NOT DISTINCT FROM $5
+                "#,
+                income.amount,
+                income.date,
+                income.description.clone(),
+                income.wallet_id,
+                user_id,
+                id
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+            if rows_affected == 0 {
+                tx.rollback().await?;
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            // Tags are only replaced when the caller explicitly provides them.
+            let Some(tag_ids) = &income.tag_ids else {
+                tx.commit().await?;
+                return Ok(());
+            };
+
+            query!("DELETE FROM income_tag WHERE income_id = $1", id)
+                .execute(&mut *tx)
+                .await?;
+
+            if tag_ids.is_empty() {
+                tx.commit().await?;
+                return Ok(());
+            }
+
+            let mut income_tag_query =
+                QueryBuilder::<Postgres>::new("INSERT INTO income_tag (income_id, tag_id) ");
+            income_tag_query.push_values(tag_ids, |mut builder, tag_id| {
+                builder.push_bind(id).push_bind(*tag_id);
+            });
+
+            income_tag_query.build().execute(&mut *tx).await?;
+
+            tx.commit().await?;
+            Ok(())
+        }
+        .timed("income.update")
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_recent_keeps_orphaned_incomes(pool: PgPool) {
+        // Prepare
+        query!(
+            r#"
+            INSERT INTO income (wallet_id, amount, date)
+            VALUES (1, 1000, '2025-04-01'), (2, 2000, '2025-04-02')
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::raw_sql(
+            r#"
+            ALTER TABLE income DROP CONSTRAINT income_wallet_id_fkey;
+            DELETE FROM wallet WHERE id = 2;
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = Repository::new(Arc::new(pool));
+        let query: IndexIncomeQuery = serde_json::from_str("{}").unwrap();
+
+        // Execute
+        let incomes = repository.find_recent(10, None).await.unwrap();
+        let exported = repository
+            .stream_export(&query, None)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(incomes.len(), 2);
+        assert_eq!(incomes[0].amount, 2000);
+        assert!(incomes[0].wallet.is_none());
+        assert_eq!(incomes[1].amount, 1000);
+        assert_eq!(incomes[1].wallet.as_ref().unwrap().name, "Cash");
+
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0].wallet_name.as_deref(), Some("Cash"));
+        assert!(exported[1].wallet_name.is_none());
+    }
 }
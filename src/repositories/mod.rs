@@ -3,3 +3,84 @@ pub mod income;
 pub mod summary;
 pub mod util;
 pub mod wallet;
+
+/// A page of records, along with whether more records exist after it and how many records match the query.
+pub struct Page<T> {
+    /// The records of the page.
+    pub items: Vec<T>,
+    /// Whether more records exist after the page.
+    pub has_more: bool,
+    /// The number of records matching the query regardless of the pagination.
+    pub total: i64,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from the rows fetched with a limit of `limit + 1`.
+    /// The extra row only tells that more records exist, so it's trimmed from the page.
+    pub fn from_rows(mut rows: Vec<T>, limit: i64, total: i64) -> Self {
+        let limit = usize::try_from(limit).unwrap_or(0);
+        let has_more = rows.len() > limit;
+        rows.truncate(limit);
+
+        Self {
+            items: rows,
+            has_more,
+            total,
+        }
+    }
+
+    /// Builds the empty page of a count-only request, holding the number of records matching the query.
+    pub fn count_only(total: i64, offset: i64) -> Self {
+        Self {
+            items: Vec::new(),
+            has_more: total > offset,
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_from_rows_with_extra_row() {
+        let page = Page::from_rows(vec![1, 2, 3], 2, 3);
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert!(page.has_more);
+    }
+
+    #[test]
+    fn test_page_from_rows_at_exact_boundary() {
+        let page = Page::from_rows(vec![1, 2], 2, 2);
+
+        assert_eq!(page.items, vec![1, 2]);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_page_count_only() {
+        let page = Page::<i32>::count_only(5, 0);
+
+        assert!(page.items.is_empty());
+        assert!(page.has_more);
+        assert_eq!(page.total, 5);
+    }
+
+    #[test]
+    fn test_page_count_only_past_the_end() {
+        let page = Page::<i32>::count_only(5, 5);
+
+        assert!(!page.has_more);
+        assert_eq!(page.total, 5);
+    }
+
+    #[test]
+    fn test_page_from_rows_below_limit() {
+        let page = Page::from_rows(vec![1], 2, 1);
+
+        assert_eq!(page.items, vec![1]);
+        assert!(!page.has_more);
+    }
+}
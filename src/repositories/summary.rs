@@ -1,10 +1,20 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use time::Date;
 
-use crate::dtos::{
-    query_result::{ExpenseSummary, IncomeSummary, ShowSummary},
-    summary::GenerateSummaryRequest,
+use crate::{
+    common::timing::TimedExt,
+    dtos::{
+        query_result::{
+            ExpenseSummary, IncomeSummary, ShowSummary, TimeseriesTotal, TransferNetFlow,
+        },
+        summary::{GenerateSummaryRequest, GenerateTimeseriesRequest},
+    },
 };
 
 /// Repository responsible for handling the summary of income and expenses.
@@ -12,22 +22,95 @@ use crate::dtos::{
 pub struct SummaryRepository {
     /// The PostgreSQL connection pool.
     pool: Arc<PgPool>,
+    /// The categories of the expenses recorded for wallet transfer fees.
+    transfer_fee_category_ids: Vec<i32>,
+    /// The lowest priority level of an expense, up to which the priority levels are summarized.
+    max_priority: i32,
 }
 
 impl SummaryRepository {
     /// Creates a new `SummaryRepository` instance.
-    pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+    pub fn new(pool: Arc<PgPool>, transfer_fee_category_ids: Vec<i32>, max_priority: i32) -> Self {
+        Self {
+            pool,
+            transfer_fee_category_ids,
+            max_priority,
+        }
+    }
+}
+
+/// Summary repository decorator that keeps generated summaries in memory for a fixed TTL.
+/// Identical requests within the TTL are served from the cache without touching the database.
+pub struct CachedSummaryRepository {
+    /// The repository used on cache misses.
+    inner: Arc<dyn RepositoryOperation>,
+    /// How long a generated summary stays fresh.
+    ttl: Duration,
+    /// The cached summaries along with the instant they were generated.
+    entries: Mutex<HashMap<SummaryCacheKey, (Instant, ShowSummary)>>,
+}
+
+impl CachedSummaryRepository {
+    /// Creates a new `CachedSummaryRepository` instance wrapping the given repository.
+    pub fn new(inner: Arc<dyn RepositoryOperation>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// The normalized form of a `GenerateSummaryRequest` used as the cache key, along with the requesting user.
+/// The excluded category IDs are sorted and deduplicated so that equivalent requests share an entry.
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct SummaryCacheKey {
+    start_date: Date,
+    end_date: Date,
+    exclude_category_ids: Vec<i32>,
+    exclude_transfer_fees: bool,
+    parent_category_id: Option<i32>,
+    user_id: Option<String>,
+}
+
+impl SummaryCacheKey {
+    /// Creates the cache key of the given request made by the given user.
+    fn new(request: &GenerateSummaryRequest, user_id: Option<&str>) -> Self {
+        let mut exclude_category_ids = request.exclude_category_ids.clone();
+        exclude_category_ids.sort_unstable();
+        exclude_category_ids.dedup();
+
+        Self {
+            start_date: request.start_date,
+            end_date: request.end_date,
+            exclude_category_ids,
+            exclude_transfer_fees: request.exclude_transfer_fees,
+            parent_category_id: request.parent_category_id,
+            user_id: user_id.map(str::to_string),
+        }
     }
 }
 
+/// Trait defining the summary operations.
+/// Only the expenses and incomes owned by the given user are summarized.
+/// Records without an owner are only summarized when no user is given.
 #[async_trait]
 pub trait RepositoryOperation: Send + Sync {
     /// Generates a summary of income and expenses based on the provided request.
+    /// When a parent category is requested, only the expenses of its categories are summarized.
+    /// Every priority level up to the configured maximum is summarized, even without any expense.
     async fn generate_raw(
         &self,
         request: &GenerateSummaryRequest,
+        user_id: Option<&str>,
     ) -> Result<ShowSummary, sqlx::Error>;
+    /// Generates the total amount of income and expenses per bucket within the requested date range.
+    /// Only the buckets with at least one record are returned, in chronological order.
+    async fn generate_timeseries(
+        &self,
+        request: &GenerateTimeseriesRequest,
+        user_id: Option<&str>,
+    ) -> Result<Vec<TimeseriesTotal>, sqlx::Error>;
 }
 
 #[async_trait]
@@ -35,40 +118,88 @@ impl RepositoryOperation for SummaryRepository {
     async fn generate_raw(
         &self,
         request: &GenerateSummaryRequest,
+        user_id: Option<&str>,
     ) -> Result<ShowSummary, sqlx::Error> {
-        let query = sqlx::query_as!(
+        let mut summary = sqlx::query_as!(
             ShowSummary,
             r#"
             WITH filtered_expense AS (
-                SELECT e.amount, e.date,  e.category_id, e.priority
+                -- Refunds are negated so that they are subtracted from every total.
+                SELECT
+                    CASE WHEN e.is_refund THEN -e.amount ELSE e.amount END AS amount,
+                    e.date,
+                    e.category_id,
+                    e.priority
                 FROM expense e
                 JOIN category c ON e.category_id = c.id
                 WHERE
                     e.date BETWEEN $1::DATE AND $2::DATE
+                    AND e.user_id IS NOT DISTINCT FROM $6
                     AND e.category_id != ALL($3::INT[])
+                    AND ($7::INT IS NULL OR c.parent_category_id = $7)
+                    AND NOT (
+                        $4::BOOLEAN
+                        AND (e.wallet_transfer_id IS NOT NULL OR e.category_id = ANY($5::INT[]))
+                    )
             ),
             filtered_income AS (
                 SELECT amount, date, wallet_id
                 FROM income
-                WHERE date BETWEEN $1 AND $2
+                WHERE date BETWEEN $1 AND $2 AND user_id IS NOT DISTINCT FROM $6
             ),
             total_expense AS (
-                SELECT COALESCE(SUM(fe.amount), 0) AS amount
+                SELECT COALESCE(SUM(fe.amount), 0) AS amount, COUNT(*) AS count
                 FROM filtered_expense fe
             ),
+            total_transfer_fee AS (
+                SELECT COALESCE(SUM(CASE WHEN e.is_refund THEN -e.amount ELSE e.amount END), 0) AS amount
+                FROM expense e
+                JOIN category c ON e.category_id = c.id
+                WHERE
+                    e.date BETWEEN $1::DATE AND $2::DATE
+                    AND e.user_id IS NOT DISTINCT FROM $6
+                    AND ($7::INT IS NULL OR c.parent_category_id = $7)
+                    AND (e.wallet_transfer_id IS NOT NULL OR e.category_id = ANY($5::INT[]))
+            ),
             total_income AS (
-                SELECT COALESCE(SUM(amount), 0) AS amount
+                SELECT COALESCE(SUM(amount), 0) AS amount, COUNT(*) AS count
                 FROM filtered_income
             ),
+            filtered_transfer AS (
+                -- The fee of a transfer is the amount of its fee expense, if any.
+                SELECT
+                    wt.amount,
+                    (SELECT SUM(e.amount) FROM expense e WHERE e.wallet_transfer_id = wt.id) AS fee
+                FROM wallet_transfer wt
+                JOIN wallet sw ON wt.source_wallet_id = sw.id
+                WHERE wt.date BETWEEN $1::DATE AND $2::DATE AND sw.user_id IS NOT DISTINCT FROM $6
+            ),
+            transfer_net_flow AS (
+                SELECT
+                    COALESCE(SUM(amount), 0) AS volume,
+                    COALESCE(SUM(fee), 0) AS fees,
+                    COUNT(*) AS count
+                FROM filtered_transfer
+            ),
+            range_month AS (
+                -- The number of calendar months touched by the date range, to scale the monthly budgets.
+                SELECT (
+                    (EXTRACT(YEAR FROM $2::DATE) - EXTRACT(YEAR FROM $1::DATE)) * 12
+                    + EXTRACT(MONTH FROM $2::DATE) - EXTRACT(MONTH FROM $1::DATE) + 1
+                )::BIGINT AS count
+            ),
             category_summary AS (
                 SELECT 
                     pc.id AS parent_id,
                     c.name,
-                    COALESCE(SUM(fe.amount), 0) AS amount
+                    COALESCE(SUM(fe.amount), 0) AS amount,
+                    cb.monthly_limit * (SELECT count FROM range_month) AS budget
                 FROM filtered_expense fe
                 JOIN category c ON fe.category_id = c.id
                 JOIN parent_category pc ON c.parent_category_id = pc.id
-                GROUP BY pc.id, c.name
+                LEFT JOIN category_budget cb
+                    ON cb.category_id = c.id AND cb.user_id IS NOT DISTINCT FROM $6
+                GROUP BY pc.id, c.id, c.name, cb.monthly_limit
             ),
             parent_category_summary AS (
                 SELECT 
@@ -79,23 +210,30 @@ impl RepositoryOperation for SummaryRepository {
                         JSONB_AGG(
                             JSONB_BUILD_OBJECT(
                                 'name', cs.name,
-                                'amount', cs.amount
+                                'amount', cs.amount,
+                                'budget', cs.budget
                             ) ORDER BY cs.amount DESC
                         ),
                         '[]'
                     ) AS categories
                 FROM category_summary cs
                 JOIN parent_category pc ON cs.parent_id = pc.id
-                WHERE cs.amount > 0
+                WHERE cs.amount != 0
                 GROUP BY pc.id, pc.name
             ),
+            priority_level AS (
+                -- Every configured level is included, so that levels without expenses are summarized as zero.
+                SELECT GENERATE_SERIES(0, $8::INT) AS level
+                UNION
+                SELECT priority FROM filtered_expense
+            ),
             priority_summary AS (
                 SELECT 
-                    priority AS level,
-                    COALESCE(SUM(amount), 0) AS amount
-                FROM filtered_expense
-                GROUP BY priority
-                ORDER BY amount DESC
+                    pl.level,
+                    COALESCE(SUM(fe.amount), 0) AS amount
+                FROM priority_level pl
+                LEFT JOIN filtered_expense fe ON fe.priority = pl.level
+                GROUP BY pl.level
             ),
             wallet_summary AS (
                 SELECT 
@@ -103,14 +241,16 @@ impl RepositoryOperation for SummaryRepository {
                     COALESCE(SUM(fi.amount), 0) AS amount
                 FROM filtered_income fi
                 JOIN wallet w ON fi.wallet_id = w.id
-                GROUP BY w.name, fi.amount
-                ORDER BY fi.amount DESC
+                GROUP BY w.id, w.name
+                ORDER BY amount DESC
             )
             SELECT 
                 JSONB_BUILD_OBJECT(
                     'amount', te.amount,
-                    'group_summary', JSONB_BUILD_OBJECT(
-                        'parent_categories', (
+                    'expenseCount', te.count,
+                    'transferFees', ttf.amount,
+                    'groupSummary', JSONB_BUILD_OBJECT(
+                        'parentCategories', (
                             SELECT COALESCE(
                                 JSONB_AGG(
                                     JSONB_BUILD_OBJECT(
@@ -129,7 +269,7 @@ impl RepositoryOperation for SummaryRepository {
                                     JSONB_BUILD_OBJECT(
                                         'level', level,
                                         'amount', amount
-                                    ) ORDER BY amount DESC
+                                    ) ORDER BY amount DESC, level
                                 ),
                                 '[]'
                             )
@@ -139,7 +279,8 @@ impl RepositoryOperation for SummaryRepository {
                 ) AS "expense!: sqlx::types::Json<ExpenseSummary>",
                 JSONB_BUILD_OBJECT(
                     'amount', ti.amount,
-                    'group_summary', JSONB_BUILD_OBJECT(
+                    'incomeCount', ti.count,
+                    'groupSummary', JSONB_BUILD_OBJECT(
                         'wallets', (
                             SELECT COALESCE(
                                 JSONB_AGG(
@@ -153,18 +294,482 @@ impl RepositoryOperation for SummaryRepository {
                             FROM wallet_summary
                         )
                     )
-                ) AS "income!: sqlx::types::Json<IncomeSummary>"
+                ) AS "income!: sqlx::types::Json<IncomeSummary>",
+                JSONB_BUILD_OBJECT(
+                    'volume', tnf.volume,
+                    'fees', tnf.fees,
+                    'transferCount', tnf.count
+                ) AS "transfer_net_flow!: sqlx::types::Json<TransferNetFlow>"
             FROM
                 total_income ti,
-                total_expense te
+                total_expense te,
+                total_transfer_fee ttf,
+                transfer_net_flow tnf
             "#,
             request.start_date,
             request.end_date,
             &request.exclude_category_ids,
+            request.exclude_transfer_fees,
+            &self.transfer_fee_category_ids,
+            user_id,
+            request.parent_category_id,
+            self.max_priority,
         )
         .fetch_one(&*self.pool)
+        .timed("summary.generate_raw")
+        .await?;
+
+        summary.expense.flag_over_budget();
+
+        Ok(summary)
+    }
+
+    async fn generate_timeseries(
+        &self,
+        request: &GenerateTimeseriesRequest,
+        user_id: Option<&str>,
+    ) -> Result<Vec<TimeseriesTotal>, sqlx::Error> {
+        let totals = sqlx::query_as!(
+            TimeseriesTotal,
+            r#"
+            SELECT
+                bucket AS "bucket!",
+                SUM(expense)::BIGINT AS "expense!",
+                SUM(income)::BIGINT AS "income!"
+            FROM (
+                SELECT
+                    DATE_TRUNC($3, date::TIMESTAMP)::DATE AS bucket,
+                    CASE WHEN is_refund THEN -amount ELSE amount END AS expense,
+                    0 AS income
+                FROM expense
+                WHERE date BETWEEN $1 AND $2 AND user_id IS NOT DISTINCT FROM $4
+                UNION ALL
+                SELECT DATE_TRUNC($3, date::TIMESTAMP)::DATE AS bucket, 0 AS expense, amount AS income
+                FROM income
+                WHERE date BETWEEN $1 AND $2 AND user_id IS NOT DISTINCT FROM $4
+            ) AS records
+            GROUP BY bucket
+            ORDER BY bucket
+            "#,
+            request.start_date,
+            request.end_date,
+            request.granularity.unit(),
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("summary.generate_timeseries")
         .await?;
 
-        Ok(query)
+        Ok(totals)
+    }
+}
+
+#[async_trait]
+impl RepositoryOperation for CachedSummaryRepository {
+    async fn generate_raw(
+        &self,
+        request: &GenerateSummaryRequest,
+        user_id: Option<&str>,
+    ) -> Result<ShowSummary, sqlx::Error> {
+        let key = SummaryCacheKey::new(request, user_id);
+
+        if let Some((generated_at, summary)) = self.entries.lock().unwrap().get(&key)
+            && generated_at.elapsed() < self.ttl
+        {
+            return Ok(summary.clone());
+        }
+
+        let summary = self.inner.generate_raw(request, user_id).await?;
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (generated_at, _)| generated_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), summary.clone()));
+
+        Ok(summary)
+    }
+
+    /// Time series are not cached.
+    async fn generate_timeseries(
+        &self,
+        request: &GenerateTimeseriesRequest,
+        user_id: Option<&str>,
+    ) -> Result<Vec<TimeseriesTotal>, sqlx::Error> {
+        self.inner.generate_timeseries(request, user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_MAX_PRIORITY;
+    use crate::dtos::query_result::{ExpenseGroupedSummary, IncomeGroupedSummary};
+    use serial_test::serial;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Mock repository counting how many times the summary is generated.
+    struct CountingSummaryRepository {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RepositoryOperation for CountingSummaryRepository {
+        async fn generate_raw(
+            &self,
+            _request: &GenerateSummaryRequest,
+            _user_id: Option<&str>,
+        ) -> Result<ShowSummary, sqlx::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
+            Ok(ShowSummary {
+                expense: sqlx::types::Json(ExpenseSummary {
+                    amount: 5000,
+                    expense_count: 6,
+                    transfer_fees: 0,
+                    group_summary: ExpenseGroupedSummary {
+                        parent_categories: vec![],
+                        priorities: vec![],
+                    },
+                }),
+                income: sqlx::types::Json(IncomeSummary {
+                    amount: 8000,
+                    income_count: 2,
+                    group_summary: IncomeGroupedSummary { wallets: vec![] },
+                }),
+                transfer_net_flow: sqlx::types::Json(TransferNetFlow {
+                    volume: 0,
+                    fees: 0,
+                    transfer_count: 0,
+                }),
+            })
+        }
+
+        async fn generate_timeseries(
+            &self,
+            _request: &GenerateTimeseriesRequest,
+            _user_id: Option<&str>,
+        ) -> Result<Vec<TimeseriesTotal>, sqlx::Error> {
+            Ok(vec![])
+        }
+    }
+
+    fn setup(ttl: Duration) -> (Arc<CountingSummaryRepository>, CachedSummaryRepository) {
+        let inner = Arc::new(CountingSummaryRepository {
+            calls: AtomicUsize::new(0),
+        });
+        let cached = CachedSummaryRepository::new(inner.clone(), ttl);
+
+        (inner, cached)
+    }
+
+    fn summary_request(exclude_category_ids: Vec<i32>) -> GenerateSummaryRequest {
+        GenerateSummaryRequest {
+            start_date: Date::from_calendar_date(2025, time::Month::March, 1).unwrap(),
+            end_date: Date::from_calendar_date(2025, time::Month::April, 1).unwrap(),
+            exclude_category_ids,
+            exclude_transfer_fees: true,
+            reject_future_dates: false,
+            parent_category_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_requests_within_ttl_hit_repository_once() {
+        let (inner, cached) = setup(Duration::from_secs(60));
+
+        cached
+            .generate_raw(&summary_request(vec![1, 2]), None)
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&summary_request(vec![1, 2]), None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_equivalent_requests_share_cache_entry() {
+        let (inner, cached) = setup(Duration::from_secs(60));
+
+        cached
+            .generate_raw(&summary_request(vec![2, 1]), None)
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&summary_request(vec![1, 2, 2]), None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_different_requests_hit_repository() {
+        let (inner, cached) = setup(Duration::from_secs(60));
+
+        cached
+            .generate_raw(&summary_request(vec![1]), None)
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&summary_request(vec![2]), None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_fee_flag_is_part_of_cache_key() {
+        let (inner, cached) = setup(Duration::from_secs(60));
+        let including_transfer_fees = GenerateSummaryRequest {
+            exclude_transfer_fees: false,
+            ..summary_request(vec![1])
+        };
+
+        cached
+            .generate_raw(&summary_request(vec![1]), None)
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&including_transfer_fees, None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_parent_category_is_part_of_cache_key() {
+        let (inner, cached) = setup(Duration::from_secs(60));
+        let single_parent_category = GenerateSummaryRequest {
+            parent_category_id: Some(1),
+            ..summary_request(vec![1])
+        };
+
+        cached
+            .generate_raw(&summary_request(vec![1]), None)
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&single_parent_category, None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_different_users_hit_repository() {
+        let (inner, cached) = setup(Duration::from_secs(60));
+
+        cached
+            .generate_raw(&summary_request(vec![1]), Some("alice"))
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&summary_request(vec![1]), Some("bob"))
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_hits_repository_again() {
+        let (inner, cached) = setup(Duration::ZERO);
+
+        cached
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+        cached
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_generate_raw_subtracts_refunds_from_category(pool: PgPool) {
+        // Prepare
+        sqlx::query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority, is_refund)
+            VALUES (1, 1, 3000, '2025-03-10', 0, FALSE), (1, 1, 500, '2025-03-12', 0, TRUE)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = SummaryRepository::new(Arc::new(pool), vec![], DEFAULT_MAX_PRIORITY);
+
+        // Execute
+        let summary = repository
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+
+        // Assert
+        let parent_categories = &summary.expense.group_summary.parent_categories;
+
+        assert_eq!(summary.expense.amount, 2500);
+        assert_eq!(parent_categories.len(), 1);
+        assert_eq!(parent_categories[0].amount, 2500);
+        assert_eq!(parent_categories[0].categories.len(), 1);
+        assert_eq!(parent_categories[0].categories[0].name, "Groceries");
+        assert_eq!(parent_categories[0].categories[0].amount, 2500);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_generate_raw_sums_incomes_per_wallet(pool: PgPool) {
+        // Prepare
+        sqlx::query!(
+            r#"
+            INSERT INTO income (wallet_id, amount, date)
+            VALUES (1, 1000, '2025-03-10'), (1, 2500, '2025-03-12')
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = SummaryRepository::new(Arc::new(pool), vec![], DEFAULT_MAX_PRIORITY);
+
+        // Execute
+        let summary = repository
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+
+        // Assert
+        let wallets = &summary.income.group_summary.wallets;
+
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].name, "Cash");
+        assert_eq!(wallets[0].amount, 3500);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_generate_raw_flags_over_budget_categories(pool: PgPool) {
+        // Prepare
+        sqlx::query!(
+            "INSERT INTO category_budget (category_id, monthly_limit) VALUES (1, 1000), (2, 1000)"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            VALUES (1, 1, 2500, '2025-03-10', 0), (2, 1, 2000, '2025-03-12', 0)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = SummaryRepository::new(Arc::new(pool), vec![], DEFAULT_MAX_PRIORITY);
+
+        // Execute
+        let summary = repository
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+
+        // Assert
+        let categories = &summary.expense.group_summary.parent_categories[0].categories;
+        let category = |name: &str| {
+            categories
+                .iter()
+                .find(|category| category.name == name)
+                .unwrap()
+        };
+
+        // The request touches March and April, so each monthly limit counts twice.
+        assert_eq!(category("Groceries").budget, Some(2000));
+        assert!(category("Groceries").over_budget);
+        assert_eq!(category("Restaurant").budget, Some(2000));
+        assert!(!category("Restaurant").over_budget);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_generate_raw_summarizes_empty_priority_levels_as_zero(pool: PgPool) {
+        // Prepare
+        sqlx::query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            VALUES (1, 1, 3000, '2025-03-10', 0)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = SummaryRepository::new(Arc::new(pool), vec![], DEFAULT_MAX_PRIORITY);
+
+        // Execute
+        let summary = repository
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+
+        // Assert
+        let priorities = summary
+            .expense
+            .group_summary
+            .priorities
+            .iter()
+            .map(|priority| (priority.level, priority.amount))
+            .collect::<Vec<_>>();
+
+        assert_eq!(priorities, vec![(0, 3000), (1, 0), (2, 0)]);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_generate_raw_sums_fees_of_transfers_in_range(pool: PgPool) {
+        // Prepare
+        let transfer_ids = sqlx::query_scalar!(
+            r#"
+            INSERT INTO wallet_transfer (source_wallet_id, target_wallet_id, amount, date)
+            VALUES (1, 2, 1000, '2025-03-05'), (1, 2, 500, '2025-06-01')
+            RETURNING id
+            "#
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority, wallet_transfer_id)
+            VALUES (1, 1, 100, '2025-03-05', 0, $1), (1, 1, 50, '2025-06-01', 0, $2)
+            "#,
+            transfer_ids[0],
+            transfer_ids[1],
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = SummaryRepository::new(Arc::new(pool), vec![], DEFAULT_MAX_PRIORITY);
+
+        // Execute
+        let summary = repository
+            .generate_raw(&summary_request(vec![]), None)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(summary.transfer_net_flow.volume, 1000);
+        assert_eq!(summary.transfer_net_flow.fees, 100);
+        assert_eq!(summary.transfer_net_flow.transfer_count, 1);
     }
 }
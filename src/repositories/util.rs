@@ -1,70 +1,248 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
-use std::sync::Arc;
+use std::{
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use crate::dtos::query_result::{ParentCategory, SimpleEntity, Tag};
+use crate::{
+    common::{env, sql::escape_like, timing::TimedExt},
+    dtos::{
+        SortDirection,
+        query_result::{CategoryBudget, CategoryWithParent, ParentCategory, SimpleEntity, Tag},
+        util::{IndexCategoriesQuery, IndexParentCategoriesQuery, IndexTagsQuery},
+    },
+};
+
+/// The outcome of moving a category to a different parent category.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReparentOutcome {
+    /// The category was moved.
+    Moved,
+    /// The category was left untouched because the parent category doesn't exist.
+    UnknownParent,
+}
+
+/// The key a listing is ordered by, before the direction requested by the client is applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    /// Orders by name, case-insensitively.
+    Name,
+    /// Orders by ID, i.e. by creation.
+    Id,
+    /// Orders the important records first, then by name. Only supported by tags.
+    Importance,
+}
+
+impl FromStr for SortKey {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "name" => Ok(Self::Name),
+            "id" => Ok(Self::Id),
+            "importance" => Ok(Self::Importance),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The default ordering of each listing served by the repository.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ListingSort {
+    /// The ordering of categories, by name unless configured otherwise.
+    pub categories: SortKey,
+    /// The ordering of parent categories, by name unless configured otherwise.
+    pub parent_categories: SortKey,
+    /// The ordering of tags, by importance unless configured otherwise.
+    pub tags: SortKey,
+}
+
+impl Default for ListingSort {
+    fn default() -> Self {
+        Self {
+            categories: SortKey::Name,
+            parent_categories: SortKey::Name,
+            tags: SortKey::Importance,
+        }
+    }
+}
+
+impl ListingSort {
+    /// Reads the ordering of each listing from the `CATEGORY_SORT_DEFAULT`, `PARENT_CATEGORY_SORT_DEFAULT`,
+    /// and `TAG_SORT_DEFAULT` environment variables, falling back to the default ordering of each listing.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        Self {
+            categories: sort_key_or("CATEGORY_SORT_DEFAULT", default.categories, false),
+            parent_categories: sort_key_or(
+                "PARENT_CATEGORY_SORT_DEFAULT",
+                default.parent_categories,
+                false,
+            ),
+            tags: sort_key_or("TAG_SORT_DEFAULT", default.tags, true),
+        }
+    }
+}
+
+/// Reads the sort key from the given environment variable.
+/// Falls back to `default` when the variable is invalid or sorts by importance a listing without importance.
+fn sort_key_or(key: &str, default: SortKey, supports_importance: bool) -> SortKey {
+    let sort_key = env::parse_or(key, default);
+
+    if sort_key == SortKey::Importance && !supports_importance {
+        tracing::warn!("{key} cannot sort by importance, falling back to the default");
+        return default;
+    }
+
+    sort_key
+}
 
 /// Repository to interact with other supporting tables in the database.
 /// This includes tables like `category`, `tag`, and `wallet`.
 pub struct Repository {
     /// The PostgreSQL connection pool.
     pool: Arc<PgPool>,
+    /// The default ordering of the listings.
+    sort: ListingSort,
 }
 
 impl Repository {
     /// Creates a new `UtilRepository` instance.
-    pub fn new(pool: Arc<PgPool>) -> Self {
-        Self { pool }
+    pub fn new(pool: Arc<PgPool>, sort: ListingSort) -> Self {
+        Self { pool, sort }
     }
 }
 
 #[async_trait]
 pub trait RepositoryOperation: Send + Sync {
     /// Finds multiple categories from the database.
-    /// The result is paginated based on the provided offset and limit.
+    /// The result is filtered and paginated based on the provided query.
     async fn find_many_categories(
         &self,
-        offset: i64,
-        limit: i64,
+        query: &IndexCategoriesQuery,
     ) -> Result<Vec<SimpleEntity>, sqlx::Error>;
 
+    /// Finds multiple categories along with their parent category from the database.
+    /// The result is filtered and paginated based on the provided query.
+    async fn find_many_categories_with_parent(
+        &self,
+        query: &IndexCategoriesQuery,
+    ) -> Result<Vec<CategoryWithParent>, sqlx::Error>;
+
     /// Finds multiple parent categories and their children from the database.
-    /// The result is paginated based on the provided offset and limit.
+    /// The result is filtered and paginated based on the provided query.
+    /// Pagination applies to the parent categories, whose children are always fully included.
     async fn find_many_parent_categories(
         &self,
-        offset: i64,
-        limit: i64,
+        query: &IndexParentCategoriesQuery,
     ) -> Result<Vec<ParentCategory>, sqlx::Error>;
 
     /// Finds multiple tags from the database.
-    /// The result is paginated based on the provided offset and limit.
-    async fn find_many_tags(
+    /// The result is filtered and paginated based on the provided query.
+    async fn find_many_tags(&self, query: &IndexTagsQuery) -> Result<Vec<Tag>, sqlx::Error>;
+
+    /// Moves a category to a different parent category.
+    /// Returns `RowNotFound` if the category doesn't exist.
+    async fn reparent_category(
+        &self,
+        id: i32,
+        parent_category_id: i32,
+    ) -> Result<ReparentOutcome, sqlx::Error>;
+
+    /// Marks the tags with the given IDs as important or not.
+    /// Returns the number of tags updated, unknown IDs are ignored.
+    async fn set_importance_bulk(
         &self,
-        mark_important_value: Option<bool>,
-        offset: i64,
-        limit: i64,
-    ) -> Result<Vec<Tag>, sqlx::Error>;
+        ids: &[i32],
+        is_important: bool,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// Finds the monthly budget of a category set by the given user.
+    /// Returns `RowNotFound` if no budget is set.
+    async fn find_category_budget(
+        &self,
+        category_id: i32,
+        user_id: Option<&str>,
+    ) -> Result<CategoryBudget, sqlx::Error>;
+
+    /// Sets the monthly budget of a category for the given user, replacing the existing one if any.
+    /// Returns `RowNotFound` if the category doesn't exist.
+    async fn upsert_category_budget(
+        &self,
+        category_id: i32,
+        monthly_limit: i32,
+        user_id: Option<&str>,
+    ) -> Result<CategoryBudget, sqlx::Error>;
+
+    /// Runs a trivial query against the database and returns its round-trip time.
+    async fn ping(&self) -> Result<Duration, sqlx::Error>;
 }
 
 #[async_trait]
 impl RepositoryOperation for Repository {
     async fn find_many_categories(
         &self,
-        offset: i64,
-        limit: i64,
+        query: &IndexCategoriesQuery,
     ) -> Result<Vec<SimpleEntity>, sqlx::Error> {
         let categories = sqlx::query_as!(
             SimpleEntity,
             r#"
             SELECT id, name
             FROM category
-            ORDER BY LOWER(name)
-            OFFSET $1 LIMIT $2
+            WHERE $1::INT IS NULL OR parent_category_id = $1
+            ORDER BY
+                CASE WHEN $5 AND $4 THEN id END DESC,
+                CASE WHEN $5 THEN id END,
+                CASE WHEN $4 THEN LOWER(name) END DESC,
+                LOWER(name)
+            OFFSET $2 LIMIT $3
+            "#,
+            query.parent_category_id,
+            query.pagination.offset(),
+            query.pagination.limit(),
+            SortDirection::is_descending(query.sort_dir),
+            self.sort.categories == SortKey::Id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("util.find_many_categories")
+        .await?;
+
+        Ok(categories)
+    }
+
+    async fn find_many_categories_with_parent(
+        &self,
+        query: &IndexCategoriesQuery,
+    ) -> Result<Vec<CategoryWithParent>, sqlx::Error> {
+        let categories = sqlx::query_as!(
+            CategoryWithParent,
+            r#"
+            SELECT
+                c.id,
+                c.name,
+                pc.id AS parent_category_id,
+                pc.name AS parent_name
+            FROM category c
+            JOIN parent_category pc ON c.parent_category_id = pc.id
+            WHERE $1::INT IS NULL OR c.parent_category_id = $1
+            ORDER BY
+                CASE WHEN $5 AND $4 THEN c.id END DESC,
+                CASE WHEN $5 THEN c.id END,
+                CASE WHEN $4 THEN LOWER(c.name) END DESC,
+                LOWER(c.name)
+            OFFSET $2 LIMIT $3
             "#,
-            offset,
-            limit,
+            query.parent_category_id,
+            query.pagination.offset(),
+            query.pagination.limit(),
+            SortDirection::is_descending(query.sort_dir),
+            self.sort.categories == SortKey::Id,
         )
         .fetch_all(&*self.pool)
+        .timed("util.find_many_categories_with_parent")
         .await?;
 
         Ok(categories)
@@ -72,8 +250,7 @@ impl RepositoryOperation for Repository {
 
     async fn find_many_parent_categories(
         &self,
-        offset: i64,
-        limit: i64,
+        query: &IndexParentCategoriesQuery,
     ) -> Result<Vec<ParentCategory>, sqlx::Error> {
         let parent_categories = sqlx::query_as!(
             ParentCategory,
@@ -96,41 +273,256 @@ impl RepositoryOperation for Repository {
                 WHERE
                     c.parent_category_id = pc.id
             ) AS c ON TRUE
+            WHERE
+                $1::TEXT IS NULL OR pc.name ILIKE '%' || $1 || '%'
             ORDER BY
+                CASE WHEN $4 THEN pc.id END,
                 pc.name
-            OFFSET $1 LIMIT $2
+            OFFSET $2 LIMIT $3
             "#,
-            offset,
-            limit,
+            query.q.as_deref().map(escape_like),
+            query.pagination.offset(),
+            query.pagination.limit(),
+            self.sort.parent_categories == SortKey::Id,
         )
         .fetch_all(&*self.pool)
+        .timed("util.find_many_parent_categories")
         .await?;
 
         Ok(parent_categories)
     }
 
-    async fn find_many_tags(
-        &self,
-        mark_important_value: Option<bool>,
-        offset: i64,
-        limit: i64,
-    ) -> Result<Vec<Tag>, sqlx::Error> {
+    async fn find_many_tags(&self, query: &IndexTagsQuery) -> Result<Vec<Tag>, sqlx::Error> {
         let tags = sqlx::query_as!(
             Tag,
             r#"
             SELECT id, name, is_important
             FROM tag
-            WHERE $1::BOOLEAN IS NULL OR is_important = $1
-            ORDER BY (CASE WHEN is_important IS true THEN 0 ELSE 1 END), LOWER(name)
-            OFFSET $2 LIMIT $3
+            WHERE
+                ($1::BOOLEAN IS NULL OR is_important = $1)
+                AND ($2::TEXT IS NULL OR name ILIKE '%' || $2 || '%')
+            ORDER BY
+                CASE WHEN $7 THEN (CASE WHEN is_important IS true THEN 0 ELSE 1 END) END,
+                CASE WHEN $6 AND $5 THEN id END DESC,
+                CASE WHEN $6 THEN id END,
+                CASE WHEN $5 THEN LOWER(name) END DESC,
+                LOWER(name)
+            OFFSET $3 LIMIT $4
             "#,
-            mark_important_value,
-            offset,
-            limit,
+            query.mark_important_value,
+            query.q.as_deref().map(escape_like),
+            query.pagination.offset(),
+            query.pagination.limit(),
+            SortDirection::is_descending(query.sort_dir),
+            self.sort.tags == SortKey::Id,
+            self.sort.tags == SortKey::Importance,
         )
         .fetch_all(&*self.pool)
+        .timed("util.find_many_tags")
         .await?;
 
         Ok(tags)
     }
+
+    async fn reparent_category(
+        &self,
+        id: i32,
+        parent_category_id: i32,
+    ) -> Result<ReparentOutcome, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE category SET parent_category_id = $2 WHERE id = $1",
+            id,
+            parent_category_id,
+        )
+        .execute(&*self.pool)
+        .timed("util.reparent_category")
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(sqlx::Error::RowNotFound),
+            Ok(_) => Ok(ReparentOutcome::Moved),
+            Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+                Ok(ReparentOutcome::UnknownParent)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn set_importance_bulk(
+        &self,
+        ids: &[i32],
+        is_important: bool,
+    ) -> Result<u64, sqlx::Error> {
+        let rows_affected = sqlx::query!(
+            "UPDATE tag SET is_important = $2 WHERE id = ANY($1)",
+            ids,
+            is_important,
+        )
+        .execute(&*self.pool)
+        .timed("util.set_importance_bulk")
+        .await?
+        .rows_affected();
+
+        Ok(rows_affected)
+    }
+
+    async fn find_category_budget(
+        &self,
+        category_id: i32,
+        user_id: Option<&str>,
+    ) -> Result<CategoryBudget, sqlx::Error> {
+        let budget = sqlx::query_as!(
+            CategoryBudget,
+            r#"
+            SELECT category_id, monthly_limit
+            FROM category_budget
+            WHERE category_id = $1 AND user_id IS NOT DISTINCT FROM $2
+            "#,
+            category_id,
+            user_id,
+        )
+        .fetch_one(&*self.pool)
+        .timed("util.find_category_budget")
+        .await?;
+
+        Ok(budget)
+    }
+
+    async fn upsert_category_budget(
+        &self,
+        category_id: i32,
+        monthly_limit: i32,
+        user_id: Option<&str>,
+    ) -> Result<CategoryBudget, sqlx::Error> {
+        let result = sqlx::query_as!(
+            CategoryBudget,
+            r#"
+            INSERT INTO category_budget (category_id, monthly_limit, user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT ON CONSTRAINT category_budget_category_user_unique
+            DO UPDATE SET monthly_limit = EXCLUDED.monthly_limit
+            RETURNING category_id, monthly_limit
+            "#,
+            category_id,
+            monthly_limit,
+            user_id,
+        )
+        .fetch_one(&*self.pool)
+        .timed("util.upsert_category_budget")
+        .await;
+
+        match result {
+            Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+                Err(sqlx::Error::RowNotFound)
+            }
+            result => result,
+        }
+    }
+
+    async fn ping(&self) -> Result<Duration, sqlx::Error> {
+        let started_at = Instant::now();
+
+        sqlx::query_scalar!(r#"SELECT 1 AS "one!""#)
+            .fetch_one(&*self.pool)
+            .await?;
+
+        Ok(started_at.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::Pagination;
+    use serial_test::serial;
+
+    fn clear_sort_env() {
+        for key in [
+            "CATEGORY_SORT_DEFAULT",
+            "PARENT_CATEGORY_SORT_DEFAULT",
+            "TAG_SORT_DEFAULT",
+        ] {
+            unsafe { std::env::remove_var(key) };
+        }
+    }
+
+    /// Lists the names of every tag with the default sort, in the given direction.
+    async fn tag_names(repository: &Repository, sort_dir: SortDirection) -> Vec<String> {
+        let query = IndexTagsQuery {
+            mark_important_value: None,
+            q: None,
+            sort_dir: Some(sort_dir),
+            pagination: Pagination::default(),
+        };
+
+        repository
+            .find_many_tags(&query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect()
+    }
+
+    #[test]
+    #[serial]
+    fn test_listing_sort_from_env_defaults() {
+        clear_sort_env();
+
+        assert_eq!(ListingSort::from_env(), ListingSort::default());
+    }
+
+    #[test]
+    #[serial]
+    fn test_listing_sort_from_env_non_default() {
+        clear_sort_env();
+        unsafe {
+            std::env::set_var("TAG_SORT_DEFAULT", "id");
+            std::env::set_var("PARENT_CATEGORY_SORT_DEFAULT", " id ");
+        }
+
+        let sort = ListingSort::from_env();
+
+        assert_eq!(sort.categories, SortKey::Name);
+        assert_eq!(sort.parent_categories, SortKey::Id);
+        assert_eq!(sort.tags, SortKey::Id);
+
+        clear_sort_env();
+    }
+
+    #[test]
+    #[serial]
+    fn test_listing_sort_from_env_invalid_values() {
+        clear_sort_env();
+        unsafe {
+            std::env::set_var("CATEGORY_SORT_DEFAULT", "importance");
+            std::env::set_var("TAG_SORT_DEFAULT", "LOWER(name)");
+        }
+
+        assert_eq!(ListingSort::from_env(), ListingSort::default());
+
+        clear_sort_env();
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_find_many_tags_keeps_important_tags_first(pool: PgPool) {
+        // Prepare
+        sqlx::query!(
+            "INSERT INTO tag (name, is_important) VALUES ('Birthday', true), ('Zoo', false)"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = Repository::new(Arc::new(pool), ListingSort::default());
+
+        // Execute
+        let ascending = tag_names(&repository, SortDirection::Asc).await;
+        let descending = tag_names(&repository, SortDirection::Desc).await;
+
+        // Assert
+        assert_eq!(ascending, vec!["Birthday", "Trip", "Gift", "Zoo"]);
+        assert_eq!(descending, vec!["Trip", "Birthday", "Zoo", "Gift"]);
+    }
 }
@@ -1,9 +1,15 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
 use std::sync::Arc;
+use time::Date;
 
 use crate::{
-    dtos::query_result::SimpleEntity,
+    common::timing::TimedExt,
+    dtos::{
+        SortDirection,
+        query_result::{IndexWalletTransferElement, SimpleEntity, WalletTransferSummaryElement},
+        wallet::{IndexWalletTransferQuery, IndexWalletsQuery},
+    },
     entities::wallet::{SaveWalletTransfer, SaveWalletTransferFee},
 };
 
@@ -21,76 +27,388 @@ impl Repository {
     }
 }
 
+/// The outcome of saving a wallet transfer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TransferOutcome {
+    /// The transfer was saved.
+    Saved,
+    /// The transfer was rejected because the source wallet doesn't have enough balance.
+    InsufficientBalance,
+    /// The transfer was rejected because an identical transfer already exists on the same date.
+    Duplicate,
+}
+
+/// The outcome of deleting a wallet.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteOutcome {
+    /// The wallet was deleted.
+    Deleted,
+    /// The wallet was left untouched because expenses, incomes or transfers still reference it.
+    InUse,
+}
+
+/// The outcome of creating a wallet.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub enum CreateOutcome {
+    /// The wallet was created.
+    Created(SimpleEntity),
+    /// The existing wallet of the user with the same name was returned instead.
+    Existing(SimpleEntity),
+    /// The wallet was left uncreated because another wallet of the user already has the name.
+    Duplicate,
+}
+
+/// The outcome of renaming a wallet.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RenameOutcome {
+    /// The wallet was renamed.
+    Renamed,
+    /// The wallet was left untouched because another wallet of the user already has the name.
+    Duplicate,
+}
+
+/// Checks whether a wallet with the given balance can cover a transfer and its fee.
+fn has_sufficient_balance(balance: i64, amount: i32, fee: i32) -> bool {
+    balance - i64::from(amount) - i64::from(fee) >= 0
+}
+
+/// Trait defining operations for the `wallet` and `wallet_transfer` tables.
+/// Every operation is scoped to the wallets owned by the given user.
+/// Wallets without an owner are only visible when no user is given.
 #[async_trait]
 pub trait RepositoryOperation: Send + Sync {
-    /// Finds multiple wallets from the database.
-    /// The result is paginated based on the provided offset and limit.
-    async fn find_many(&self, offset: i64, limit: i64) -> Result<Vec<SimpleEntity>, sqlx::Error>;
+    /// Finds multiple wallets from the database, sorted by name.
+    /// The result is sorted and paginated based on the provided query.
+    async fn find_many(
+        &self,
+        query: &IndexWalletsQuery,
+        user_id: Option<&str>,
+    ) -> Result<Vec<SimpleEntity>, sqlx::Error>;
+
+    /// Finds a specific wallet by ID from the database.
+    async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<SimpleEntity, sqlx::Error>;
+
+    /// Finds the transfers within the optional date range, ordered by date then ID.
+    /// Only transfers from a wallet owned by the given user are returned.
+    async fn find_many_transfers(
+        &self,
+        query: &IndexWalletTransferQuery,
+        user_id: Option<&str>,
+    ) -> Result<Vec<IndexWalletTransferElement>, sqlx::Error>;
+
+    /// Sums the transfers within the optional date range for each pair of source and target wallets,
+    /// ordered by the name of the source wallet then the target wallet.
+    /// The fee of a transfer is the amount of its fee expense, if any.
+    /// Only transfers from a wallet owned by the given user are summed.
+    async fn summarize_transfers(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<WalletTransferSummaryElement>, sqlx::Error>;
 
     /// Saves a record of money transfer between wallets.
     /// If a fee record is provided, the fee will be saved in the `expense` table.
+    /// If `enforce_balance` is set, the transfer is rejected when the source wallet can't cover the amount and fee.
+    /// If `reject_duplicates` is set, the transfer is rejected when an identical transfer already exists on the same date.
+    /// Transfers involving a wallet owned by another user are treated as not found.
     async fn insert_wallet_transfer_with_fee(
         &self,
         wallet_transfer_record: &SaveWalletTransfer,
         fee_record: Option<&SaveWalletTransferFee>,
+        enforce_balance: bool,
+        reject_duplicates: bool,
+        user_id: Option<&str>,
+    ) -> Result<TransferOutcome, sqlx::Error>;
+
+    /// Deletes a wallet from the database, unless expenses, incomes or transfers still reference it.
+    /// Returns `RowNotFound` if the wallet doesn't exist.
+    async fn delete(&self, id: i32, user_id: Option<&str>) -> Result<DeleteOutcome, sqlx::Error>;
+
+    /// Deletes a record of money transfer between wallets, along with its fee expense if any.
+    /// Only transfers from a wallet owned by the given user are deleted.
+    async fn delete_wallet_transfer(
+        &self,
+        id: i32,
+        user_id: Option<&str>,
     ) -> Result<(), sqlx::Error>;
+
+    /// Creates a wallet for the user, unless another wallet of the user already has the name, case-insensitively.
+    /// With `get_or_create`, the existing wallet is returned instead.
+    async fn insert(
+        &self,
+        name: &str,
+        get_or_create: bool,
+        user_id: Option<&str>,
+    ) -> Result<CreateOutcome, sqlx::Error>;
+
+    /// Renames a wallet, unless another wallet of the user already has the name, case-insensitively.
+    /// Returns `RowNotFound` if the wallet doesn't exist.
+    async fn update(
+        &self,
+        id: i32,
+        name: &str,
+        user_id: Option<&str>,
+    ) -> Result<RenameOutcome, sqlx::Error>;
 }
 
 #[async_trait]
 impl RepositoryOperation for Repository {
-    async fn find_many(&self, offset: i64, limit: i64) -> Result<Vec<SimpleEntity>, sqlx::Error> {
+    async fn find_many(
+        &self,
+        query: &IndexWalletsQuery,
+        user_id: Option<&str>,
+    ) -> Result<Vec<SimpleEntity>, sqlx::Error> {
         let wallets = sqlx::query_as!(
             SimpleEntity,
             r#"
             SELECT id, name
             FROM wallet
-            ORDER BY LOWER(name)
+            WHERE user_id IS NOT DISTINCT FROM $3
+            ORDER BY CASE WHEN $4 THEN LOWER(name) END DESC, LOWER(name)
             OFFSET $1 LIMIT $2
             "#,
-            offset,
-            limit,
+            query.pagination.offset(),
+            query.pagination.limit(),
+            user_id,
+            SortDirection::is_descending(query.sort_dir),
         )
         .fetch_all(&*self.pool)
+        .timed("wallet.find_many")
         .await?;
 
         Ok(wallets)
     }
 
+    async fn find_one(&self, id: i32, user_id: Option<&str>) -> Result<SimpleEntity, sqlx::Error> {
+        let wallet = sqlx::query_as!(
+            SimpleEntity,
+            "SELECT id, name FROM wallet WHERE id = $1 AND user_id IS NOT DISTINCT FROM $2",
+            id,
+            user_id,
+        )
+        .fetch_one(&*self.pool)
+        .timed("wallet.find_one")
+        .await?;
+
+        Ok(wallet)
+    }
+
+    async fn find_many_transfers(
+        &self,
+        query: &IndexWalletTransferQuery,
+        user_id: Option<&str>,
+    ) -> Result<Vec<IndexWalletTransferElement>, sqlx::Error> {
+        let transfers = sqlx::query_as!(
+            IndexWalletTransferElement,
+            r#"
+            SELECT
+                wt.id,
+                wt.amount,
+                TO_CHAR(wt.date, 'YYYY-MM-DD') AS "date!",
+                wt.description,
+                JSONB_BUILD_OBJECT(
+                    'id', sw.id,
+                    'name', sw.name
+                ) AS "source_wallet!: sqlx::types::Json<SimpleEntity>",
+                JSONB_BUILD_OBJECT(
+                    'id', tw.id,
+                    'name', tw.name
+                ) AS "target_wallet!: sqlx::types::Json<SimpleEntity>"
+            FROM
+                wallet_transfer wt
+            JOIN
+                wallet sw ON wt.source_wallet_id = sw.id
+            JOIN
+                wallet tw ON wt.target_wallet_id = tw.id
+            WHERE
+                ($1::DATE IS NULL OR wt.date >= $1::DATE)
+                AND ($2::DATE IS NULL OR wt.date <= $2::DATE)
+                AND sw.user_id IS NOT DISTINCT FROM $5
+            ORDER BY wt.date, wt.id
+            LIMIT $3 OFFSET $4
+            "#,
+            query.start_date,
+            query.end_date,
+            query.pagination.limit(),
+            query.pagination.offset(),
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("wallet.find_many_transfers")
+        .await?;
+
+        Ok(transfers)
+    }
+
+    async fn summarize_transfers(
+        &self,
+        start_date: Option<Date>,
+        end_date: Option<Date>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<WalletTransferSummaryElement>, sqlx::Error> {
+        let summary = sqlx::query_as!(
+            WalletTransferSummaryElement,
+            r#"
+            SELECT
+                JSONB_BUILD_OBJECT(
+                    'id', sw.id,
+                    'name', sw.name
+                ) AS "source!: sqlx::types::Json<SimpleEntity>",
+                JSONB_BUILD_OBJECT(
+                    'id', tw.id,
+                    'name', tw.name
+                ) AS "target!: sqlx::types::Json<SimpleEntity>",
+                SUM(wt.amount)::BIGINT AS "total_amount!",
+                COALESCE(SUM(f.fee), 0)::BIGINT AS "total_fee!",
+                COUNT(*) AS "count!"
+            FROM
+                wallet_transfer wt
+            JOIN
+                wallet sw ON wt.source_wallet_id = sw.id
+            JOIN
+                wallet tw ON wt.target_wallet_id = tw.id
+            LEFT JOIN (
+                SELECT wallet_transfer_id, SUM(amount) AS fee
+                FROM expense
+                WHERE wallet_transfer_id IS NOT NULL
+                GROUP BY wallet_transfer_id
+            ) f ON f.wallet_transfer_id = wt.id
+            WHERE
+                ($1::DATE IS NULL OR wt.date >= $1::DATE)
+                AND ($2::DATE IS NULL OR wt.date <= $2::DATE)
+                AND sw.user_id IS NOT DISTINCT FROM $3
+            GROUP BY sw.id, sw.name, tw.id, tw.name
+            ORDER BY LOWER(sw.name), LOWER(tw.name)
+            "#,
+            start_date,
+            end_date,
+            user_id,
+        )
+        .fetch_all(&*self.pool)
+        .timed("wallet.summarize_transfers")
+        .await?;
+
+        Ok(summary)
+    }
+
     async fn insert_wallet_transfer_with_fee(
         &self,
         wallet_transfer_record: &SaveWalletTransfer,
         fee_record: Option<&SaveWalletTransferFee>,
-    ) -> Result<(), sqlx::Error> {
-        let insert_wallet_transfer_with_fee_query = sqlx::query!(
-            r#"
-            INSERT INTO wallet_transfer (source_wallet_id, target_wallet_id, amount, date, description)
-            VALUES ($1, $2, $3, $4, $5)
-            "#,
-            wallet_transfer_record.source_wallet_id,
-            wallet_transfer_record.target_wallet_id,
-            wallet_transfer_record.amount,
-            wallet_transfer_record.date,
-            wallet_transfer_record.description,
-        );
+        enforce_balance: bool,
+        reject_duplicates: bool,
+        user_id: Option<&str>,
+    ) -> Result<TransferOutcome, sqlx::Error> {
+        async {
+            let mut tx = self.pool.begin().await?;
+
+            // Nonexistent wallets are left to the foreign key constraints.
+            let involves_foreign_wallet = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM wallet
+                    WHERE id IN ($1, $2) AND user_id IS DISTINCT FROM $3
+                ) AS "involves_foreign_wallet!"
+                "#,
+                wallet_transfer_record.source_wallet_id,
+                wallet_transfer_record.target_wallet_id,
+                user_id,
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if involves_foreign_wallet {
+                tx.rollback().await?;
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            if enforce_balance || reject_duplicates {
+                // Lock the source wallet so that concurrent guarded transfers from it are serialized.
+                sqlx::query!(
+                    "SELECT id FROM wallet WHERE id = $1 FOR UPDATE",
+                    wallet_transfer_record.source_wallet_id,
+                )
+                .fetch_optional(&mut *tx)
+                .await?;
+            }
+
+            if reject_duplicates {
+                let is_duplicate = sqlx::query_scalar!(
+                    r#"
+                    SELECT EXISTS (
+                        SELECT 1
+                        FROM wallet_transfer
+                        WHERE
+                            source_wallet_id = $1
+                            AND target_wallet_id = $2
+                            AND amount = $3
+                            AND date = $4
+                            AND description IS NOT DISTINCT FROM $5
+                    ) AS "is_duplicate!"
+                    "#,
+                    wallet_transfer_record.source_wallet_id,
+                    wallet_transfer_record.target_wallet_id,
+                    wallet_transfer_record.amount,
+                    wallet_transfer_record.date,
+                    wallet_transfer_record.description,
+                )
+                .fetch_one(&mut *tx)
+                .await?;
 
-        match fee_record {
-            None => {
-                insert_wallet_transfer_with_fee_query
-                    .execute(&*self.pool)
-                    .await?;
+                if is_duplicate {
+                    tx.rollback().await?;
+                    return Ok(TransferOutcome::Duplicate);
+                }
             }
 
-            Some(fee_record) => {
-                let mut tx = self.pool.begin().await?;
+            if enforce_balance {
+                let balance = sqlx::query_scalar!(
+                    r#"
+                    SELECT
+                        (SELECT COALESCE(SUM(amount), 0) FROM income WHERE wallet_id = $1)
+                        - (
+                            SELECT COALESCE(SUM(CASE WHEN is_refund THEN -amount ELSE amount END), 0)
+                            FROM expense
+                            WHERE wallet_id = $1
+                        )
+                        - (SELECT COALESCE(SUM(amount), 0) FROM wallet_transfer WHERE source_wallet_id = $1)
+                        + (SELECT COALESCE(SUM(amount), 0) FROM wallet_transfer WHERE target_wallet_id = $1)
+                        AS "balance!"
+                    "#,
+                    wallet_transfer_record.source_wallet_id,
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+
+                let fee = fee_record.map_or(0, |fee_record| fee_record.amount);
+                if !has_sufficient_balance(balance, wallet_transfer_record.amount, fee) {
+                    tx.rollback().await?;
+                    return Ok(TransferOutcome::InsufficientBalance);
+                }
+            }
 
-                insert_wallet_transfer_with_fee_query
-                    .execute(&mut *tx)
-                    .await?;
+            let wallet_transfer_id = sqlx::query_scalar!(
+                r#"
+                INSERT INTO wallet_transfer (source_wallet_id, target_wallet_id, amount, date, description)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id
+                "#,
+                wallet_transfer_record.source_wallet_id,
+                wallet_transfer_record.target_wallet_id,
+                wallet_transfer_record.amount,
+                wallet_transfer_record.date,
+                wallet_transfer_record.description,
+            )
+            .fetch_one(&mut *tx)
+            .await?;
 
+            if let Some(fee_record) = fee_record {
                 sqlx::query!(
                     r#"
-                    INSERT INTO expense (category_id, priority, wallet_id, amount, date, description)
-                    VALUES ($1, $2, $3, $4, $5, $6)
+                    INSERT INTO expense (category_id, priority, wallet_id, amount, date, description, wallet_transfer_id, created_by, user_id)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)
                     "#,
                     fee_record.category_id,
                     fee_record.priority,
@@ -98,13 +416,407 @@ impl RepositoryOperation for Repository {
                     fee_record.amount,
                     fee_record.date,
                     fee_record.description,
+                    wallet_transfer_id,
+                    user_id,
                 )
-                .execute(&mut *tx).await?;
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            tx.commit().await?;
+
+            Ok(TransferOutcome::Saved)
+        }
+        .timed("wallet.insert_wallet_transfer_with_fee")
+        .await
+    }
+
+    async fn delete(&self, id: i32, user_id: Option<&str>) -> Result<DeleteOutcome, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM wallet WHERE id = $1 AND user_id IS NOT DISTINCT FROM $2",
+            id,
+            user_id,
+        )
+        .execute(&*self.pool)
+        .timed("wallet.delete")
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => Err(sqlx::Error::RowNotFound),
+            Ok(_) => Ok(DeleteOutcome::Deleted),
+            Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+                Ok(DeleteOutcome::InUse)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn delete_wallet_transfer(
+        &self,
+        id: i32,
+        user_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        async {
+            let mut tx = self.pool.begin().await?;
+
+            sqlx::query!(
+                "DELETE FROM expense WHERE wallet_transfer_id = $1 AND user_id IS NOT DISTINCT FROM $2",
+                id,
+                user_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            let rows_affected = sqlx::query!(
+                r#"
+                DELETE FROM wallet_transfer wt
+                USING wallet w
+                WHERE
+                    wt.id = $1
+                    AND w.id = wt.source_wallet_id
+                    AND w.user_id IS NOT DISTINCT FROM $2
+                "#,
+                id,
+                user_id,
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
 
-                tx.commit().await?;
+            if rows_affected == 0 {
+                tx.rollback().await?;
+                return Err(sqlx::Error::RowNotFound);
             }
+
+            tx.commit().await?;
+
+            Ok(())
         }
+        .timed("wallet.delete_wallet_transfer")
+        .await
+    }
+
+    async fn insert(
+        &self,
+        name: &str,
+        get_or_create: bool,
+        user_id: Option<&str>,
+    ) -> Result<CreateOutcome, sqlx::Error> {
+        // The no-op update only returns the existing wallet when requested, otherwise nothing is returned.
+        // A freshly inserted row has no deleting transaction, which tells it apart from the existing one.
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO wallet (name, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id, LOWER(name)) DO UPDATE SET name = wallet.name WHERE $3
+            RETURNING id, name, (xmax = 0) AS "created!"
+            "#,
+            name,
+            user_id,
+            get_or_create,
+        )
+        .fetch_optional(&*self.pool)
+        .timed("wallet.insert")
+        .await?;
+
+        Ok(match row {
+            Some(row) if row.created => CreateOutcome::Created(SimpleEntity {
+                id: row.id.into(),
+                name: row.name,
+            }),
+            Some(row) => CreateOutcome::Existing(SimpleEntity {
+                id: row.id.into(),
+                name: row.name,
+            }),
+            None => CreateOutcome::Duplicate,
+        })
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        name: &str,
+        user_id: Option<&str>,
+    ) -> Result<RenameOutcome, sqlx::Error> {
+        async {
+            let mut tx = self.pool.begin().await?;
+
+            let is_duplicate = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM wallet
+                    WHERE id <> $1 AND LOWER(name) = LOWER($2) AND user_id IS NOT DISTINCT FROM $3
+                ) AS "exists!"
+                "#,
+                id,
+                name,
+                user_id,
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if is_duplicate {
+                tx.rollback().await?;
+                return Ok(RenameOutcome::Duplicate);
+            }
+
+            let rows_affected = sqlx::query!(
+                "UPDATE wallet SET name = $2 WHERE id = $1 AND user_id IS NOT DISTINCT FROM $3",
+                id,
+                name,
+                user_id,
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+            if rows_affected == 0 {
+                tx.rollback().await?;
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            tx.commit().await?;
+
+            Ok(RenameOutcome::Renamed)
+        }
+        .timed("wallet.update")
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use time::Month;
+
+    /// Builds a transfer of 1000 from the first to the second wallet of the base fixture.
+    fn transfer(description: Option<&str>) -> SaveWalletTransfer {
+        SaveWalletTransfer {
+            source_wallet_id: 1,
+            target_wallet_id: 2,
+            amount: 1000,
+            date: Date::from_calendar_date(2025, Month::May, 1).unwrap(),
+            description: description.map(str::to_string),
+        }
+    }
+
+    /// Counts the transfers recorded in the database.
+    async fn count_transfers(pool: &PgPool) -> i64 {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM wallet_transfer"#)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_creates_wallet(pool: PgPool) {
+        // Prepare
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let outcome = repository.insert("Savings", false, None).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            outcome,
+            CreateOutcome::Created(SimpleEntity {
+                id: 3,
+                name: "Savings".to_string(),
+            })
+        );
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_returns_existing_wallet_with_name_of_other_case(pool: PgPool) {
+        // Prepare
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let outcome = repository.insert("cASH", true, None).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            outcome,
+            CreateOutcome::Existing(SimpleEntity {
+                id: 1,
+                name: "Cash".to_string(),
+            })
+        );
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_rejects_duplicate_without_user(pool: PgPool) {
+        // Prepare
+        let repository = Repository::new(Arc::new(pool.clone()));
+
+        // Execute
+        // The wallets of the base fixture have no user, which only conflict because of NULLS NOT DISTINCT.
+        let outcome = repository.insert("cASH", false, None).await.unwrap();
+
+        // Assert
+        let names = sqlx::query_scalar!("SELECT name FROM wallet ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CreateOutcome::Duplicate);
+        assert_eq!(names, vec!["Cash", "Bank Account"]);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_allows_name_of_wallet_of_other_user(pool: PgPool) {
+        // Prepare
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let outcome = repository
+            .insert("Cash", false, Some("alice"))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            outcome,
+            CreateOutcome::Created(SimpleEntity {
+                id: 3,
+                name: "Cash".to_string(),
+            })
+        );
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_wallet_transfer_rejects_duplicate(pool: PgPool) {
+        // Prepare
+        let repository = Repository::new(Arc::new(pool.clone()));
+
+        // Execute
+        let first_outcome = repository
+            .insert_wallet_transfer_with_fee(&transfer(Some("Rent")), None, false, true, None)
+            .await
+            .unwrap();
+        let second_outcome = repository
+            .insert_wallet_transfer_with_fee(&transfer(Some("Rent")), None, false, true, None)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(first_outcome, TransferOutcome::Saved);
+        assert_eq!(second_outcome, TransferOutcome::Duplicate);
+        assert_eq!(count_transfers(&pool).await, 1);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_wallet_transfer_counts_refunds_in_balance(pool: PgPool) {
+        // Prepare
+        sqlx::query!("INSERT INTO income (wallet_id, amount, date) VALUES (1, 1000, '2025-04-01')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority, is_refund)
+            VALUES (1, 1, 600, '2025-04-02', 0, FALSE), (1, 1, 600, '2025-04-03', 0, TRUE)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = Repository::new(Arc::new(pool));
+
+        // Execute
+        let outcome = repository
+            .insert_wallet_transfer_with_fee(&transfer(None), None, true, false, None)
+            .await
+            .unwrap();
+
+        // Assert
+        // The refund brings the balance back to the whole income
+        assert_eq!(outcome, TransferOutcome::Saved);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_wallet_transfer_rejects_insufficient_balance(pool: PgPool) {
+        // Prepare
+        sqlx::query!("INSERT INTO income (wallet_id, amount, date) VALUES (1, 1500, '2025-04-01')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO expense (category_id, wallet_id, amount, date, priority)
+            VALUES (1, 1, 600, '2025-04-02', 0)
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = Repository::new(Arc::new(pool.clone()));
+
+        // Execute
+        let outcome = repository
+            .insert_wallet_transfer_with_fee(&transfer(None), None, true, false, None)
+            .await
+            .unwrap();
+
+        // Assert
+        // The balance of 900 doesn't cover the transfer of 1000
+        assert_eq!(outcome, TransferOutcome::InsufficientBalance);
+        assert_eq!(count_transfers(&pool).await, 0);
+    }
+
+    #[sqlx::test(fixtures("base"))]
+    #[serial]
+    async fn test_insert_wallet_transfer_with_other_description_is_not_duplicate(pool: PgPool) {
+        // Prepare
+        let repository = Repository::new(Arc::new(pool.clone()));
+
+        // Execute
+        repository
+            .insert_wallet_transfer_with_fee(&transfer(Some("Rent")), None, false, true, None)
+            .await
+            .unwrap();
+        let outcome = repository
+            .insert_wallet_transfer_with_fee(&transfer(None), None, false, true, None)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(outcome, TransferOutcome::Saved);
+        assert_eq!(count_transfers(&pool).await, 2);
+    }
+
+    #[test]
+    fn test_has_sufficient_balance_exact() {
+        assert!(has_sufficient_balance(1010, 1000, 10));
+    }
+
+    #[test]
+    fn test_has_sufficient_balance_rejects_negative_result() {
+        assert!(!has_sufficient_balance(1009, 1000, 10));
+    }
+
+    #[test]
+    fn test_has_sufficient_balance_rejects_negative_balance() {
+        assert!(!has_sufficient_balance(-500, 1, 0));
+    }
 
-        Ok(())
+    #[test]
+    fn test_has_sufficient_balance_does_not_overflow() {
+        assert!(has_sufficient_balance(
+            i64::from(i32::MAX) * 2,
+            i32::MAX,
+            i32::MAX
+        ));
     }
 }
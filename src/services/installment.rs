@@ -0,0 +1,110 @@
+use time::{Date, Duration, Month};
+
+use crate::dtos::expense::{InstallmentCadence, SaveExpense, SaveExpenseInstallments};
+
+/// Finds the date of the installment paid the given number of installments after the first one.
+/// Monthly installments keep the day of the month of the first one, clamped to the length of the month.
+fn installment_date(start_date: Date, cadence: InstallmentCadence, index: i32) -> Option<Date> {
+    match cadence {
+        InstallmentCadence::Weekly => start_date.checked_add(Duration::weeks(index.into())),
+        InstallmentCadence::Monthly => {
+            let months = i32::from(u8::from(start_date.month())) - 1 + index;
+            let year = start_date.year() + months / 12;
+            let month = Month::try_from((months % 12 + 1) as u8).ok()?;
+            let day = start_date.day().min(month.length(year));
+
+            Date::from_calendar_date(year, month, day).ok()
+        }
+    }
+}
+
+/// Splits an amount into the given number of installments.
+/// The remainder of the division is spread over the first installments, so that they add up to the amount.
+fn split_amount(amount: i32, count: i32) -> impl Iterator<Item = i32> {
+    let (quotient, remainder) = (amount / count, amount % count);
+
+    (0..count).map(move |index| quotient + i32::from(index < remainder))
+}
+
+/// Builds the expenses of an installment schedule, in chronological order.
+/// Every installment shares the description, priority, category, wallet and tags of the schedule.
+/// Returns `None` if an installment would fall past the last supported date.
+pub fn schedule(installments: &SaveExpenseInstallments) -> Option<Vec<SaveExpense>> {
+    split_amount(installments.amount, installments.count)
+        .enumerate()
+        .map(|(index, amount)| {
+            let date =
+                installment_date(installments.start_date, installments.cadence, index as i32)?;
+
+            Some(SaveExpense {
+                amount,
+                date,
+                description: installments.description.clone(),
+                priority: installments.priority,
+                category_id: installments.category_id,
+                wallet_id: installments.wallet_id,
+                tag_ids: installments.tag_ids.clone(),
+                is_refund: false,
+                external_id: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_split_amount_even() {
+        assert_eq!(
+            split_amount(3000, 3).collect::<Vec<_>>(),
+            vec![1000, 1000, 1000]
+        );
+    }
+
+    #[test]
+    fn test_split_amount_with_remainder() {
+        assert_eq!(
+            split_amount(1000, 3).collect::<Vec<_>>(),
+            vec![334, 333, 333]
+        );
+    }
+
+    #[test]
+    fn test_installment_date_weekly() {
+        assert_eq!(
+            installment_date(date!(2025 - 12 - 29), InstallmentCadence::Weekly, 1),
+            Some(date!(2026 - 01 - 05))
+        );
+    }
+
+    #[test]
+    fn test_installment_date_monthly_across_year_boundary() {
+        assert_eq!(
+            installment_date(date!(2025 - 11 - 15), InstallmentCadence::Monthly, 3),
+            Some(date!(2026 - 02 - 15))
+        );
+    }
+
+    #[test]
+    fn test_installment_date_monthly_clamps_day() {
+        assert_eq!(
+            installment_date(date!(2024 - 01 - 31), InstallmentCadence::Monthly, 1),
+            Some(date!(2024 - 02 - 29))
+        );
+        assert_eq!(
+            installment_date(date!(2024 - 01 - 31), InstallmentCadence::Monthly, 2),
+            Some(date!(2024 - 03 - 31))
+        );
+    }
+
+    #[test]
+    fn test_installment_date_past_last_supported_date() {
+        assert_eq!(
+            installment_date(date!(9999 - 12 - 01), InstallmentCadence::Monthly, 1),
+            None
+        );
+    }
+}
@@ -1 +1,3 @@
 pub mod chart;
+pub mod installment;
+pub mod timeseries;
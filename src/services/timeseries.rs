@@ -0,0 +1,183 @@
+use time::{Date, Duration, Month};
+
+use crate::dtos::{
+    query_result::TimeseriesTotal,
+    summary::{Granularity, TimeseriesPoint},
+};
+
+/// Finds the first day of the bucket containing the given date.
+/// Weeks start on Monday, following ISO 8601.
+fn bucket_start(date: Date, granularity: Granularity) -> Date {
+    match granularity {
+        Granularity::Day => date,
+        Granularity::Week => date - Duration::days(date.weekday().number_days_from_monday().into()),
+        Granularity::Month => date.replace_day(1).unwrap(),
+    }
+}
+
+/// Finds the first day of the bucket following the one starting at the given date.
+fn next_bucket_start(bucket: Date, granularity: Granularity) -> Option<Date> {
+    match granularity {
+        Granularity::Day => bucket.next_day(),
+        Granularity::Week => bucket.checked_add(Duration::weeks(1)),
+        Granularity::Month => {
+            let year = match bucket.month() {
+                Month::December => bucket.year() + 1,
+                _ => bucket.year(),
+            };
+
+            Date::from_calendar_date(year, bucket.month().next(), 1).ok()
+        }
+    }
+}
+
+/// Formats the label of the bucket starting at the given date.
+/// Weeks are labeled with their ISO week-numbering year and week number, e.g. `2025-W01`.
+fn bucket_label(bucket: Date, granularity: Granularity) -> String {
+    match granularity {
+        Granularity::Day => bucket.to_string(),
+        Granularity::Week => {
+            let (year, week, _) = bucket.to_iso_week_date();
+            format!("{year}-W{week:02}")
+        }
+        Granularity::Month => format!("{}-{:02}", bucket.year(), u8::from(bucket.month())),
+    }
+}
+
+/// Builds the time series between the given dates out of the bucket totals.
+/// Buckets without any total are included with zero amounts.
+pub fn fill_buckets(
+    start_date: Date,
+    end_date: Date,
+    granularity: Granularity,
+    totals: &[TimeseriesTotal],
+) -> Vec<TimeseriesPoint> {
+    let mut points = Vec::new();
+    let last_bucket = bucket_start(end_date, granularity);
+    let mut bucket = Some(bucket_start(start_date, granularity));
+
+    while let Some(current) = bucket.filter(|bucket| *bucket <= last_bucket) {
+        let total = totals.iter().find(|total| total.bucket == current);
+
+        points.push(TimeseriesPoint {
+            label: bucket_label(current, granularity),
+            expense: total.map_or(0, |total| total.expense),
+            income: total.map_or(0, |total| total.income),
+        });
+
+        bucket = next_bucket_start(current, granularity);
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn point(label: &str, expense: i64, income: i64) -> TimeseriesPoint {
+        TimeseriesPoint {
+            label: label.to_string(),
+            expense,
+            income,
+        }
+    }
+
+    #[test]
+    fn test_fill_buckets_weeks_across_year_boundary() {
+        let totals = vec![TimeseriesTotal {
+            bucket: date!(2024 - 12 - 30),
+            expense: 1500,
+            income: 8000,
+        }];
+
+        let points = fill_buckets(
+            date!(2024 - 12 - 25),
+            date!(2025 - 01 - 08),
+            Granularity::Week,
+            &totals,
+        );
+
+        assert_eq!(
+            points,
+            vec![
+                point("2024-W52", 0, 0),
+                point("2025-W01", 1500, 8000),
+                point("2025-W02", 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_buckets_iso_year_differs_from_calendar_year() {
+        // 2027-01-01 is a Friday, so it belongs to the last ISO week of 2026.
+        let points = fill_buckets(
+            date!(2027 - 01 - 01),
+            date!(2027 - 01 - 04),
+            Granularity::Week,
+            &[],
+        );
+
+        assert_eq!(
+            points,
+            vec![point("2026-W53", 0, 0), point("2027-W01", 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_fill_buckets_days() {
+        let totals = vec![TimeseriesTotal {
+            bucket: date!(2025 - 04 - 02),
+            expense: 200,
+            income: 0,
+        }];
+
+        let points = fill_buckets(
+            date!(2025 - 04 - 01),
+            date!(2025 - 04 - 03),
+            Granularity::Day,
+            &totals,
+        );
+
+        assert_eq!(
+            points,
+            vec![
+                point("2025-04-01", 0, 0),
+                point("2025-04-02", 200, 0),
+                point("2025-04-03", 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_buckets_months_across_year_boundary() {
+        let points = fill_buckets(
+            date!(2024 - 11 - 15),
+            date!(2025 - 01 - 15),
+            Granularity::Month,
+            &[],
+        );
+
+        assert_eq!(
+            points,
+            vec![
+                point("2024-11", 0, 0),
+                point("2024-12", 0, 0),
+                point("2025-01", 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_buckets_reversed_range() {
+        let points = fill_buckets(
+            date!(2025 - 04 - 03),
+            date!(2025 - 04 - 01),
+            Granularity::Day,
+            &[],
+        );
+
+        assert!(points.is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::repositories::{expense, income, summary, util, wallet};
+
+/// The state shared by every router, holding all repositories.
+/// Handlers extract only the repositories they need through [`FromRef`].
+#[derive(Clone)]
+pub struct AppState {
+    pub expense_repository: Arc<dyn expense::RepositoryOperation>,
+    pub income_repository: Arc<dyn income::RepositoryOperation>,
+    pub summary_repository: Arc<dyn summary::RepositoryOperation>,
+    pub util_repository: Arc<dyn util::RepositoryOperation>,
+    pub wallet_repository: Arc<dyn wallet::RepositoryOperation>,
+}
+
+impl FromRef<AppState> for Arc<dyn expense::RepositoryOperation> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.expense_repository)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn income::RepositoryOperation> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.income_repository)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn summary::RepositoryOperation> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.summary_repository)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn util::RepositoryOperation> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.util_repository)
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn wallet::RepositoryOperation> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.wallet_repository)
+    }
+}
+
+#[cfg(test)]
+impl AppState {
+    /// Builds a state holding the mock repositories of the handler tests.
+    /// Tests replace the repository under test with their own instance to inspect it.
+    pub fn mocked() -> Self {
+        use crate::handlers::{
+            expense::tests::MockExpenseRepository, income::tests::MockIncomeRepository,
+            summary::tests::MockSummaryRepository, util::tests::MockUtilRepository,
+            wallet::tests::MockWalletRepository,
+        };
+
+        Self {
+            expense_repository: MockExpenseRepository::new(),
+            income_repository: MockIncomeRepository::new(),
+            summary_repository: MockSummaryRepository::new(),
+            util_repository: MockUtilRepository::new(),
+            wallet_repository: MockWalletRepository::new(),
+        }
+    }
+}